@@ -0,0 +1,49 @@
+const std = @import("std");
+const lightmix = @import("lightmix");
+const Wave = lightmix.Wave;
+const Composer = lightmix.Composer;
+
+/// Composes 5000 events of 0.1 seconds spread over 5 minutes, and prints how long finalizing takes.
+///
+/// Run it with `zig build bench -Doptimize=ReleaseFast`.
+pub fn main() !void {
+    var gpa: std.heap.DebugAllocator(.{}) = .init;
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    var composer = Composer(f64).init(allocator, .{
+        .sample_rate = 44100,
+        .channels = 1,
+    });
+    defer composer.deinit();
+
+    // 0.1 seconds of silence
+    const data: []f64 = try allocator.alloc(f64, 4410);
+    defer allocator.free(data);
+    @memset(data, 0.0);
+
+    const wave: Wave(f64) = try Wave(f64).init(data, allocator, .{
+        .sample_rate = 44100,
+        .channels = 1,
+    });
+    defer wave.deinit();
+
+    var append_list: std.array_list.Aligned(Composer(f64).WaveInfo, null) = .empty;
+    defer append_list.deinit(allocator);
+    for (0..5000) |i| {
+        try append_list.append(allocator, .{ .wave = wave, .start_point = i * 2646 });
+    }
+    try composer.appendSlice(append_list.items);
+
+    var timer = try std.time.Timer.start();
+
+    const result = try composer.finalize(.{});
+    defer result.deinit();
+
+    const elapsed: u64 = timer.read();
+
+    var buffer: [256]u8 = undefined;
+    var stdout = std.fs.File.stdout().writer(&buffer);
+    try stdout.interface.print("Composed 5000 events into {d} samples in {d} ms\n", .{ result.samples.len, elapsed / std.time.ns_per_ms });
+    try stdout.interface.flush();
+}
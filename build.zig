@@ -1,5 +1,7 @@
 const std = @import("std");
 const z_wav = @import("zigggwavvv");
+const aiff = @import("./src/aiff.zig");
+const raw = @import("./src/raw.zig");
 
 pub const Wave = @import("./src/wave.zig");
 pub const Composer = @import("./src/composer.zig");
@@ -92,6 +94,23 @@ pub fn build(b: *std.Build) !void {
     const run_composer_integration_tests = b.addRunArtifact(composer_integration_test);
     test_step.dependOn(&run_composer_integration_tests.step);
 
+    // Benchmarks
+    // Run them with optimizations, e.g. `zig build bench -Doptimize=ReleaseFast`
+    const bench_step = b.step("bench", "Run benchmarks");
+    const composer_bench = b.addExecutable(.{
+        .name = "composer-bench",
+        .root_module = b.createModule(.{
+            .root_source_file = b.path("bench/composer.zig"),
+            .target = target,
+            .optimize = optimize,
+            .imports = &.{
+                .{ .name = "lightmix", .module = lib_mod },
+            },
+        }),
+    });
+    const run_composer_bench = b.addRunArtifact(composer_bench);
+    bench_step.dependOn(&run_composer_bench.step);
+
     // Docs
     const docs_step = b.step("docs", "Emit docs");
     const docs_install = b.addInstallDirectory(.{
@@ -102,10 +121,10 @@ pub fn build(b: *std.Build) !void {
     docs_step.dependOn(&docs_install.step);
 }
 
-/// Creates a build step that generates a WAV file at compile time.
+/// Creates a build step that generates a WAV or AIFF file at compile time.
 ///
 /// This function enables compile-time audio generation by calling a user-defined
-/// function that returns a Wave instance, then writing it to a WAV file during
+/// function that returns a Wave instance, then writing it to an audio file during
 /// the build process.
 ///
 /// ## Parameters
@@ -155,125 +174,142 @@ pub fn build(b: *std.Build) !void {
 ///
 /// The user module must export a function matching the signature specified in
 /// `options.func_name` (default: "gen") that returns `!lightmix.Wave(T)`, and receives an argument `std.mem.Allocator`.
+/// With the `.midi` format, the function returns `!lightmix.Midi(T)` instead.
 pub fn addWave(
     b: *std.Build,
     mod: *std.Build.Module,
     options: CreateWaveOptions,
 ) anyerror!*CompileWave {
     return switch (options.format) {
-        .wav => Generator.Wav.gen(b, mod, options),
+        .wav => |wav| Generator.gen(b, mod, options, wav.name, try std.fmt.allocPrint(b.allocator,
+            \\.wav, &writer.interface, .{{
+            \\        .format_code = .{s},
+            \\        .bits = {d},
+            \\    }}
+        , .{ @tagName(wav.format_code), wav.bits })),
+        .aiff => |aiff_options| Generator.gen(b, mod, options, aiff_options.name, try std.fmt.allocPrint(b.allocator,
+            \\.aiff, &writer.interface, .{{
+            \\        .format_code = .{s},
+            \\        .bits = {d},
+            \\    }}
+        , .{ @tagName(aiff_options.format_code), aiff_options.bits })),
+        .flac => |flac| Generator.gen(b, mod, options, flac.name, try std.fmt.allocPrint(b.allocator,
+            \\.flac, &writer.interface, .{{
+            \\        .bits = {d},
+            \\        .compression_level = {d},
+            \\    }}
+        , .{ flac.bits, flac.compression_level })),
+        .raw => |raw_options| Generator.gen(b, mod, options, raw_options.name, try std.fmt.allocPrint(b.allocator,
+            \\.raw, &writer.interface, .{{
+            \\        .encoding = .{s},
+            \\        .endian = .{s},
+            \\    }}
+        , .{ @tagName(raw_options.encoding), @tagName(raw_options.endian) })),
+        .midi => |midi| Generator.gen(b, mod, options, midi.name, "&writer.interface"),
     };
 }
 
 const Generator = struct {
-    const Wav = struct {
-        fn gen(
-            b: *std.Build,
-            mod: *std.Build.Module,
-            options: CreateWaveOptions,
-        ) anyerror!*CompileWave {
-            // Create .zig-cache/lightmix directory
-            b.cache_root.handle.access("lightmix", .{}) catch {
-                try b.cache_root.handle.makeDir("lightmix");
-            };
-
-            // Create a wave file in .zig-cache/lightmix
-            const tmp_path: []const u8 = try std.fs.path.join(b.allocator, &[_][]const u8{
-                try b.build_root.handle.realpathAlloc(b.allocator, "."),
-                ".zig-cache",
-                "lightmix",
-                options.format.wav.name,
-            });
-            const tmp_path_in_zig: []const u8 = try std.mem.replaceOwned(
-                u8,
-                b.allocator,
-                tmp_path,
-                "\\",
-                "/",
-            );
-            // Generate temporary Zig code that calls the user's function
-            const gen_source = try std.fmt.allocPrint(b.allocator,
-                \\const std = @import("std");
-                \\const user_module = @import("user_module");
-                \\
-                \\var gpa = std.heap.GeneralPurposeAllocator(.{{}}){{}};
-                \\const allocator = gpa.allocator();
-                \\
-                \\pub fn main() !void {{
-                \\    defer {{
-                \\        const leaked = gpa.deinit();
-                \\        if (leaked == .leak)
-                \\            @panic("Memory leak happened");
-                \\    }}
-                \\
-                \\    const wave = try user_module.{s}(allocator);
-                \\    defer wave.deinit();
-                \\
-                \\    const bits = {d};
-                \\    const bytes_per_sample = (bits + 7) / 8;
-                \\
-                \\    const header_size = 44;
-                \\    const total_size = header_size + (wave.samples.len * wave.channels * bytes_per_sample);
-                \\
-                \\    const file = try std.fs.cwd().createFile("{s}", .{{}});
-                \\    defer file.close();
-                \\    const buf = try allocator.alloc(u8, total_size);
-                \\    defer allocator.free(buf);
-                \\    var writer = file.writer(buf);
-                \\
-                \\    try wave.write(.wav, &writer.interface, .{{
-                \\        .format_code = .{s},
-                \\        .bits = bits,
-                \\    }});
-                \\
-                \\    try writer.interface.flush();
-                \\}}
-            , .{
-                options.func_name,
-                options.format.wav.bits,
-                tmp_path_in_zig,
-                @tagName(options.format.wav.format_code),
-            });
-
-            // Create a write files step to generate the temporary source
-            const write_files = b.addWriteFiles();
-            const gen_file = write_files.add("wave_gen.zig", gen_source);
-
-            // Create executable that generates the wave
-            const gen_exe = b.addExecutable(.{
-                .name = "wave_generator",
-                .root_module = b.createModule(.{
-                    .root_source_file = gen_file,
-                    .target = b.graph.host,
-                    .optimize = .Debug,
-                    .imports = &.{
-                        .{ .name = "user_module", .module = mod },
-                    },
-                }),
-            });
-
-            // Run the generator during build
-            const run_gen = b.addRunArtifact(gen_exe);
-
-            const src_path: []const u8 = try std.fs.path.join(b.allocator, &[_][]const u8{ ".zig-cache", "lightmix", options.format.wav.name });
-            // Install the generated wave file
-            const install_wave = b.addInstallFileWithDir(
-                b.path(src_path),
-                options.path,
-                options.format.wav.name,
-            );
-            install_wave.step.dependOn(&run_gen.step);
-
-            const result = try b.allocator.create(CompileWave);
-            result.* = CompileWave{
-                .step = &install_wave.step,
-                .root_module = mod,
-                .name = options.format.wav.name,
-                .create_wave_options = options,
-            };
-            return result;
-        }
-    };
+    /// Builds and runs a program which calls the user's function, then writes the wave
+    /// with `wave.write({write_args})` into `.zig-cache/lightmix/{name}`, and installs it.
+    fn gen(
+        b: *std.Build,
+        mod: *std.Build.Module,
+        options: CreateWaveOptions,
+        name: []const u8,
+        write_args: []const u8,
+    ) anyerror!*CompileWave {
+        // Create .zig-cache/lightmix directory
+        b.cache_root.handle.access("lightmix", .{}) catch {
+            try b.cache_root.handle.makeDir("lightmix");
+        };
+
+        // Create a wave file in .zig-cache/lightmix
+        const tmp_path: []const u8 = try std.fs.path.join(b.allocator, &[_][]const u8{
+            try b.build_root.handle.realpathAlloc(b.allocator, "."),
+            ".zig-cache",
+            "lightmix",
+            name,
+        });
+        const tmp_path_in_zig: []const u8 = try std.mem.replaceOwned(
+            u8,
+            b.allocator,
+            tmp_path,
+            "\\",
+            "/",
+        );
+        // Generate temporary Zig code that calls the user's function
+        const gen_source = try std.fmt.allocPrint(b.allocator,
+            \\const std = @import("std");
+            \\const user_module = @import("user_module");
+            \\
+            \\var gpa = std.heap.GeneralPurposeAllocator(.{{}}){{}};
+            \\const allocator = gpa.allocator();
+            \\
+            \\pub fn main() !void {{
+            \\    defer {{
+            \\        const leaked = gpa.deinit();
+            \\        if (leaked == .leak)
+            \\            @panic("Memory leak happened");
+            \\    }}
+            \\
+            \\    const wave = try user_module.{s}(allocator);
+            \\    defer wave.deinit();
+            \\
+            \\    const file = try std.fs.cwd().createFile("{s}", .{{}});
+            \\    defer file.close();
+            \\    const buf = try allocator.alloc(u8, 64 * 1024);
+            \\    defer allocator.free(buf);
+            \\    var writer = file.writer(buf);
+            \\
+            \\    try wave.write({s});
+            \\
+            \\    try writer.interface.flush();
+            \\}}
+        , .{
+            options.func_name,
+            tmp_path_in_zig,
+            write_args,
+        });
+
+        // Create a write files step to generate the temporary source
+        const write_files = b.addWriteFiles();
+        const gen_file = write_files.add("wave_gen.zig", gen_source);
+
+        // Create executable that generates the wave
+        const gen_exe = b.addExecutable(.{
+            .name = "wave_generator",
+            .root_module = b.createModule(.{
+                .root_source_file = gen_file,
+                .target = b.graph.host,
+                .optimize = .Debug,
+                .imports = &.{
+                    .{ .name = "user_module", .module = mod },
+                },
+            }),
+        });
+
+        // Run the generator during build
+        const run_gen = b.addRunArtifact(gen_exe);
+
+        const src_path: []const u8 = try std.fs.path.join(b.allocator, &[_][]const u8{ ".zig-cache", "lightmix", name });
+        // Install the generated wave file
+        const install_wave = b.addInstallFileWithDir(
+            b.path(src_path),
+            options.path,
+            name,
+        );
+        install_wave.step.dependOn(&run_gen.step);
+
+        const result = try b.allocator.create(CompileWave);
+        result.* = CompileWave{
+            .step = &install_wave.step,
+            .root_module = mod,
+            .name = name,
+            .create_wave_options = options,
+        };
+        return result;
+    }
 };
 
 /// A return type for addWave function.
@@ -287,14 +323,14 @@ pub const CompileWave = struct {
 /// Options for configuring compile-time wave generation.
 ///
 /// These options control how the wave generation function is called and
-/// where the resulting WAV file is installed.
+/// where the resulting audio file is installed.
 pub const CreateWaveOptions = struct {
     /// Name of the function in the module that generates the Wave.
     /// The function must have signature: `pub fn name() !lightmix.Wave(T)`
-    /// where T is typically f64, f80, or f128.
+    /// where T is typically f64, f80, or f128. With the `.midi` format, it returns `!lightmix.Midi(T)`.
     func_name: []const u8 = "gen",
 
-    /// Destination path relative to the install prefix where the WAV file will be installed.
+    /// Destination path relative to the install prefix where the audio file will be installed.
     /// Defaults to the "share" directory.
     path: std.Build.InstallDir = .{ .custom = "share" },
 
@@ -305,6 +341,10 @@ pub const CreateWaveOptions = struct {
 /// Tagged union that selects the output format and carries its format-specific options.
 pub const FormatOptions = union(enum) {
     wav: WavOptions,
+    aiff: AiffOptions,
+    flac: FlacOptions,
+    raw: RawOptions,
+    midi: MidiOptions,
 };
 
 /// Options for configuring a WAV file's output properties.
@@ -322,6 +362,56 @@ pub const WavOptions = struct {
     format_code: z_wav.FormatCode,
 };
 
+/// Options for configuring an AIFF file's output properties.
+///
+/// `.pcm` samples are written into an AIFF file, and `.ieee_float` samples
+/// into an AIFF-C file.
+pub const AiffOptions = struct {
+    /// The output filename for the AIFF file (e.g., "result.aiff").
+    name: []const u8 = "result.aiff",
+
+    /// The bit depth: 8, 16, 24 or 32 for `.pcm`, 32 or 64 for `.ieee_float`.
+    bits: u16,
+
+    /// Audio encoding format, .pcm (PCM integer) or .ieee_float (floating-point).
+    format_code: aiff.FormatCode,
+};
+
+/// Options for configuring a FLAC file's output properties.
+pub const FlacOptions = struct {
+    /// The output filename for the FLAC file (e.g., "result.flac").
+    name: []const u8 = "result.flac",
+
+    /// The bit depth, from 4 to 32 bits per sample.
+    bits: u16,
+
+    /// From 0 (fastest) to 8 (smallest file), as in the reference encoder.
+    compression_level: u4 = 5,
+};
+
+/// Options for configuring a headerless PCM file's output properties.
+///
+/// The file holds nothing but interleaved samples, ready to be `@embedFile`d.
+pub const RawOptions = struct {
+    /// The output filename for the raw file (e.g., "result.raw" or "result.pcm").
+    name: []const u8 = "result.raw",
+
+    /// Sample encoding: .u8, .i16, .i24, .i32, .f32 or .f64.
+    encoding: raw.Encoding,
+
+    /// Byte order of the samples.
+    endian: std.builtin.Endian = .little,
+};
+
+/// Options for configuring a Standard MIDI File's output properties.
+///
+/// The file holds the notes of the `lightmix.Midi(T)` returned by the user's function,
+/// so it can't be played with `addPlay`.
+pub const MidiOptions = struct {
+    /// The output filename for the MIDI file (e.g., "result.mid").
+    name: []const u8 = "result.mid",
+};
+
 /// A helper function to install Wave file from a pointer of a value typed CompileWave.
 ///
 /// This function does as the following:
@@ -358,6 +448,10 @@ pub fn installWave(b: *std.Build, wave: *CompileWave) void {
 /// ## Returns
 /// A Run step that executes the play functionality
 ///
+/// ## Errors
+/// - `MidiNotPlayable`: The wave was added with the `.midi` format, whose function returns notes
+/// - Memory allocation failures
+///
 /// ## Usage
 /// ```zig
 /// const wave = try l.addWave(b, mod, .{
@@ -371,6 +465,15 @@ pub fn addPlay(
     wave: *CompileWave,
     options: PlayOptions,
 ) anyerror!*std.Build.Step.Run {
+    // A `.midi` function returns notes rather than a Wave, so there is nothing to play
+    if (wave.create_wave_options.format == .midi) {
+        std.log.err("addPlay: '{s}' returns MIDI notes for '{s}', which can't be played", .{
+            wave.create_wave_options.func_name,
+            wave.name,
+        });
+        return error.MidiNotPlayable;
+    }
+
     // Generate temporary Zig code that calls the user's function and plays it
     const play_source = try std.fmt.allocPrint(b.allocator,
         \\const std = @import("std");
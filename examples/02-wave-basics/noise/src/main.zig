@@ -6,25 +6,38 @@
 //! - Brown Noise: Even more bass-heavy (like ocean waves)
 //!
 //! ## What you'll learn:
-//! - Using random number generation for audio
+//! - Generating reproducible noise from a seed
 //! - Different types of noise and their characteristics
-//! - Filtering techniques for colored noise
 
 const std = @import("std");
 const lightmix = @import("lightmix");
 const Wave = lightmix.Wave;
+const Noise = lightmix.Noise;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
 
+    // All noise generators share the same options.
+    // The seed makes the output reproducible: the same seed always generates the same noise.
+    const options: Noise(f64).Options = .{
+        .amplitude = 0.5,
+        .duration = 1.0, // Seconds
+        .sample_rate = 44100,
+        .channels = 1,
+        .seed = 0,
+    };
+
     // Generate different types of noise
-    const white_noise = try generateWhiteNoise(allocator);
+    // White noise: completely random values
+    const white_noise = try Noise(f64).white(allocator, options);
     defer white_noise.deinit();
 
-    const pink_noise = try generatePinkNoise(allocator);
+    // Pink noise: filtered white noise with 1/f spectrum
+    const pink_noise = try Noise(f64).pink(allocator, options);
     defer pink_noise.deinit();
 
-    const brown_noise = try generateBrownNoise(allocator);
+    // Brown noise: integrated white noise (random walk)
+    const brown_noise = try Noise(f64).brown(allocator, options);
     defer brown_noise.deinit();
 
     // Save each to a file
@@ -38,75 +51,6 @@ pub fn main() !void {
     std.debug.print("  brown_noise.wav - Bass-heavy noise\n", .{});
 }
 
-/// White noise: completely random values
-fn generateWhiteNoise(allocator: std.mem.Allocator) !Wave(f64) {
-    var prng = std.Random.DefaultPrng.init(0);
-    const rand = prng.random();
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        // Random value between -0.5 and +0.5
-        samples[i] = (rand.float(f64) * 2.0 - 1.0) * 0.5;
-    }
-
-    return try Wave(f64).init(samples[0..], allocator, .{
-        .sample_rate = 44100,
-        .channels = 1,
-    });
-}
-
-/// Pink noise: filtered white noise with 1/f spectrum
-fn generatePinkNoise(allocator: std.mem.Allocator) !Wave(f64) {
-    var prng = std.Random.DefaultPrng.init(0);
-    const rand = prng.random();
-
-    // Paul Kellett's refined method for pink noise
-    var b0: f64 = 0.0;
-    var b1: f64 = 0.0;
-    var b2: f64 = 0.0;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        const white = rand.float(f64) * 2.0 - 1.0;
-
-        b0 = 0.99765 * b0 + white * 0.0990460;
-        b1 = 0.96300 * b1 + white * 0.2965164;
-        b2 = 0.57000 * b2 + white * 1.0526913;
-
-        samples[i] = (b0 + b1 + b2 + white * 0.1848) * 0.15;
-    }
-
-    return try Wave(f64).init(samples[0..], allocator, .{
-        .sample_rate = 44100,
-        .channels = 1,
-    });
-}
-
-/// Brown noise: integrated white noise (random walk)
-fn generateBrownNoise(allocator: std.mem.Allocator) !Wave(f64) {
-    var prng = std.Random.DefaultPrng.init(0);
-    const rand = prng.random();
-
-    var last_value: f64 = 0.0;
-    var samples: [44100]f64 = undefined;
-
-    for (0..samples.len) |i| {
-        // Add small random step to previous value
-        const step = (rand.float(f64) * 2.0 - 1.0) * 0.02;
-        last_value += step;
-
-        // Clamp to prevent drift
-        last_value = @max(-1.0, @min(1.0, last_value));
-
-        samples[i] = last_value * 0.5;
-    }
-
-    return try Wave(f64).init(samples[0..], allocator, .{
-        .sample_rate = 44100,
-        .channels = 1,
-    });
-}
-
 fn saveWave(wave: Wave(f64), filename: []const u8, allocator: std.mem.Allocator) !void {
     const file = try std.fs.cwd().createFile(filename, .{});
     defer file.close();
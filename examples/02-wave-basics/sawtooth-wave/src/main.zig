@@ -10,26 +10,16 @@
 
 const std = @import("std");
 const lightmix = @import("lightmix");
-const Wave = lightmix.Wave;
+const Oscillator = lightmix.Oscillator;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
 
     // Generate a 440Hz sawtooth wave
-    const frequency: f64 = 440.0;
-    const sample_rate: f64 = 44100.0;
-    const period: f64 = sample_rate / frequency;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        // Calculate position within the current period (0.0 to 1.0)
-        const phase = @mod(@as(f64, @floatFromInt(i)), period) / period;
-
-        // Sawtooth wave: Linear ramp from -1 to +1
-        samples[i] = (phase * 2.0) - 1.0;
-    }
-
-    const wave = try Wave(f64).init(samples[0..], allocator, .{
+    // Sawtooth wave: Linear ramp from -1 to +1 in every period
+    const wave = try Oscillator(f64).sawtooth(allocator, .{
+        .frequency = 440.0,
+        .duration = 1.0, // Seconds
         .sample_rate = 44100,
         .channels = 1,
     });
@@ -13,25 +13,17 @@
 
 const std = @import("std");
 const lightmix = @import("lightmix");
-const Wave = lightmix.Wave;
+const Oscillator = lightmix.Oscillator;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
 
     // Generate a 440Hz sine wave (A4 note)
-    const frequency: f64 = 440.0;
-    const sample_rate: f64 = 44100.0;
-    const radians_per_sec: f64 = frequency * 2.0 * std.math.pi;
-    const volume: f64 = 0.5;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        const t = @as(f64, @floatFromInt(i)) / sample_rate;
-        // Sine wave formula: amplitude * sin(2π * frequency * time)
-        samples[i] = volume * @sin(radians_per_sec * t);
-    }
-
-    const wave = try Wave(f64).init(samples[0..], allocator, .{
+    // Sine wave formula: amplitude * sin(2π * frequency * time)
+    const wave = try Oscillator(f64).sine(allocator, .{
+        .frequency = 440.0,
+        .amplitude = 0.5, // Volume
+        .duration = 1.0, // Seconds
         .sample_rate = 44100,
         .channels = 1,
     });
@@ -14,27 +14,17 @@
 
 const std = @import("std");
 const lightmix = @import("lightmix");
-const Wave = lightmix.Wave;
+const Oscillator = lightmix.Oscillator;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
 
     // Generate a 440Hz square wave
-    const frequency: f64 = 440.0;
-    const sample_rate: f64 = 44100.0;
-    const volume: f64 = 0.5;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        const t = @as(f64, @floatFromInt(i)) / sample_rate;
-        const phase = t * frequency;
-
-        // Square wave: +1 for first half of cycle, -1 for second half
-        // We use @mod to get the fractional part of the phase
-        samples[i] = if (@mod(phase, 1.0) < 0.5) volume else -volume;
-    }
-
-    const wave = try Wave(f64).init(samples[0..], allocator, .{
+    // Square wave: +1 for first half of cycle, -1 for second half
+    const wave = try Oscillator(f64).square(allocator, .{
+        .frequency = 440.0,
+        .amplitude = 0.5, // Volume
+        .duration = 1.0, // Seconds
         .sample_rate = 44100,
         .channels = 1,
     });
@@ -11,29 +11,16 @@
 
 const std = @import("std");
 const lightmix = @import("lightmix");
-const Wave = lightmix.Wave;
+const Oscillator = lightmix.Oscillator;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
 
     // Generate a 440Hz triangle wave
-    const frequency: f64 = 440.0;
-    const sample_rate: f64 = 44100.0;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        const t = @as(f64, @floatFromInt(i)) / sample_rate;
-        const phase = @mod(t * frequency, 1.0);
-
-        // Triangle wave: rises from 0 to 1 in first half, falls from 1 to 0 in second half
-        // Transform to -1 to +1 range
-        samples[i] = if (phase < 0.5)
-            4.0 * phase - 1.0 // Rising: -1 to +1
-        else
-            3.0 - 4.0 * phase; // Falling: +1 to -1
-    }
-
-    const wave = try Wave(f64).init(samples[0..], allocator, .{
+    // Triangle wave: rises from -1 to +1 in first half, falls from +1 to -1 in second half
+    const wave = try Oscillator(f64).triangle(allocator, .{
+        .frequency = 440.0,
+        .duration = 1.0, // Seconds
         .sample_rate = 44100,
         .channels = 1,
     });
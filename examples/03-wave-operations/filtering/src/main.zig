@@ -1,35 +1,36 @@
 //! # Filtering - Apply Multiple Transformations
 //!
 //! This example shows how to chain multiple filters together to transform audio.
-//! We'll create a sine wave and apply decay, volume reduction, and distortion filters.
+//! We'll create a sawtooth wave and apply low-pass, decay, and volume reduction filters.
 //!
 //! ## What you'll learn:
 //! - How to chain multiple filters
+//! - Using the built-in biquad filters with arguments
 //! - Creating different types of audio effects
 //! - Understanding filter composition
 
 const std = @import("std");
 const lightmix = @import("lightmix");
 const Wave = lightmix.Wave;
+const Oscillator = lightmix.Oscillator;
+const Biquad = lightmix.Biquad;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
 
-    // Generate a sine wave
-    const frequency: f64 = 440.0;
-    const sample_rate: f64 = 44100.0;
-    const radians_per_sec: f64 = frequency * 2.0 * std.math.pi;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        const t = @as(f64, @floatFromInt(i)) / sample_rate;
-        samples[i] = 0.8 * @sin(radians_per_sec * t);
-    }
-
-    var wave = try Wave(f64).init(samples[0..], allocator, .{
+    // Generate a sawtooth wave, which is rich in harmonics
+    var wave = try Oscillator(f64).sawtooth(allocator, .{
+        .frequency = 440.0,
+        .amplitude = 0.8,
+        .duration = 1.0,
         .sample_rate = 44100,
         .channels = 1,
     });
+    // Remove harmonics above 2000 Hz to soften the tone
+    try wave.filter_with(Biquad(f64).Options, Biquad(f64).filter, .{
+        .kind = .low_pass,
+        .cutoff = 2000.0,
+    });
     try wave.filter(decayFilter); // Apply fade-out
     try wave.filter(halveSampleValuesFilter); // Reduce volume
     defer wave.deinit();
@@ -48,8 +49,9 @@ pub fn main() !void {
     try writer.interface.flush();
 
     std.debug.print("✓ Applied multiple filters:\n", .{});
-    std.debug.print("  1. Decay (fade-out)\n", .{});
-    std.debug.print("  2. Volume reduction (50%)\n", .{});
+    std.debug.print("  1. Low-pass (2000 Hz)\n", .{});
+    std.debug.print("  2. Decay (fade-out)\n", .{});
+    std.debug.print("  3. Volume reduction (50%)\n", .{});
 }
 
 /// Decay filter: Linear fade-out effect
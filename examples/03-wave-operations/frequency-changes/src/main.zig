@@ -12,6 +12,7 @@
 const std = @import("std");
 const lightmix = @import("lightmix");
 const Wave = lightmix.Wave;
+const Oscillator = lightmix.Oscillator;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
@@ -39,16 +40,10 @@ pub fn main() !void {
 }
 
 fn generateSineWave(frequency: f64, allocator: std.mem.Allocator) !Wave(f64) {
-    const sample_rate: f64 = 44100.0;
-    const radians_per_sec: f64 = frequency * 2.0 * std.math.pi;
-
-    var samples: [44100]f64 = undefined;
-    for (0..samples.len) |i| {
-        const t = @as(f64, @floatFromInt(i)) / sample_rate;
-        samples[i] = 0.5 * @sin(radians_per_sec * t);
-    }
-
-    return try Wave(f64).init(samples[0..], allocator, .{
+    return try Oscillator(f64).sine(allocator, .{
+        .frequency = frequency,
+        .amplitude = 0.5,
+        .duration = 1.0, // Seconds
         .sample_rate = 44100,
         .channels = 1,
     });
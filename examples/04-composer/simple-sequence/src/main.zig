@@ -6,6 +6,7 @@
 //! ## What you'll learn:
 //! - How to use the Composer API
 //! - Sequencing waves with start_point
+//! - Placing waves on beats with a tempo-aware timeline
 //! - Creating simple melodies
 //!
 //! ## The melody:
@@ -21,10 +22,11 @@ pub fn main() !void {
     defer _ = gpa.deinit();
     const allocator = gpa.allocator();
 
-    // Create composer
+    // Create composer at 120 BPM
     var composer = Composer(f64).init(allocator, .{
         .sample_rate = 44100,
         .channels = 1,
+        .timeline = .{ .tempo = 120.0 },
     });
     defer composer.deinit();
 
@@ -40,10 +42,10 @@ pub fn main() !void {
     var notes_list: std.array_list.Aligned(Composer(f64).WaveInfo, null) = .empty;
     defer notes_list.deinit(allocator);
 
-    try notes_list.append(allocator, .{ .wave = c4, .start_point = 0 }); // 0.0s
-    try notes_list.append(allocator, .{ .wave = d4, .start_point = 22050 }); // 0.5s
-    try notes_list.append(allocator, .{ .wave = e4, .start_point = 44100 }); // 1.0s
-    try notes_list.append(allocator, .{ .wave = c4, .start_point = 66150 }); // 1.5s
+    try notes_list.append(allocator, .{ .wave = c4, .start_point = try composer.frameAt(.{ .musical = .{ .bar = 0, .beat = 0 } }) }); // 0.0s
+    try notes_list.append(allocator, .{ .wave = d4, .start_point = try composer.frameAt(.{ .musical = .{ .bar = 0, .beat = 1 } }) }); // 0.5s
+    try notes_list.append(allocator, .{ .wave = e4, .start_point = try composer.frameAt(.{ .musical = .{ .bar = 0, .beat = 2 } }) }); // 1.0s
+    try notes_list.append(allocator, .{ .wave = c4, .start_point = try composer.frameAt(.{ .musical = .{ .bar = 0, .beat = 3 } }) }); // 1.5s
 
     try composer.appendSlice(notes_list.items);
 
@@ -13,6 +13,7 @@
 const std = @import("std");
 const lightmix = @import("lightmix");
 const Wave = lightmix.Wave;
+const Noise = lightmix.Noise;
 
 pub fn main() !void {
     const allocator = std.heap.page_allocator;
@@ -38,7 +39,13 @@ pub fn main() !void {
 
 fn generateSnare(allocator: std.mem.Allocator) !Wave(f64) {
     // Generate pink noise for snare wires
-    var noise: Wave(f64) = try generatePinkNoise(allocator);
+    var noise: Wave(f64) = try Noise(f64).pink(allocator, .{
+        .amplitude = 0.6,
+        .duration = 0.5, // Seconds
+        .sample_rate = 44100,
+        .channels = 1,
+        .seed = 0,
+    });
     // Apply aggressive decay to noise
     try noise.filter(fastDecayFilter);
     try noise.filter(fastDecayFilter);
@@ -56,29 +63,6 @@ fn generateSnare(allocator: std.mem.Allocator) !Wave(f64) {
     return noise.mix(tone, .{});
 }
 
-fn generatePinkNoise(allocator: std.mem.Allocator) !Wave(f64) {
-    var prng = std.Random.DefaultPrng.init(0);
-    const rand = prng.random();
-
-    var b0: f64 = 0.0;
-    var b1: f64 = 0.0;
-    var b2: f64 = 0.0;
-
-    var samples: [22050]f64 = undefined; // 0.5 seconds
-    for (0..samples.len) |i| {
-        const white = rand.float(f64) * 2.0 - 1.0;
-        b0 = 0.99765 * b0 + white * 0.0990460;
-        b1 = 0.96300 * b1 + white * 0.2965164;
-        b2 = 0.57000 * b2 + white * 1.0526913;
-        samples[i] = (b0 + b1 + b2 + white * 0.1848) * 0.3;
-    }
-
-    return try Wave(f64).init(samples[0..], allocator, .{
-        .sample_rate = 44100,
-        .channels = 1,
-    });
-}
-
 fn generateDrumTone(allocator: std.mem.Allocator) !Wave(f64) {
     const frequency: f64 = 200.0; // Low frequency for drum body
     const sample_rate: f64 = 44100.0;
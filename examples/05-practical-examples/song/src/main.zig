@@ -0,0 +1,189 @@
+//! # Song - Tracks, Buses and Effects
+//!
+//! This example arranges a short song from three tracks:
+//! - Drums (a kick and a snare, like the drum example)
+//! - Bass (a low-passed sawtooth)
+//! - Lead (plucked strings, like the guitar example)
+//!
+//! ## What you'll learn:
+//! - Writing each part in its own Composer
+//! - Nesting those Composers as tracks of a song
+//! - Giving each track its own gain, pan position and effects
+//! - Sending tracks into a bus
+
+const std = @import("std");
+const lightmix = @import("lightmix");
+const Wave = lightmix.Wave;
+const Composer = lightmix.Composer;
+const Oscillator = lightmix.Oscillator;
+const Noise = lightmix.Noise;
+const Envelope = lightmix.Envelope;
+const Biquad = lightmix.Biquad;
+
+const sample_rate = 44100;
+const timeline: lightmix.Timeline(f64) = .{ .tempo = 120.0 };
+
+pub fn main() !void {
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const allocator = gpa.allocator();
+
+    // Sounds
+    const kick = try generateKick(allocator);
+    defer kick.deinit();
+    const snare = try generateSnare(allocator);
+    defer snare.deinit();
+
+    const bass_notes = [_]f64{ 55.00, 55.00, 73.42, 65.41 }; // A1 A1 D2 C2
+    var bass_waves: [bass_notes.len]Wave(f64) = undefined;
+    for (bass_notes, 0..) |frequency, i| {
+        bass_waves[i] = try Oscillator(f64).sawtooth(allocator, .{
+            .frequency = frequency,
+            .amplitude = 0.3,
+            .duration = 0.9,
+            .sample_rate = sample_rate,
+            .band_limited = true,
+        });
+    }
+    defer for (bass_waves) |wave| wave.deinit();
+
+    const lead_notes = [_]f64{ 440.00, 523.25, 659.25, 587.33, 523.25, 440.00, 392.00, 440.00 }; // A4 C5 E5 D5 C5 A4 G4 A4
+    var lead_waves: [lead_notes.len]Wave(f64) = undefined;
+    for (lead_notes, 0..) |frequency, i| {
+        lead_waves[i] = try generatePluck(frequency, allocator);
+    }
+    defer for (lead_waves) |wave| wave.deinit();
+
+    // Drums: a kick on beats 1 and 3, a snare on beats 2 and 4
+    var drums = Composer(f64).init(allocator, .{ .sample_rate = sample_rate, .channels = 1, .timeline = timeline });
+    defer drums.deinit();
+    for (0..2) |bar| {
+        for (0..4) |beat| {
+            const wave = if (beat % 2 == 0) kick else snare;
+            try drums.append(.{ .wave = wave, .start_point = try drums.frameAt(.{ .musical = .{ .bar = bar, .beat = beat } }) });
+        }
+    }
+
+    // Bass: one note every two beats
+    var bass = Composer(f64).init(allocator, .{ .sample_rate = sample_rate, .channels = 1, .timeline = timeline });
+    defer bass.deinit();
+    for (bass_waves, 0..) |wave, i| {
+        try bass.append(.{ .wave = wave, .start_point = try bass.frameAt(.{ .musical = .{ .bar = 0, .beat = i * 2 } }) });
+    }
+
+    // Lead: one note every beat
+    var lead = Composer(f64).init(allocator, .{ .sample_rate = sample_rate, .channels = 1, .timeline = timeline });
+    defer lead.deinit();
+    for (lead_waves, 0..) |wave, i| {
+        try lead.append(.{ .wave = wave, .start_point = try lead.frameAt(.{ .musical = .{ .bar = 0, .beat = i } }) });
+    }
+
+    // Effects
+    const bass_lowpass: Biquad(f64).Options = .{ .kind = .low_pass, .cutoff = 400.0 };
+    const bass_effects = [_]Composer(f64).Effect{
+        Composer(f64).Effect.init(Biquad(f64).Options, Biquad(f64).filter, &bass_lowpass),
+    };
+
+    const band_highpass: Biquad(f64).Options = .{ .kind = .high_pass, .cutoff = 40.0 };
+    const band_effects = [_]Composer(f64).Effect{
+        Composer(f64).Effect.init(Biquad(f64).Options, Biquad(f64).filter, &band_highpass),
+    };
+
+    // The song: every track is a nested Composer, rendered when the song is finalized
+    var song = Composer(f64).init(allocator, .{ .sample_rate = sample_rate, .channels = 2, .timeline = timeline });
+    defer song.deinit();
+
+    try song.addBus(.{ .name = "band", .effects = &band_effects, .gain = .{ .decibel = -3.0 } });
+    try song.addTrack(.{ .name = "drums", .composer = &drums, .pan = 0.0 });
+    try song.addTrack(.{ .name = "bass", .composer = &bass, .effects = &bass_effects, .pan = -0.2, .output = "band" });
+    try song.addTrack(.{ .name = "lead", .composer = &lead, .gain = .{ .decibel = -4.0 }, .pan = 0.4, .output = "band" });
+
+    const result = try song.finalize(.{});
+    defer result.deinit();
+
+    const file = try std.fs.cwd().createFile("result.wav", .{});
+    defer file.close();
+    const buf = try allocator.alloc(u8, 10 * 1024 * 1024);
+    defer allocator.free(buf);
+    var writer = file.writer(buf);
+
+    try result.write(.wav, &writer.interface, .{
+        .format_code = .pcm,
+        .bits = 16,
+    });
+
+    try writer.interface.flush();
+
+    std.debug.print("✓ Created a song with drums, bass and lead tracks\n", .{});
+}
+
+fn generateKick(allocator: std.mem.Allocator) !Wave(f64) {
+    var kick = try Oscillator(f64).sine(allocator, .{
+        .frequency = 60.0,
+        .amplitude = 0.8,
+        .duration = 0.3,
+        .sample_rate = sample_rate,
+    });
+    errdefer kick.deinit();
+
+    try kick.filter_with(Envelope(f64), Envelope(f64).filter, .{ .adsr = .{
+        .attack = 0.002,
+        .decay = 0.25,
+        .sustain = 0.0,
+        .release = 0.0,
+        .decay_curve = .exponential,
+    } });
+
+    return kick;
+}
+
+fn generateSnare(allocator: std.mem.Allocator) !Wave(f64) {
+    var snare = try Noise(f64).pink(allocator, .{
+        .amplitude = 0.5,
+        .duration = 0.2,
+        .sample_rate = sample_rate,
+        .seed = 0,
+    });
+    errdefer snare.deinit();
+
+    try snare.filter_with(Envelope(f64), Envelope(f64).filter, .{ .adsr = .{
+        .attack = 0.001,
+        .decay = 0.15,
+        .sustain = 0.0,
+        .release = 0.0,
+        .decay_curve = .exponential,
+    } });
+
+    return snare;
+}
+
+/// Karplus-Strong plucked string synthesis
+fn generatePluck(frequency: f64, allocator: std.mem.Allocator) !Wave(f64) {
+    const decay: f64 = 0.995;
+
+    var result: [22050]f64 = undefined;
+
+    const period = @as(usize, @intFromFloat(@as(f64, sample_rate) / frequency));
+
+    var buffer: [2000]f64 = undefined;
+    var prng = std.Random.DefaultPrng.init(0);
+    const rand = prng.random();
+
+    for (buffer[0..period]) |*val| {
+        val.* = rand.float(f64) * 2.0 - 1.0;
+    }
+
+    var idx: usize = 0;
+    for (0..result.len) |i| {
+        const next_idx = (idx + 1) % period;
+        const avg = (buffer[idx] + buffer[next_idx]) * 0.5 * decay;
+        buffer[idx] = avg;
+        result[i] = avg * 0.5;
+        idx = next_idx;
+    }
+
+    return try Wave(f64).init(result[0..], allocator, .{
+        .sample_rate = sample_rate,
+        .channels = 1,
+    });
+}
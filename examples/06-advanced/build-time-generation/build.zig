@@ -26,6 +26,13 @@ pub fn build(b: *std.Build) !void {
     });
     l.installWave(b, wave);
 
+    // MIDI Installation
+    const midi = try l.addWave(b, mod, .{
+        .func_name = "genMidi",
+        .format = .{ .midi = .{ .name = "chord.mid" } },
+    });
+    l.installWave(b, midi);
+
     // Unit tests
     const unit_tests = b.addTest(.{
         .root_module = mod,
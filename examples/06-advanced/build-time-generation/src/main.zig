@@ -1,6 +1,7 @@
 const std = @import("std");
 const lightmix = @import("lightmix");
 const Wave = lightmix.Wave;
+const Midi = lightmix.Midi;
 
 pub fn gen(allocator: std.mem.Allocator) !Wave(f64) {
     // Generate a chord using const-compatible patterns
@@ -20,6 +21,15 @@ pub fn gen(allocator: std.mem.Allocator) !Wave(f64) {
     return chord;
 }
 
+pub fn genMidi(allocator: std.mem.Allocator) !Midi(f64) {
+    // The same chord as notes, for a DAW: C5, E5 and G5 held for a beat
+    return try Midi(f64).init(&.{
+        .{ .tick = 0, .duration = 480, .key = 72, .velocity = 100 },
+        .{ .tick = 0, .duration = 480, .key = 76, .velocity = 100 },
+        .{ .tick = 0, .duration = 480, .key = 79, .velocity = 100 },
+    }, allocator, .{});
+}
+
 fn generateSineWave(frequency: f64, allocator: std.mem.Allocator) !Wave(f64) {
     const sample_rate: f64 = 44100.0;
     const radians_per_sec: f64 = frequency * 2.0 * std.math.pi;
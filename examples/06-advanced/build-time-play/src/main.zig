@@ -3,21 +3,24 @@ const lightmix = @import("lightmix");
 const Wave = lightmix.Wave;
 
 pub fn gen(allocator: std.mem.Allocator) !Wave(f64) {
-    // Generate a chord using const-compatible patterns
-    const c5 = try generateSineWave(523.25, allocator); // C5
-    defer c5.deinit();
-    const e5 = try generateSineWave(659.25, allocator); // E5
-    defer e5.deinit();
-    const g5 = try generateSineWave(783.99, allocator); // G5
-    defer g5.deinit();
+    // C5, E5 and G5, with A4 at 440 Hz
+    const chord = try lightmix.Chord(f64).parse("C", 5);
+    const frequencies = try chord.frequencies(allocator, .{});
+    defer allocator.free(frequencies);
 
-    // Mix the three notes
-    const ce_mix = try c5.mix(e5, .{});
-    defer ce_mix.deinit();
+    // Mix the notes of the chord
+    var result = try generateSineWave(frequencies[0], allocator);
+    errdefer result.deinit();
+    for (frequencies[1..]) |frequency| {
+        const note = try generateSineWave(frequency, allocator);
+        defer note.deinit();
 
-    const chord = try ce_mix.mix(g5, .{});
+        const mixed = try result.mix(note, .{});
+        result.deinit();
+        result = mixed;
+    }
 
-    return chord;
+    return result;
 }
 
 fn generateSineWave(frequency: f64, allocator: std.mem.Allocator) !Wave(f64) {
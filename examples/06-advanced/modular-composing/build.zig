@@ -7,7 +7,6 @@ pub fn build(b: *std.Build) !void {
 
     const lightmix = b.dependency("lightmix", .{});
     const synths = b.dependency("synths", .{});
-    const temperaments = b.dependency("temperaments", .{});
 
     const mod = b.addModule("modular-composing", .{
         .root_source_file = b.path("src/root.zig"),
@@ -16,7 +15,6 @@ pub fn build(b: *std.Build) !void {
         .imports = &.{
             .{ .name = "lightmix", .module = lightmix.module("lightmix") },
             .{ .name = "synths", .module = synths.module("synths") },
-            .{ .name = "temperaments", .module = temperaments.module("temperaments") },
         },
     });
 
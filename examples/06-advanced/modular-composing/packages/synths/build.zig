@@ -6,7 +6,6 @@ pub fn build(b: *std.Build) !void {
     const optimize = b.standardOptimizeOption(.{});
 
     const lightmix = b.dependency("lightmix", .{});
-    const temperaments = b.dependency("temperaments", .{});
 
     const mod = b.addModule("synths", .{
         .root_source_file = b.path("src/synths.zig"),
@@ -14,7 +13,6 @@ pub fn build(b: *std.Build) !void {
         .optimize = optimize,
         .imports = &.{
             .{ .name = "lightmix", .module = lightmix.module("lightmix") },
-            .{ .name = "temperaments", .module = temperaments.module("temperaments") },
         },
     });
 
@@ -1,29 +0,0 @@
-const std = @import("std");
-
-pub fn build(b: *std.Build) void {
-    const target = b.standardTargetOptions(.{});
-    const optimize = b.standardOptimizeOption(.{});
-
-    // Declare a temperaments module
-    const mod = b.addModule("temperaments", .{
-        .root_source_file = b.path("src/root.zig"),
-        .target = target,
-        .optimize = optimize,
-    });
-
-    // Install lib as a static library
-    const lib = b.addLibrary(.{
-        .name = "temperaments",
-        .root_module = mod,
-    });
-    b.installArtifact(lib);
-
-    // Tests
-    const mod_tests = b.addTest(.{
-        .root_module = mod,
-    });
-    const run_mod_tests = b.addRunArtifact(mod_tests);
-
-    const test_step = b.step("test", "Run tests");
-    test_step.dependOn(&run_mod_tests.step);
-}
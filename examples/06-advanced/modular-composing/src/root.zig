@@ -5,5 +5,5 @@ const synths = @import("synths");
 const Wave = lightmix.Wave;
 
 pub fn gen(allocator: std.mem.Allocator) !Wave(f64) {
-    return synths.Sine.gen(allocator, 44100, 44100, 1, .{ .code = .c, .octave = 4 });
+    return synths.Sine.gen(allocator, 44100, 44100, 1, .init(.c, 4));
 }
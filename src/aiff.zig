@@ -0,0 +1,454 @@
+const std = @import("std");
+const testing = std.testing;
+
+/// Sample encodings which can be written into AIFF files.
+pub const FormatCode = enum {
+    /// Big-endian two's complement integers (8, 16, 24 or 32 bits), written as an AIFF file
+    pcm,
+    /// Big-endian IEEE floats (32 or 64 bits), written as an AIFF-C file
+    ieee_float,
+};
+
+/// Aiff type function: Creates an AIFF and AIFF-C codec for the specified sample type.
+///
+/// Reads AIFF files with 1 to 32-bit PCM samples, and AIFF-C files with `NONE`, `twos`,
+/// `sowt` (little-endian), `fl32` and `fl64` sample encodings.
+/// Integer samples are scaled to the range from -1.0 to 1.0, as in WAV files.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// Use it through `Wave(T).LowLevelInterfaces.aiff`:
+/// ```zig
+/// const wave = try Wave(f64).read(.aiff, allocator, &reader);
+/// try wave.write(.aiff, &writer.interface, .{ .bits = 24, .format_code = .pcm });
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// Errors that can occur when reading or writing AIFF files.
+        pub const AiffErrors = error{
+            /// The data is not an AIFF or AIFF-C file, or one of its chunks is malformed
+            InvalidAiffFile,
+            /// The AIFF-C compression type is not supported
+            UnsupportedCompression,
+            /// The bit depth is not supported by the sample encoding
+            UnsupportedBits,
+        };
+
+        /// Options for writing an AIFF or AIFF-C file.
+        pub const WriteOptions = struct {
+            /// Bits per sample: 8, 16, 24 or 32 for `.pcm`, 32 or 64 for `.ieee_float`
+            bits: u16,
+            /// Sample encoding
+            format_code: FormatCode,
+        };
+
+        /// Samples and format decoded from an AIFF file.
+        pub const Decoded = struct {
+            samples: []const T,
+            sample_rate: u32,
+            channels: u16,
+        };
+
+        const Encoding = struct {
+            float: bool,
+            endian: std.builtin.Endian,
+        };
+
+        /// Contents of the `COMM` chunk.
+        const Common = struct {
+            channels: u16,
+            frames: u32,
+            bits: u16,
+            sample_rate: u32,
+            encoding: Encoding,
+        };
+
+        /// Reads an AIFF or AIFF-C file.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for sample data
+        /// - `reader`: A reader interface providing the raw file bytes
+        ///
+        /// ## Returns
+        /// The decoded samples, owned by the caller, with the sample rate and channel count
+        ///
+        /// ## Errors
+        /// - `InvalidAiffFile`: The data is not an AIFF or AIFF-C file, or a chunk is malformed
+        /// - `UnsupportedCompression`: The AIFF-C compression type is not supported
+        /// - `UnsupportedBits`: The bit depth is not supported
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn read(allocator: std.mem.Allocator, reader: anytype) anyerror!Decoded {
+            if (!std.mem.eql(u8, try reader.takeArray(4), "FORM"))
+                return error.InvalidAiffFile;
+
+            const form_size: u32 = try reader.takeInt(u32, .big);
+            const form_type: [4]u8 = (try reader.takeArray(4)).*;
+            const is_aifc: bool = std.mem.eql(u8, &form_type, "AIFC");
+            if (!is_aifc and !std.mem.eql(u8, &form_type, "AIFF"))
+                return error.InvalidAiffFile;
+            if (form_size < 4)
+                return error.InvalidAiffFile;
+
+            var common: ?Common = null;
+            var sound: ?[]u8 = null;
+            defer if (sound) |data| allocator.free(data);
+
+            var remaining: usize = form_size - 4;
+            while (remaining >= 8) {
+                const id: [4]u8 = (try reader.takeArray(4)).*;
+                const size: u32 = try reader.takeInt(u32, .big);
+                const padded_size: usize = @as(usize, size) + size % 2;
+                if (padded_size > remaining - 8)
+                    return error.InvalidAiffFile;
+                remaining -= padded_size + 8;
+
+                if (std.mem.eql(u8, &id, "COMM")) {
+                    common = try readCommon(reader, size, is_aifc);
+                    try reader.discardAll(padded_size - size);
+                } else if (std.mem.eql(u8, &id, "SSND")) {
+                    if (size < 8 or sound != null)
+                        return error.InvalidAiffFile;
+
+                    const offset: u32 = try reader.takeInt(u32, .big);
+                    _ = try reader.takeInt(u32, .big); // Block size
+                    if (offset > size - 8)
+                        return error.InvalidAiffFile;
+
+                    try reader.discardAll(offset);
+                    sound = try reader.readAlloc(allocator, size - 8 - offset);
+                    try reader.discardAll(padded_size - size);
+                } else {
+                    try reader.discardAll(padded_size);
+                }
+            }
+
+            const comm: Common = common orelse return error.InvalidAiffFile;
+            const data: []const u8 = sound orelse &.{};
+
+            const bytes: usize = (comm.bits + 7) / 8;
+            const count: usize = @as(usize, comm.frames) * comm.channels;
+            if (data.len < count * bytes)
+                return error.InvalidAiffFile;
+
+            const samples: []T = try allocator.alloc(T, count);
+            for (samples, 0..) |*sample, i| {
+                sample.* = decodeSample(data[i * bytes ..][0..bytes], comm.encoding);
+            }
+
+            return .{
+                .samples = samples,
+                .sample_rate = comm.sample_rate,
+                .channels = comm.channels,
+            };
+        }
+
+        fn readCommon(reader: anytype, size: u32, is_aifc: bool) anyerror!Common {
+            const used: u32 = if (is_aifc) 22 else 18;
+            if (size < used)
+                return error.InvalidAiffFile;
+
+            const channels: u16 = try reader.takeInt(u16, .big);
+            const frames: u32 = try reader.takeInt(u32, .big);
+            var bits: u16 = try reader.takeInt(u16, .big);
+            const sample_rate: u32 = try decodeExtended(try reader.takeArray(10));
+
+            var encoding: Encoding = .{ .float = false, .endian = .big };
+            if (is_aifc) {
+                const compression: [4]u8 = (try reader.takeArray(4)).*;
+                encoding = try encodingOf(compression);
+
+                if (encoding.float)
+                    bits = if (compression[2] == '3') 32 else 64;
+            }
+            try reader.discardAll(size - used); // Compression name
+
+            if (channels == 0 or sample_rate == 0)
+                return error.InvalidAiffFile;
+            if (bits == 0 or (bits > 32 and !encoding.float))
+                return error.UnsupportedBits;
+
+            return .{
+                .channels = channels,
+                .frames = frames,
+                .bits = bits,
+                .sample_rate = sample_rate,
+                .encoding = encoding,
+            };
+        }
+
+        fn encodingOf(compression: [4]u8) AiffErrors!Encoding {
+            const Entry = struct { []const u8, Encoding };
+            const entries = [_]Entry{
+                .{ "NONE", .{ .float = false, .endian = .big } },
+                .{ "twos", .{ .float = false, .endian = .big } },
+                .{ "sowt", .{ .float = false, .endian = .little } },
+                .{ "fl32", .{ .float = true, .endian = .big } },
+                .{ "FL32", .{ .float = true, .endian = .big } },
+                .{ "fl64", .{ .float = true, .endian = .big } },
+                .{ "FL64", .{ .float = true, .endian = .big } },
+            };
+
+            for (entries) |entry| {
+                if (std.mem.eql(u8, entry[0], &compression))
+                    return entry[1];
+            }
+
+            return error.UnsupportedCompression;
+        }
+
+        fn decodeSample(bytes: []const u8, encoding: Encoding) T {
+            if (encoding.float) {
+                return switch (bytes.len) {
+                    4 => @floatCast(@as(f32, @bitCast(std.mem.readInt(u32, bytes[0..4], encoding.endian)))),
+                    else => @floatCast(@as(f64, @bitCast(std.mem.readInt(u64, bytes[0..8], encoding.endian)))),
+                };
+            }
+
+            return switch (bytes.len) {
+                1 => scale(i8, @bitCast(bytes[0])),
+                2 => scale(i16, std.mem.readInt(i16, bytes[0..2], encoding.endian)),
+                3 => scale(i24, std.mem.readInt(i24, bytes[0..3], encoding.endian)),
+                else => scale(i32, std.mem.readInt(i32, bytes[0..4], encoding.endian)),
+            };
+        }
+
+        fn scale(comptime I: type, value: I) T {
+            return @as(T, @floatFromInt(value)) / @as(T, @floatFromInt(std.math.maxInt(I)));
+        }
+
+        /// Writes samples as an AIFF file (`.pcm`) or an AIFF-C file (`.ieee_float`).
+        ///
+        /// ## Parameters
+        /// - `samples`: Interleaved samples
+        /// - `sample_rate`: Samples per second
+        /// - `channels`: Number of channels
+        /// - `writer`: A writer interface for the output bytes
+        /// - `options`: Bit depth and sample encoding
+        ///
+        /// ## Errors
+        /// - `UnsupportedBits`: The bit depth is not supported by the sample encoding
+        /// - `InvalidRange`: `channels` is 0, or there are more frames than an AIFF file can hold
+        /// - `LengthMismatch`: The sample count is not a multiple of `channels`
+        /// - Any error returned by the writer
+        pub fn write(
+            samples: []const T,
+            sample_rate: u32,
+            channels: u16,
+            writer: anytype,
+            options: WriteOptions,
+        ) anyerror!void {
+            const supported: bool = switch (options.format_code) {
+                .pcm => options.bits == 8 or options.bits == 16 or options.bits == 24 or options.bits == 32,
+                .ieee_float => options.bits == 32 or options.bits == 64,
+            };
+            if (!supported)
+                return error.UnsupportedBits;
+            if (channels == 0)
+                return error.InvalidRange;
+            if (samples.len % channels != 0)
+                return error.LengthMismatch;
+
+            const frames: usize = samples.len / channels;
+            const bytes: usize = options.bits / 8;
+            const data_size: usize = samples.len * bytes;
+            if (frames > std.math.maxInt(u32) or data_size + 64 > std.math.maxInt(u32))
+                return error.InvalidRange;
+
+            const compression_name: []const u8 = if (options.bits == 32) "32-bit floating point" else "64-bit floating point";
+            // A Pascal string, padded to an even length
+            const name_size: usize = (compression_name.len + 2) / 2 * 2;
+
+            const is_aifc: bool = options.format_code == .ieee_float;
+            const comm_size: usize = if (is_aifc) 22 + name_size else 18;
+            const fver_size: usize = if (is_aifc) 12 else 0;
+            const form_size: usize = 4 + fver_size + 8 + comm_size + 16 + data_size + data_size % 2;
+
+            try writer.writeAll("FORM");
+            try writer.writeInt(u32, @intCast(form_size), .big);
+            try writer.writeAll(if (is_aifc) "AIFC" else "AIFF");
+
+            if (is_aifc) {
+                try writer.writeAll("FVER");
+                try writer.writeInt(u32, 4, .big);
+                try writer.writeInt(u32, aifc_version, .big);
+            }
+
+            try writer.writeAll("COMM");
+            try writer.writeInt(u32, @intCast(comm_size), .big);
+            try writer.writeInt(u16, channels, .big);
+            try writer.writeInt(u32, @intCast(frames), .big);
+            try writer.writeInt(u16, options.bits, .big);
+            try writer.writeAll(&encodeExtended(sample_rate));
+            if (is_aifc) {
+                try writer.writeAll(if (options.bits == 32) "fl32" else "fl64");
+                try writer.writeByte(@intCast(compression_name.len));
+                try writer.writeAll(compression_name);
+                try writer.splatByteAll(0, name_size - compression_name.len - 1);
+            }
+
+            try writer.writeAll("SSND");
+            try writer.writeInt(u32, @intCast(data_size + 8), .big);
+            try writer.writeInt(u32, 0, .big); // Offset
+            try writer.writeInt(u32, 0, .big); // Block size
+
+            for (samples) |sample| {
+                switch (options.format_code) {
+                    .pcm => switch (options.bits) {
+                        8 => try writer.writeInt(i8, quantize(i8, sample), .big),
+                        16 => try writer.writeInt(i16, quantize(i16, sample), .big),
+                        24 => try writer.writeInt(i24, quantize(i24, sample), .big),
+                        else => try writer.writeInt(i32, quantize(i32, sample), .big),
+                    },
+                    .ieee_float => switch (options.bits) {
+                        32 => try writer.writeInt(u32, @bitCast(@as(f32, @floatCast(sample))), .big),
+                        else => try writer.writeInt(u64, @bitCast(@as(f64, @floatCast(sample))), .big),
+                    },
+                }
+            }
+
+            if (data_size % 2 == 1)
+                try writer.writeByte(0);
+        }
+
+        /// The `FVER` timestamp of the AIFF-C version 1 specification
+        const aifc_version: u32 = 0xA2805140;
+
+        fn quantize(comptime I: type, sample: T) I {
+            const max: T = @floatFromInt(std.math.maxInt(I));
+            return @intFromFloat(@round(std.math.clamp(sample, -1.0, 1.0) * max));
+        }
+
+        /// Encodes a sample rate as an 80-bit IEEE 754 extended precision number.
+        fn encodeExtended(value: u32) [10]u8 {
+            var result: [10]u8 = [_]u8{0} ** 10;
+            if (value == 0)
+                return result;
+
+            const log2: u16 = 31 - @as(u16, @clz(value));
+            std.mem.writeInt(u16, result[0..2], 16383 + log2, .big);
+            std.mem.writeInt(u64, result[2..10], @as(u64, value) << @intCast(63 - log2), .big);
+
+            return result;
+        }
+
+        /// Decodes an 80-bit IEEE 754 extended precision number as a sample rate, rounded to the nearest integer.
+        fn decodeExtended(bytes: *const [10]u8) AiffErrors!u32 {
+            const sign_exponent: u16 = std.mem.readInt(u16, bytes[0..2], .big);
+            const mantissa: u64 = std.mem.readInt(u64, bytes[2..10], .big);
+
+            if (sign_exponent & 0x8000 != 0)
+                return error.InvalidAiffFile;
+
+            const exponent: i32 = @as(i32, sign_exponent) - 16383;
+            if (mantissa == 0 or exponent < -1)
+                return 0;
+            if (exponent > 31)
+                return error.InvalidAiffFile;
+
+            const shift: u7 = @intCast(63 - exponent);
+            const value: u64 = (if (shift == 64) 0 else mantissa >> @intCast(shift)) + ((mantissa >> @intCast(shift - 1)) & 1);
+            if (value > std.math.maxInt(u32))
+                return error.InvalidAiffFile;
+
+            return @intCast(value);
+        }
+
+        test "extended precision sample rates" {
+            const rates = [_]u32{ 1, 8000, 22050, 44100, 48000, 96000, 192000, std.math.maxInt(u32) };
+            for (rates) |rate| {
+                try testing.expectEqual(try decodeExtended(&encodeExtended(rate)), rate);
+            }
+
+            // 44100 Hz, as written by most tools
+            try testing.expectEqualSlices(u8, &encodeExtended(44100), &.{ 0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0 });
+            // 22254.5454... Hz, a rate of old Macintosh computers
+            try testing.expectEqual(try decodeExtended(&.{ 0x40, 0x0D, 0xAD, 0xDD, 0x17, 0x45, 0xD1, 0x74, 0x5D, 0x17 }), 22255);
+        }
+
+        test "write & read every encoding" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 0.0, 0.5, -0.5, 1.0, -1.0, 0.25 };
+
+            const encodings = [_]WriteOptions{
+                .{ .bits = 8, .format_code = .pcm },
+                .{ .bits = 16, .format_code = .pcm },
+                .{ .bits = 24, .format_code = .pcm },
+                .{ .bits = 32, .format_code = .pcm },
+                .{ .bits = 32, .format_code = .ieee_float },
+                .{ .bits = 64, .format_code = .ieee_float },
+            };
+
+            for (encodings) |options| {
+                var buffer: [256]u8 = undefined;
+                var writer = std.Io.Writer.fixed(&buffer);
+                try Self.write(samples, 48000, 2, &writer, options);
+
+                const written: []const u8 = writer.buffered();
+                try testing.expectEqual(written.len % 2, 0);
+                try testing.expectEqualSlices(u8, written[8..12], if (options.format_code == .pcm) "AIFF" else "AIFC");
+
+                var reader = std.Io.Reader.fixed(written);
+                const decoded = try Self.read(allocator, &reader);
+                defer allocator.free(decoded.samples);
+
+                try testing.expectEqual(decoded.sample_rate, 48000);
+                try testing.expectEqual(decoded.channels, 2);
+                try testing.expectEqual(decoded.samples.len, samples.len);
+
+                const tolerance: T = 1.0 / @as(T, @floatFromInt(@as(u64, 1) << @intCast(options.bits - 1)));
+                for (samples, decoded.samples) |expected, actual| {
+                    try testing.expectApproxEqAbs(expected, actual, tolerance);
+                }
+            }
+        }
+
+        test "read little-endian AIFF-C" {
+            const allocator = testing.allocator;
+            const bytes = "FORM" ++ "\x00\x00\x00\x36" ++ "AIFC" ++
+                "COMM" ++ "\x00\x00\x00\x16" ++ "\x00\x01" ++ "\x00\x00\x00\x02" ++ "\x00\x10" ++
+                "\x40\x0E\xAC\x44\x00\x00\x00\x00\x00\x00" ++ "sowt" ++
+                "SSND" ++ "\x00\x00\x00\x0c" ++ "\x00\x00\x00\x00" ++ "\x00\x00\x00\x00" ++ "\xff\x7f" ++ "\x01\x80";
+
+            var reader = std.Io.Reader.fixed(bytes);
+            const decoded = try Self.read(allocator, &reader);
+            defer allocator.free(decoded.samples);
+
+            try testing.expectEqual(decoded.sample_rate, 44100);
+            try testing.expectEqualSlices(T, decoded.samples, &.{ 1.0, -1.0 });
+        }
+
+        test "invalid files and options" {
+            const allocator = testing.allocator;
+
+            var riff = std.Io.Reader.fixed("RIFF\x00\x00\x00\x04WAVE");
+            try testing.expectError(error.InvalidAiffFile, Self.read(allocator, &riff));
+
+            var no_comm = std.Io.Reader.fixed("FORM\x00\x00\x00\x04AIFF");
+            try testing.expectError(error.InvalidAiffFile, Self.read(allocator, &no_comm));
+
+            const ulaw = "FORM" ++ "\x00\x00\x00\x22" ++ "AIFC" ++
+                "COMM" ++ "\x00\x00\x00\x16" ++ "\x00\x01" ++ "\x00\x00\x00\x00" ++ "\x00\x10" ++
+                "\x40\x0E\xAC\x44\x00\x00\x00\x00\x00\x00" ++ "ulaw";
+            var ulaw_reader = std.Io.Reader.fixed(ulaw);
+            try testing.expectError(error.UnsupportedCompression, Self.read(allocator, &ulaw_reader));
+
+            var buffer: [256]u8 = undefined;
+            var writer = std.Io.Writer.fixed(&buffer);
+            try testing.expectError(error.UnsupportedBits, Self.write(&.{0.0}, 44100, 1, &writer, .{ .bits = 12, .format_code = .pcm }));
+            try testing.expectError(error.UnsupportedBits, Self.write(&.{0.0}, 44100, 1, &writer, .{ .bits = 16, .format_code = .ieee_float }));
+            try testing.expectError(error.LengthMismatch, Self.write(&.{0.0}, 44100, 2, &writer, .{ .bits = 16, .format_code = .pcm }));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
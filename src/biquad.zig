@@ -0,0 +1,502 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+const Parameter = @import("./root.zig").Parameter;
+
+/// Biquad type function: Creates a Biquad filter type for the specified sample type.
+///
+/// Biquad is a second-order IIR filter. Its coefficients are computed with the formulas from
+/// Robert Bristow-Johnson's "Audio EQ Cookbook" for low-pass, high-pass, band-pass, notch,
+/// all-pass, peaking EQ, low shelf and high shelf responses.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Biquad = lightmix.Biquad;
+///
+/// // Every channel of the interleaved samples is filtered independently
+/// try wave.filter_with(Biquad(f64).Options, Biquad(f64).filter, .{
+///     .kind = .low_pass,
+///     .cutoff = 1000.0,
+///     .q = 0.707,
+/// });
+///
+/// // Cutoff, Q and gain can also follow control waves
+/// try wave.filter_with(Biquad(f64).ModulatedOptions, Biquad(f64).modulatedFilter, .{
+///     .kind = .low_pass,
+///     .cutoff = .{ .control = cutoff_wave },
+/// });
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        // Coefficients normalized by a0
+        b0: T,
+        b1: T,
+        b2: T,
+        a1: T,
+        a2: T,
+
+        const Self = @This();
+
+        /// Filter responses from the Audio EQ Cookbook.
+        pub const Kind = enum {
+            low_pass,
+            high_pass,
+            /// Band-pass with a constant 0 dB peak gain
+            band_pass,
+            notch,
+            all_pass,
+            /// Peaking EQ, boosting or cutting around `cutoff` by `gain` dB
+            peaking,
+            /// Boosts or cuts frequencies below `cutoff` by `gain` dB
+            low_shelf,
+            /// Boosts or cuts frequencies above `cutoff` by `gain` dB
+            high_shelf,
+        };
+
+        /// Options for designing a biquad filter.
+        pub const Options = struct {
+            /// Filter response
+            kind: Kind,
+            /// Cutoff (or center) frequency in Hz
+            cutoff: T,
+            /// Quality factor. The default gives a Butterworth response for low-pass and high-pass filters.
+            q: T = std.math.sqrt1_2,
+            /// Gain in dB. Only used by `.peaking`, `.low_shelf` and `.high_shelf`.
+            gain: T = 0.0,
+        };
+
+        /// Computes the filter coefficients.
+        ///
+        /// ## Parameters
+        /// - `options`: Filter response, cutoff frequency, Q and gain
+        /// - `sample_rate`: Samples per second of the wave to filter
+        ///
+        /// ## Returns
+        /// A Biquad holding the normalized coefficients
+        pub fn init(options: Options, sample_rate: u32) Self {
+            const w0: T = 2.0 * std.math.pi * options.cutoff / @as(T, @floatFromInt(sample_rate));
+            const cos_w0: T = @cos(w0);
+            const alpha: T = @sin(w0) / (2.0 * options.q);
+            const a: T = @exp(options.gain / 40.0 * std.math.ln10);
+            const two_sqrt_a_alpha: T = 2.0 * @sqrt(a) * alpha;
+
+            // b0, b1, b2, a0, a1, a2
+            const c: [6]T = switch (options.kind) {
+                .low_pass => .{ (1.0 - cos_w0) / 2.0, 1.0 - cos_w0, (1.0 - cos_w0) / 2.0, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha },
+                .high_pass => .{ (1.0 + cos_w0) / 2.0, -(1.0 + cos_w0), (1.0 + cos_w0) / 2.0, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha },
+                .band_pass => .{ alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha },
+                .notch => .{ 1.0, -2.0 * cos_w0, 1.0, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha },
+                .all_pass => .{ 1.0 - alpha, -2.0 * cos_w0, 1.0 + alpha, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha },
+                .peaking => .{ 1.0 + alpha * a, -2.0 * cos_w0, 1.0 - alpha * a, 1.0 + alpha / a, -2.0 * cos_w0, 1.0 - alpha / a },
+                .low_shelf => .{
+                    a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+                    2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+                    a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+                    (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+                    -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+                    (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+                },
+                .high_shelf => .{
+                    a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+                    -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+                    a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+                    (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+                    2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+                    (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+                },
+            };
+
+            return Self{
+                .b0 = c[0] / c[3],
+                .b1 = c[1] / c[3],
+                .b2 = c[2] / c[3],
+                .a1 = c[4] / c[3],
+                .a2 = c[5] / c[3],
+            };
+        }
+
+        /// Returns the magnitude of the frequency response at `frequency` Hz.
+        pub fn magnitudeAt(self: Self, frequency: T, sample_rate: u32) T {
+            const w: T = 2.0 * std.math.pi * frequency / @as(T, @floatFromInt(sample_rate));
+
+            const num_re: T = self.b0 + self.b1 * @cos(w) + self.b2 * @cos(2.0 * w);
+            const num_im: T = -(self.b1 * @sin(w) + self.b2 * @sin(2.0 * w));
+            const den_re: T = 1.0 + self.a1 * @cos(w) + self.a2 * @cos(2.0 * w);
+            const den_im: T = -(self.a1 * @sin(w) + self.a2 * @sin(2.0 * w));
+
+            return @sqrt((num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im));
+        }
+
+        /// Delay line of a single channel (transposed direct form II).
+        pub const State = struct {
+            z1: T = 0.0,
+            z2: T = 0.0,
+
+            /// Filters one sample and advances the delay line.
+            pub fn process(self: *State, coefficients: Self, x: T) T {
+                const y: T = coefficients.b0 * x + self.z1;
+                self.z1 = coefficients.b1 * x - coefficients.a1 * y + self.z2;
+                self.z2 = coefficients.b2 * x - coefficients.a2 * y;
+
+                return y;
+            }
+        };
+
+        /// A filter function which applies a biquad filter to every channel of a wave.
+        ///
+        /// Use it through `Wave(T).filter_with`.
+        ///
+        /// ## Example
+        /// ```zig
+        /// try wave.filter_with(Biquad(f64).Options, Biquad(f64).filter, .{
+        ///     .kind = .peaking,
+        ///     .cutoff = 2500.0,
+        ///     .q = 1.0,
+        ///     .gain = -6.0,
+        /// });
+        /// ```
+        pub fn filter(comptime _: type, original_wave: Wave(T), options: Options) std.mem.Allocator.Error!Wave(T) {
+            const coefficients: Self = Self.init(options, original_wave.sample_rate);
+            const channels: usize = original_wave.channels;
+
+            const samples: []T = try original_wave.allocator.alloc(T, original_wave.samples.len);
+
+            // Each channel is processed with its own delay line
+            for (0..channels) |ch| {
+                var state: State = .{};
+
+                var i: usize = ch;
+                while (i < samples.len) : (i += channels) {
+                    samples[i] = state.process(coefficients, original_wave.samples[i]);
+                }
+            }
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = original_wave.allocator,
+
+                .sample_rate = original_wave.sample_rate,
+                .channels = original_wave.channels,
+            };
+        }
+
+        /// Options for a biquad filter whose settings change over time.
+        ///
+        /// Control waves must have as many frames as the filtered wave.
+        pub const ModulatedOptions = struct {
+            /// Filter response
+            kind: Kind,
+            /// Cutoff (or center) frequency in Hz
+            cutoff: Parameter(T),
+            /// Quality factor (resonance)
+            q: Parameter(T) = .{ .constant = std.math.sqrt1_2 },
+            /// Gain in dB. Only used by `.peaking`, `.low_shelf` and `.high_shelf`.
+            gain: Parameter(T) = .{ .constant = 0.0 },
+            /// Number of frames between two coefficient updates
+            block_size: usize = 32,
+        };
+
+        /// A filter function which applies a biquad filter with time-varying settings
+        /// to every channel of a wave.
+        ///
+        /// The coefficients are recalculated at the start of every block of
+        /// `options.block_size` frames, from the parameter values at that frame.
+        /// Use it through `Wave(T).filter_with`.
+        ///
+        /// ## Errors
+        /// - `ControlLengthMismatch`: A control wave doesn't have as many frames as the filtered wave
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        ///
+        /// ## Example
+        /// ```zig
+        /// // Sweep the cutoff frequency with an envelope rendered as a control wave
+        /// try wave.filter_with(Biquad(f64).ModulatedOptions, Biquad(f64).modulatedFilter, .{
+        ///     .kind = .low_pass,
+        ///     .cutoff = .{ .control = cutoff_wave },
+        ///     .q = .{ .constant = 4.0 },
+        /// });
+        /// ```
+        pub fn modulatedFilter(
+            comptime _: type,
+            original_wave: Wave(T),
+            options: ModulatedOptions,
+        ) (Parameter(T).ParameterErrors || std.mem.Allocator.Error)!Wave(T) {
+            const allocator = original_wave.allocator;
+            const channels: usize = original_wave.channels;
+            const frames: usize = if (channels == 0) 0 else original_wave.samples.len / channels;
+
+            try options.cutoff.validate(frames);
+            try options.q.validate(frames);
+            try options.gain.validate(frames);
+
+            // Each channel is processed with its own delay line
+            const states: []State = try allocator.alloc(State, channels);
+            defer allocator.free(states);
+            @memset(states, State{});
+
+            const samples: []T = try allocator.alloc(T, original_wave.samples.len);
+
+            const block_size: usize = @max(options.block_size, 1);
+            var coefficients: Self = undefined;
+
+            for (0..frames) |frame| {
+                if (frame % block_size == 0) {
+                    coefficients = Self.init(.{
+                        .kind = options.kind,
+                        .cutoff = options.cutoff.at(frame),
+                        .q = options.q.at(frame),
+                        .gain = options.gain.at(frame),
+                    }, original_wave.sample_rate);
+                }
+
+                for (0..channels) |ch| {
+                    const i: usize = frame * channels + ch;
+                    samples[i] = states[ch].process(coefficients, original_wave.samples[i]);
+                }
+            }
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = allocator,
+
+                .sample_rate = original_wave.sample_rate,
+                .channels = original_wave.channels,
+            };
+        }
+
+        fn expectCoefficients(expected: [5]T, actual: Self) !void {
+            try testing.expectApproxEqAbs(expected[0], actual.b0, 0.000000001);
+            try testing.expectApproxEqAbs(expected[1], actual.b1, 0.000000001);
+            try testing.expectApproxEqAbs(expected[2], actual.b2, 0.000000001);
+            try testing.expectApproxEqAbs(expected[3], actual.a1, 0.000000001);
+            try testing.expectApproxEqAbs(expected[4], actual.a2, 0.000000001);
+        }
+
+        test "coefficients" {
+            const q: T = 0.7071067811865476;
+
+            try expectCoefficients(.{ 0.003916126660547383, 0.007832253321094766, 0.003916126660547383, -1.815341082704568, 0.8310055893467576 }, Self.init(.{ .kind = .low_pass, .cutoff = 1000.0, .q = q }, 48000));
+            try expectCoefficients(.{ 0.9115866680128315, -1.823173336025663, 0.9115866680128315, -1.815341082704568, 0.8310055893467576 }, Self.init(.{ .kind = .high_pass, .cutoff = 1000.0, .q = q }, 48000));
+            try expectCoefficients(.{ 0.08449720532662121, 0.0, -0.08449720532662121, -1.815341082704568, 0.8310055893467576 }, Self.init(.{ .kind = .band_pass, .cutoff = 1000.0, .q = q }, 48000));
+            try expectCoefficients(.{ 0.9155027946733788, -1.815341082704568, 0.9155027946733788, -1.815341082704568, 0.8310055893467576 }, Self.init(.{ .kind = .notch, .cutoff = 1000.0, .q = q }, 48000));
+            try expectCoefficients(.{ 0.8310055893467576, -1.815341082704568, 1.0, -1.815341082704568, 0.8310055893467576 }, Self.init(.{ .kind = .all_pass, .cutoff = 1000.0, .q = q }, 48000));
+            try expectCoefficients(.{ 1.0610424252634374, -1.8612731439964758, 0.816291571321481, -1.8612731439964758, 0.8773339965849185 }, Self.init(.{ .kind = .peaking, .cutoff = 1000.0, .q = q, .gain = 6.0 }, 48000));
+            try expectCoefficients(.{ 1.0325624832475901, -1.8388568718996408, 0.8287476843124699, -1.84445686716092, 0.855710172298781 }, Self.init(.{ .kind = .low_shelf, .cutoff = 1000.0, .q = q, .gain = 6.0 }, 48000));
+            try expectCoefficients(.{ 1.9323405094996573, -3.5641187224398743, 1.6535234303238662, -1.7808674067995511, 0.8026126241832001 }, Self.init(.{ .kind = .high_shelf, .cutoff = 1000.0, .q = q, .gain = 6.0 }, 48000));
+        }
+
+        test "frequency response" {
+            const sample_rate: u32 = 48000;
+            const nyquist: T = 24000.0;
+            // 6 dB as a linear amplitude ratio
+            const gain: T = 1.9952623149688795;
+
+            const low_pass = Self.init(.{ .kind = .low_pass, .cutoff = 1000.0 }, sample_rate);
+            try testing.expectApproxEqAbs(low_pass.magnitudeAt(0.0, sample_rate), 1.0, 0.000001);
+            try testing.expectApproxEqAbs(low_pass.magnitudeAt(1000.0, sample_rate), std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(low_pass.magnitudeAt(nyquist, sample_rate), 0.0, 0.000001);
+
+            const high_pass = Self.init(.{ .kind = .high_pass, .cutoff = 1000.0 }, sample_rate);
+            try testing.expectApproxEqAbs(high_pass.magnitudeAt(0.0, sample_rate), 0.0, 0.000001);
+            try testing.expectApproxEqAbs(high_pass.magnitudeAt(1000.0, sample_rate), std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(high_pass.magnitudeAt(nyquist, sample_rate), 1.0, 0.000001);
+
+            const band_pass = Self.init(.{ .kind = .band_pass, .cutoff = 1000.0, .q = 2.0 }, sample_rate);
+            try testing.expectApproxEqAbs(band_pass.magnitudeAt(0.0, sample_rate), 0.0, 0.000001);
+            try testing.expectApproxEqAbs(band_pass.magnitudeAt(1000.0, sample_rate), 1.0, 0.000001);
+            try testing.expectApproxEqAbs(band_pass.magnitudeAt(nyquist, sample_rate), 0.0, 0.000001);
+
+            const notch = Self.init(.{ .kind = .notch, .cutoff = 1000.0, .q = 2.0 }, sample_rate);
+            try testing.expectApproxEqAbs(notch.magnitudeAt(0.0, sample_rate), 1.0, 0.000001);
+            try testing.expectApproxEqAbs(notch.magnitudeAt(1000.0, sample_rate), 0.0, 0.000001);
+            try testing.expectApproxEqAbs(notch.magnitudeAt(nyquist, sample_rate), 1.0, 0.000001);
+
+            const all_pass = Self.init(.{ .kind = .all_pass, .cutoff = 1000.0 }, sample_rate);
+            for ([_]T{ 0.0, 100.0, 1000.0, 5000.0, nyquist }) |frequency| {
+                try testing.expectApproxEqAbs(all_pass.magnitudeAt(frequency, sample_rate), 1.0, 0.000001);
+            }
+
+            const peaking = Self.init(.{ .kind = .peaking, .cutoff = 1000.0, .gain = 6.0 }, sample_rate);
+            try testing.expectApproxEqAbs(peaking.magnitudeAt(0.0, sample_rate), 1.0, 0.000001);
+            try testing.expectApproxEqAbs(peaking.magnitudeAt(1000.0, sample_rate), gain, 0.000001);
+            try testing.expectApproxEqAbs(peaking.magnitudeAt(nyquist, sample_rate), 1.0, 0.000001);
+
+            const low_shelf = Self.init(.{ .kind = .low_shelf, .cutoff = 1000.0, .gain = 6.0 }, sample_rate);
+            try testing.expectApproxEqAbs(low_shelf.magnitudeAt(0.0, sample_rate), gain, 0.000001);
+            try testing.expectApproxEqAbs(low_shelf.magnitudeAt(nyquist, sample_rate), 1.0, 0.000001);
+
+            const high_shelf = Self.init(.{ .kind = .high_shelf, .cutoff = 1000.0, .gain = 6.0 }, sample_rate);
+            try testing.expectApproxEqAbs(high_shelf.magnitudeAt(0.0, sample_rate), 1.0, 0.000001);
+            try testing.expectApproxEqAbs(high_shelf.magnitudeAt(nyquist, sample_rate), gain, 0.000001);
+        }
+
+        test "filter attenuates a sine by the magnitude response" {
+            const allocator = testing.allocator;
+            const sample_rate: u32 = 48000;
+            const frequency: T = 10000.0;
+
+            var samples: [48000]T = undefined;
+            for (0..samples.len) |i| {
+                samples[i] = @sin(2.0 * std.math.pi * frequency * @as(T, @floatFromInt(i)) / @as(T, @floatFromInt(sample_rate)));
+            }
+
+            var wave = try Wave(T).init(samples[0..], allocator, .{
+                .sample_rate = sample_rate,
+                .channels = 1,
+            });
+            const options: Options = .{ .kind = .low_pass, .cutoff = 1000.0 };
+            try wave.filter_with(Options, Self.filter, options);
+            defer wave.deinit();
+
+            // RMS of the steady state, over an integer number of periods (24 samples = 5 periods)
+            const tail: []const T = wave.samples[wave.samples.len - 4800 ..];
+            var energy: T = 0.0;
+            for (tail) |sample| {
+                energy += sample * sample;
+            }
+            const amplitude: T = @sqrt(2.0 * energy / @as(T, @floatFromInt(tail.len)));
+
+            const expected: T = Self.init(options, sample_rate).magnitudeAt(frequency, sample_rate);
+            try testing.expectApproxEqAbs(amplitude, expected, 0.000001);
+            try testing.expect(amplitude < 0.02);
+        }
+
+        test "filter processes every channel independently" {
+            const allocator = testing.allocator;
+            const options: Options = .{ .kind = .high_pass, .cutoff = 100.0 };
+
+            var mono_samples: [64]T = undefined;
+            var stereo_samples: [128]T = undefined;
+            for (0..mono_samples.len) |i| {
+                mono_samples[i] = if (i % 8 < 4) 1.0 else -0.5;
+                stereo_samples[i * 2] = mono_samples[i];
+                stereo_samples[i * 2 + 1] = 0.0;
+            }
+
+            var mono = try Wave(T).init(mono_samples[0..], allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            try mono.filter_with(Options, Self.filter, options);
+            defer mono.deinit();
+
+            var stereo = try Wave(T).init(stereo_samples[0..], allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            try stereo.filter_with(Options, Self.filter, options);
+            defer stereo.deinit();
+
+            try testing.expectEqual(stereo.channels, 2);
+            for (0..mono_samples.len) |i| {
+                try testing.expectEqual(mono.samples[i], stereo.samples[i * 2]);
+                try testing.expectEqual(@as(T, 0.0), stereo.samples[i * 2 + 1]);
+            }
+        }
+
+        test "modulatedFilter with constant parameters matches filter" {
+            const allocator = testing.allocator;
+
+            var samples: [256]T = undefined;
+            for (0..samples.len) |i| {
+                samples[i] = if (i % 16 < 8) 1.0 else -1.0;
+            }
+
+            var constant = try Wave(T).init(samples[0..], allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            try constant.filter_with(Options, Self.filter, .{ .kind = .band_pass, .cutoff = 2000.0, .q = 3.0 });
+            defer constant.deinit();
+
+            var modulated = try Wave(T).init(samples[0..], allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            try modulated.filter_with(ModulatedOptions, Self.modulatedFilter, .{
+                .kind = .band_pass,
+                .cutoff = .{ .constant = 2000.0 },
+                .q = .{ .constant = 3.0 },
+                .block_size = 7,
+            });
+            defer modulated.deinit();
+
+            try testing.expectEqualSlices(T, constant.samples, modulated.samples);
+        }
+
+        test "modulatedFilter follows a control wave" {
+            const allocator = testing.allocator;
+            const sample_rate: u32 = 48000;
+
+            var samples: [24000]T = undefined;
+            var cutoff: [24000]T = undefined;
+            for (0..samples.len) |i| {
+                samples[i] = @sin(2.0 * std.math.pi * 5000.0 * @as(T, @floatFromInt(i)) / @as(T, @floatFromInt(sample_rate)));
+                // The cutoff frequency jumps from 200 Hz to 20000 Hz at the middle of the wave
+                cutoff[i] = if (i < 12000) 200.0 else 20000.0;
+            }
+
+            const control = try Wave(T).init(cutoff[0..], allocator, .{
+                .sample_rate = sample_rate,
+                .channels = 1,
+            });
+            defer control.deinit();
+
+            var wave = try Wave(T).init(samples[0..], allocator, .{
+                .sample_rate = sample_rate,
+                .channels = 1,
+            });
+            try wave.filter_with(ModulatedOptions, Self.modulatedFilter, .{
+                .kind = .low_pass,
+                .cutoff = .{ .control = control },
+            });
+            defer wave.deinit();
+
+            const rms = struct {
+                fn f(slice: []const T) T {
+                    var energy: T = 0.0;
+                    for (slice) |sample| {
+                        energy += sample * sample;
+                    }
+                    return @sqrt(energy / @as(T, @floatFromInt(slice.len)));
+                }
+            }.f;
+
+            try testing.expect(rms(wave.samples[6000..12000]) < 0.01);
+            try testing.expect(rms(wave.samples[18000..24000]) > 0.6);
+        }
+
+        test "modulatedFilter rejects a control wave with a different length" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 0.0, 0.0, 0.0, 0.0 };
+            const cutoff: []const T = &[_]T{ 1000.0, 1000.0, 1000.0 };
+
+            const control = try Wave(T).init(cutoff, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer control.deinit();
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try testing.expectError(error.ControlLengthMismatch, wave.filter_with(ModulatedOptions, Self.modulatedFilter, .{
+                .kind = .low_pass,
+                .cutoff = .{ .control = control },
+            }));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
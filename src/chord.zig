@@ -0,0 +1,512 @@
+const std = @import("std");
+const testing = std.testing;
+const Tuning = @import("./root.zig").Tuning;
+const Timeline = @import("./root.zig").Timeline;
+const Midi = @import("./root.zig").Midi;
+
+/// Chord type function: Creates chords for the specified sample type.
+///
+/// A chord is a root, the intervals of its tones from the root, and how they are arranged:
+/// an inversion, a voicing and a bass note. Its notes are note numbers as in MIDI (60 is C4),
+/// which a `Tuning` turns into frequencies, and its arpeggios are MIDI note sequences which
+/// `Composer(T).appendMidi` renders.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const chord = try Chord(f64).parse("Am/E", 4);
+///
+/// const frequencies = try chord.frequencies(allocator, .{}); // E4, A4 and C5, with A4 at 440 Hz
+/// defer allocator.free(frequencies);
+///
+/// const arpeggio = try chord.arpeggiate(allocator, .{ .pattern = .up_down, .count = 8 });
+/// defer arpeggio.deinit();
+/// try composer.appendMidi(arpeggio, piano, .{});
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        /// Note number of the root, where 60 is C4
+        root: i32,
+        /// Semitones from the root to each tone, ascending
+        intervals: []const i32 = major,
+        /// Number of times the lowest tone is moved up an octave
+        inversion: usize = 0,
+        /// Arrangement of the tones after the inversion
+        voicing: Voicing = .close,
+        /// Pitch class of the lowest note, as in "Am/E". When it is a chord tone, the tones above it
+        /// are moved down an octave, and `inversion` is ignored. Otherwise, it is added below the chord.
+        bass: ?Tuning(T).Code = null,
+
+        const Self = @This();
+
+        /// Major triad
+        pub const major: []const i32 = &.{ 0, 4, 7 };
+        /// Minor triad
+        pub const minor: []const i32 = &.{ 0, 3, 7 };
+        /// Diminished triad
+        pub const diminished: []const i32 = &.{ 0, 3, 6 };
+        /// Augmented triad
+        pub const augmented: []const i32 = &.{ 0, 4, 8 };
+        /// Suspended second
+        pub const sus2: []const i32 = &.{ 0, 2, 7 };
+        /// Suspended fourth
+        pub const sus4: []const i32 = &.{ 0, 5, 7 };
+        /// Power chord, the root and the fifth
+        pub const power: []const i32 = &.{ 0, 7 };
+        /// Major sixth
+        pub const major6: []const i32 = &.{ 0, 4, 7, 9 };
+        /// Minor sixth
+        pub const minor6: []const i32 = &.{ 0, 3, 7, 9 };
+        /// Six-nine
+        pub const six_nine: []const i32 = &.{ 0, 4, 7, 9, 14 };
+        /// Dominant seventh
+        pub const dominant7: []const i32 = &.{ 0, 4, 7, 10 };
+        /// Major seventh
+        pub const major7: []const i32 = &.{ 0, 4, 7, 11 };
+        /// Minor seventh
+        pub const minor7: []const i32 = &.{ 0, 3, 7, 10 };
+        /// Minor major seventh
+        pub const minor_major7: []const i32 = &.{ 0, 3, 7, 11 };
+        /// Half-diminished seventh
+        pub const half_diminished7: []const i32 = &.{ 0, 3, 6, 10 };
+        /// Diminished seventh
+        pub const diminished7: []const i32 = &.{ 0, 3, 6, 9 };
+        /// Augmented seventh
+        pub const augmented7: []const i32 = &.{ 0, 4, 8, 10 };
+        /// Dominant seventh with a suspended fourth
+        pub const dominant7_sus4: []const i32 = &.{ 0, 5, 7, 10 };
+        /// Major triad with an added ninth
+        pub const add9: []const i32 = &.{ 0, 4, 7, 14 };
+        /// Minor triad with an added ninth
+        pub const minor_add9: []const i32 = &.{ 0, 3, 7, 14 };
+        /// Dominant ninth
+        pub const dominant9: []const i32 = &.{ 0, 4, 7, 10, 14 };
+        /// Major ninth
+        pub const major9: []const i32 = &.{ 0, 4, 7, 11, 14 };
+        /// Minor ninth
+        pub const minor9: []const i32 = &.{ 0, 3, 7, 10, 14 };
+        /// Dominant eleventh
+        pub const dominant11: []const i32 = &.{ 0, 4, 7, 10, 14, 17 };
+        /// Dominant thirteenth, without the eleventh
+        pub const dominant13: []const i32 = &.{ 0, 4, 7, 10, 14, 21 };
+
+        /// Chord symbols after the root, and their intervals
+        const symbols = [_]struct { name: []const u8, intervals: []const i32 }{
+            .{ .name = "", .intervals = major },
+            .{ .name = "M", .intervals = major },
+            .{ .name = "maj", .intervals = major },
+            .{ .name = "m", .intervals = minor },
+            .{ .name = "min", .intervals = minor },
+            .{ .name = "-", .intervals = minor },
+            .{ .name = "dim", .intervals = diminished },
+            .{ .name = "o", .intervals = diminished },
+            .{ .name = "aug", .intervals = augmented },
+            .{ .name = "+", .intervals = augmented },
+            .{ .name = "sus2", .intervals = sus2 },
+            .{ .name = "sus4", .intervals = sus4 },
+            .{ .name = "sus", .intervals = sus4 },
+            .{ .name = "5", .intervals = power },
+            .{ .name = "6", .intervals = major6 },
+            .{ .name = "m6", .intervals = minor6 },
+            .{ .name = "6/9", .intervals = six_nine },
+            .{ .name = "7", .intervals = dominant7 },
+            .{ .name = "maj7", .intervals = major7 },
+            .{ .name = "M7", .intervals = major7 },
+            .{ .name = "m7", .intervals = minor7 },
+            .{ .name = "min7", .intervals = minor7 },
+            .{ .name = "-7", .intervals = minor7 },
+            .{ .name = "mMaj7", .intervals = minor_major7 },
+            .{ .name = "mM7", .intervals = minor_major7 },
+            .{ .name = "m(maj7)", .intervals = minor_major7 },
+            .{ .name = "m7b5", .intervals = half_diminished7 },
+            .{ .name = "ø", .intervals = half_diminished7 },
+            .{ .name = "ø7", .intervals = half_diminished7 },
+            .{ .name = "dim7", .intervals = diminished7 },
+            .{ .name = "o7", .intervals = diminished7 },
+            .{ .name = "aug7", .intervals = augmented7 },
+            .{ .name = "+7", .intervals = augmented7 },
+            .{ .name = "7#5", .intervals = augmented7 },
+            .{ .name = "7sus4", .intervals = dominant7_sus4 },
+            .{ .name = "add9", .intervals = add9 },
+            .{ .name = "madd9", .intervals = minor_add9 },
+            .{ .name = "9", .intervals = dominant9 },
+            .{ .name = "maj9", .intervals = major9 },
+            .{ .name = "M9", .intervals = major9 },
+            .{ .name = "m9", .intervals = minor9 },
+            .{ .name = "11", .intervals = dominant11 },
+            .{ .name = "13", .intervals = dominant13 },
+        };
+
+        /// Errors that can occur when parsing chord symbols.
+        pub const ChordErrors = error{
+            /// The text is not a chord symbol such as "C", "Cmaj7" or "Am/E"
+            InvalidChordSymbol,
+        };
+
+        /// Arrangements of the tones of a chord.
+        pub const Voicing = enum {
+            /// The tones as they are stacked
+            close,
+            /// The second highest tone is moved down an octave
+            drop2,
+            /// The third highest tone is moved down an octave
+            drop3,
+            /// Every other tone from the second lowest is moved up an octave
+            spread,
+        };
+
+        /// Orders of the notes of an arpeggio.
+        pub const Pattern = enum {
+            /// From the lowest note to the highest
+            up,
+            /// From the highest note to the lowest
+            down,
+            /// Up, then down, without repeating the highest and the lowest notes
+            up_down,
+        };
+
+        /// Options for `arpeggiate`.
+        pub const ArpeggioOptions = struct {
+            /// Order of the notes
+            pattern: Pattern = .up,
+            /// Number of notes, repeating the pattern. `null` plays the pattern once.
+            count: ?usize = null,
+            /// Tick of the first note
+            start: usize = 0,
+            /// Ticks between the starts of two notes
+            step: usize = 240,
+            /// Length of each note, in ticks. `null` holds each note until the next one.
+            duration: ?usize = null,
+            /// Strength of the notes, from 1 to 127
+            velocity: u7 = 100,
+            /// MIDI channel of the notes
+            channel: u4 = 0,
+            /// Tempo map of the arpeggio, whose beats last `ticks_per_beat` ticks
+            timeline: Timeline(T) = .{},
+        };
+
+        /// Parses a chord symbol: an uppercase root letter with any number of sharps (`#`) and
+        /// flats (`b`), a quality such as "m", "7", "maj7", "m7b5", "dim7", "sus4" or "add9",
+        /// and an optional bass note after a slash.
+        ///
+        /// ## Parameters
+        /// - `symbol`: The chord symbol, such as "C", "Cmaj7", "F#m7b5" or "Am/E"
+        /// - `octave`: Octave of the root, where C4 is the middle C
+        ///
+        /// ## Returns
+        /// The chord, in close voicing without inversion
+        ///
+        /// ## Errors
+        /// - `InvalidChordSymbol`: The text is not a chord symbol, or the octave is so far from
+        ///   the 4th that the root's note number overflows
+        pub fn parse(symbol: []const u8, octave: i32) ChordErrors!Self {
+            var root_length: usize = 0;
+            const root_class: i32 = pitchClass(symbol, &root_length) orelse return error.InvalidChordSymbol;
+
+            var quality: []const u8 = symbol[root_length..];
+            var bass_code: ?Tuning(T).Code = null;
+            if (intervalsOf(quality) == null) {
+                if (std.mem.lastIndexOfScalar(u8, quality, '/')) |slash| {
+                    var bass_length: usize = 0;
+                    const bass_class: i32 = pitchClass(quality[slash + 1 ..], &bass_length) orelse return error.InvalidChordSymbol;
+                    if (slash + 1 + bass_length != quality.len)
+                        return error.InvalidChordSymbol;
+
+                    bass_code = @enumFromInt(@as(u8, @intCast(@mod(bass_class, 12))));
+                    quality = quality[0..slash];
+                }
+            }
+
+            // Octaves which don't fit a note number are rejected rather than overflowing
+            const octave_start: i32 = std.math.mul(i32, 12, std.math.add(i32, octave, 1) catch return error.InvalidChordSymbol) catch return error.InvalidChordSymbol;
+            return Self{
+                .root = std.math.add(i32, octave_start, root_class) catch return error.InvalidChordSymbol,
+                .intervals = intervalsOf(quality) orelse return error.InvalidChordSymbol,
+                .bass = bass_code,
+            };
+        }
+
+        /// Returns the semitones from C to the note name at the start of the text, which must
+        /// start with an uppercase letter, and sets `length` to its length.
+        fn pitchClass(text: []const u8, length: *usize) ?i32 {
+            if (text.len == 0 or !std.ascii.isUpper(text[0]))
+                return null;
+
+            return Tuning(T).Pitch.parseClass(text, length);
+        }
+
+        fn intervalsOf(quality: []const u8) ?[]const i32 {
+            for (symbols) |entry| {
+                if (std.mem.eql(u8, entry.name, quality))
+                    return entry.intervals;
+            }
+
+            return null;
+        }
+
+        /// Returns the note numbers of the chord, from the lowest.
+        ///
+        /// ## Parameters
+        /// - `self`: The chord
+        /// - `allocator`: Memory allocator for the note numbers
+        ///
+        /// ## Returns
+        /// The note numbers, owned by the caller
+        ///
+        /// ## Errors
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn notes(self: Self, allocator: std.mem.Allocator) std.mem.Allocator.Error![]i32 {
+            var tones: std.array_list.Aligned(i32, null) = .empty;
+            defer tones.deinit(allocator);
+            for (self.intervals) |interval| {
+                try tones.append(allocator, self.root + interval);
+            }
+            if (tones.items.len == 0)
+                return tones.toOwnedSlice(allocator);
+
+            var added_bass: ?i32 = null;
+            if (self.bass) |code| {
+                const bass_class: i32 = @intFromEnum(code);
+                const is_tone: bool = for (tones.items) |tone| {
+                    if (@mod(tone - bass_class, 12) == 0)
+                        break true;
+                } else false;
+
+                if (is_tone) {
+                    // Move the tones above the bass down an octave, from the highest
+                    while (@mod(tones.items[0] - bass_class, 12) != 0) {
+                        tones.items[tones.items.len - 1] -= 12;
+                        std.mem.sort(i32, tones.items, {}, std.sort.asc(i32));
+                    }
+                } else {
+                    added_bass = bass_class;
+                }
+            }
+            if (self.bass == null or added_bass != null) {
+                for (0..self.inversion) |_| {
+                    tones.items[0] += 12;
+                    std.mem.sort(i32, tones.items, {}, std.sort.asc(i32));
+                }
+            }
+
+            const len: usize = tones.items.len;
+            switch (self.voicing) {
+                .close => {},
+                .drop2 => if (len >= 2) {
+                    tones.items[len - 2] -= 12;
+                },
+                .drop3 => if (len >= 3) {
+                    tones.items[len - 3] -= 12;
+                },
+                .spread => {
+                    var i: usize = 1;
+                    while (i < len) : (i += 2) {
+                        tones.items[i] += 12;
+                    }
+                },
+            }
+            std.mem.sort(i32, tones.items, {}, std.sort.asc(i32));
+
+            if (added_bass) |bass_class| {
+                const lowest: i32 = tones.items[0];
+                try tones.insert(allocator, 0, lowest - @mod(lowest - bass_class, 12));
+            }
+
+            return tones.toOwnedSlice(allocator);
+        }
+
+        /// Returns the frequencies of the notes of the chord in a tuning, in Hz, from the lowest.
+        ///
+        /// ## Parameters
+        /// - `self`: The chord
+        /// - `allocator`: Memory allocator for the frequencies
+        /// - `tuning`: The tuning, with its reference pitch
+        ///
+        /// ## Returns
+        /// The frequencies, owned by the caller
+        ///
+        /// ## Errors
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn frequencies(self: Self, allocator: std.mem.Allocator, tuning: Tuning(T)) std.mem.Allocator.Error![]T {
+            const chord_notes: []i32 = try self.notes(allocator);
+            defer allocator.free(chord_notes);
+
+            const result: []T = try allocator.alloc(T, chord_notes.len);
+            for (result, chord_notes) |*hz, number| {
+                hz.* = tuning.frequency(.{ .note = number });
+            }
+
+            return result;
+        }
+
+        /// Plays the notes of the chord one after another, as a MIDI note sequence which
+        /// `Composer(T).appendMidi` renders, or `Midi(T).write` exports.
+        ///
+        /// ## Parameters
+        /// - `self`: The chord
+        /// - `allocator`: Memory allocator for the sequence
+        /// - `options`: Pattern, rhythm and tempo of the arpeggio
+        ///
+        /// ## Returns
+        /// The note sequence, which must be freed with `deinit`
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: A note is out of the MIDI range, from 0 to 127
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn arpeggiate(self: Self, allocator: std.mem.Allocator, options: ArpeggioOptions) (error{InvalidRange} || std.mem.Allocator.Error)!Midi(T) {
+            const chord_notes: []i32 = try self.notes(allocator);
+            defer allocator.free(chord_notes);
+
+            var order: std.array_list.Aligned(i32, null) = .empty;
+            defer order.deinit(allocator);
+            switch (options.pattern) {
+                .up => try order.appendSlice(allocator, chord_notes),
+                .down => for (0..chord_notes.len) |i| {
+                    try order.append(allocator, chord_notes[chord_notes.len - 1 - i]);
+                },
+                .up_down => {
+                    try order.appendSlice(allocator, chord_notes);
+                    var i: usize = chord_notes.len -| 1;
+                    while (i > 1) {
+                        i -= 1;
+                        try order.append(allocator, chord_notes[i]);
+                    }
+                },
+            }
+
+            var sequence: std.array_list.Aligned(Midi(T).Note, null) = .empty;
+            defer sequence.deinit(allocator);
+            if (order.items.len > 0) {
+                for (0..options.count orelse order.items.len) |i| {
+                    const number: i32 = order.items[i % order.items.len];
+                    if (number < 0 or number > 127)
+                        return error.InvalidRange;
+
+                    try sequence.append(allocator, .{
+                        .tick = options.start + i * options.step,
+                        .duration = options.duration orelse options.step,
+                        .key = @intCast(number),
+                        .velocity = options.velocity,
+                        .channel = options.channel,
+                    });
+                }
+            }
+
+            return Midi(T).init(sequence.items, allocator, .{ .timeline = options.timeline });
+        }
+
+        test "parse chord symbols" {
+            const allocator = testing.allocator;
+            const cases = [_]struct { symbol: []const u8, octave: i32, expected: []const i32 }{
+                .{ .symbol = "C", .octave = 4, .expected = &.{ 60, 64, 67 } },
+                .{ .symbol = "Cmaj7", .octave = 4, .expected = &.{ 60, 64, 67, 71 } },
+                .{ .symbol = "Dm7", .octave = 3, .expected = &.{ 50, 53, 57, 60 } },
+                .{ .symbol = "F#dim", .octave = 4, .expected = &.{ 66, 69, 72 } },
+                .{ .symbol = "Bbm7b5", .octave = 3, .expected = &.{ 58, 61, 64, 68 } },
+                .{ .symbol = "G7sus4", .octave = 3, .expected = &.{ 55, 60, 62, 65 } },
+                .{ .symbol = "Ebadd9", .octave = 4, .expected = &.{ 63, 67, 70, 77 } },
+                .{ .symbol = "C6/9", .octave = 4, .expected = &.{ 60, 64, 67, 69, 74 } },
+                .{ .symbol = "Cb", .octave = 4, .expected = &.{ 59, 63, 66 } },
+                // Slash chords: a chord tone in the bass inverts the chord, and another note is added below
+                .{ .symbol = "Am/E", .octave = 4, .expected = &.{ 64, 69, 72 } },
+                .{ .symbol = "C/G", .octave = 4, .expected = &.{ 55, 60, 64 } },
+                .{ .symbol = "C/Bb", .octave = 4, .expected = &.{ 58, 60, 64, 67 } },
+                .{ .symbol = "Dm7/C", .octave = 4, .expected = &.{ 60, 62, 65, 69 } },
+            };
+
+            for (cases) |case| {
+                const chord = try Self.parse(case.symbol, case.octave);
+                const chord_notes = try chord.notes(allocator);
+                defer allocator.free(chord_notes);
+                try testing.expectEqualSlices(i32, case.expected, chord_notes);
+            }
+
+            for ([_][]const u8{ "", "H", "c", "Cxyz", "C/", "C/H", "C/Ebm", "C/e", "Cmaj7/", "Am/E/G" }) |symbol| {
+                try testing.expectError(error.InvalidChordSymbol, Self.parse(symbol, 4));
+            }
+
+            try testing.expectError(error.InvalidChordSymbol, Self.parse("C", std.math.maxInt(i32)));
+            try testing.expectError(error.InvalidChordSymbol, Self.parse("C", 999999999));
+        }
+
+        test "inversions and voicings" {
+            const allocator = testing.allocator;
+            const cases = [_]struct { chord: Self, expected: []const i32 }{
+                .{ .chord = .{ .root = 60, .inversion = 1 }, .expected = &.{ 64, 67, 72 } },
+                .{ .chord = .{ .root = 60, .inversion = 2 }, .expected = &.{ 67, 72, 76 } },
+                .{ .chord = .{ .root = 60, .inversion = 3 }, .expected = &.{ 72, 76, 79 } },
+                .{ .chord = .{ .root = 60, .intervals = major7, .voicing = .drop2 }, .expected = &.{ 55, 60, 64, 71 } },
+                .{ .chord = .{ .root = 60, .intervals = major7, .voicing = .drop3 }, .expected = &.{ 52, 60, 67, 71 } },
+                .{ .chord = .{ .root = 60, .voicing = .spread }, .expected = &.{ 60, 67, 76 } },
+                .{ .chord = .{ .root = 60, .inversion = 1, .voicing = .spread }, .expected = &.{ 64, 72, 79 } },
+                .{ .chord = .{ .root = 60, .inversion = 1, .bass = .d }, .expected = &.{ 62, 64, 67, 72 } },
+                // A chord tone in the bass overrides the inversion
+                .{ .chord = .{ .root = 60, .inversion = 1, .bass = .g }, .expected = &.{ 55, 60, 64 } },
+                .{ .chord = .{ .root = 60, .intervals = dominant9, .bass = .e }, .expected = &.{ 52, 55, 58, 60, 62 } },
+            };
+
+            for (cases) |case| {
+                const chord_notes = try case.chord.notes(allocator);
+                defer allocator.free(chord_notes);
+                try testing.expectEqualSlices(i32, case.expected, chord_notes);
+            }
+        }
+
+        test "frequencies with a reference pitch" {
+            const allocator = testing.allocator;
+            const chord = try Self.parse("C", 5);
+
+            const concert = try chord.frequencies(allocator, .{});
+            defer allocator.free(concert);
+            try testing.expectApproxEqAbs(concert[0], 523.2511306011972, 0.000001);
+            try testing.expectApproxEqAbs(concert[1], 659.2551138257398, 0.000001);
+            try testing.expectApproxEqAbs(concert[2], 783.9908719634985, 0.000001);
+
+            const baroque = try chord.frequencies(allocator, .{ .reference_frequency = 415.0 });
+            defer allocator.free(baroque);
+            try testing.expectApproxEqAbs(baroque[0], 523.2511306011972 * 415.0 / 440.0, 0.000001);
+
+            const just = try chord.frequencies(allocator, .{ .system = .just_intonation, .tonic = 72 });
+            defer allocator.free(just);
+            try testing.expectApproxEqAbs(just[1] / just[0], 1.25, 0.000001);
+            try testing.expectApproxEqAbs(just[2] / just[0], 1.5, 0.000001);
+        }
+
+        test "arpeggiate" {
+            const allocator = testing.allocator;
+            const chord = try Self.parse("Am", 3);
+
+            const up_down = try chord.arpeggiate(allocator, .{ .pattern = .up_down, .count = 6, .duration = 120 });
+            defer up_down.deinit();
+            try testing.expectEqual(up_down.notes.len, 6);
+            for (up_down.notes, [_]u7{ 57, 60, 64, 60, 57, 60 }, 0..) |note, key, i| {
+                try testing.expectEqual(note.key, key);
+                try testing.expectEqual(note.tick, i * 240);
+                try testing.expectEqual(note.duration, 120);
+            }
+            // Eighth notes at 120 BPM
+            try testing.expectApproxEqAbs(up_down.secondsAt(up_down.notes[2].tick), 0.5, 0.000001);
+
+            const down = try chord.arpeggiate(allocator, .{ .pattern = .down, .start = 960, .velocity = 64, .timeline = .{ .tempo = 90.0 } });
+            defer down.deinit();
+            try testing.expectEqual(down.notes.len, 3);
+            try testing.expectEqual(down.notes[0].key, 64);
+            try testing.expectEqual(down.notes[0].tick, 960);
+            try testing.expectEqual(down.notes[2].key, 57);
+            try testing.expectEqual(down.notes[2].velocity, 64);
+            try testing.expectEqual(down.timeline.tempo, 90.0);
+
+            const too_high: Self = .{ .root = 125 };
+            try testing.expectError(error.InvalidRange, too_high.arpeggiate(allocator, .{}));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
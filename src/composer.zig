@@ -1,11 +1,18 @@
 const std = @import("std");
 const testing = std.testing;
 const Wave = @import("./root.zig").Wave;
+const Pan = @import("./root.zig").Pan;
+const Gain = @import("./root.zig").Gain;
+const Timeline = @import("./root.zig").Timeline;
+const Midi = @import("./root.zig").Midi;
+const Parameter = @import("./root.zig").Parameter;
+const Error = @import("./error.zig").Error;
 
 /// Composer type function: Creates a Composer type for the specified sample type.
 ///
 /// Composer allows sequencing and overlaying multiple Wave instances in time to create
-/// complex audio arrangements.
+/// complex audio arrangements. Tracks nest other Composers with their own effect chains,
+/// and can be sent into buses.
 ///
 /// ## Type Parameter
 /// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
@@ -32,40 +39,120 @@ const Wave = @import("./root.zig").Wave;
 pub fn inner(comptime T: type) type {
     return struct {
         info: []const WaveInfo,
+        tracks: []const Track,
+        /// Waves rendered by `appendMidi`, which the composer owns and frees in `deinit`
+        rendered_waves: []const Wave(T),
+        buses: []const Bus,
         allocator: std.mem.Allocator,
         sample_rate: u32,
         channels: u16,
+        resample_quality: Wave(T).ResampleQuality,
+        pan_law: Pan(T).Law,
+        timeline: Timeline(T),
 
         const Self = @This();
 
         /// Information about a wave to be placed at a specific time point.
         pub const WaveInfo = struct {
             wave: Wave(T),
+            /// Frame index where the wave starts. A frame holds one sample per channel.
             start_point: usize,
+            /// Stereo position of a mono wave, from -1.0 (left) to 1.0 (right). Panning requires a
+            /// two-channel composer. `null` copies a mono wave to every channel of the composer,
+            /// and requires any other wave to have the composer's channel count.
+            pan: ?T = null,
+            /// Level of the wave, e.g. `.{ .linear = 0.5 }` or `.{ .decibel = -6.0 }`
+            gain: Gain(T).Level = .{ .linear = 1.0 },
+            /// Silences the wave
+            mute: bool = false,
+            /// When any wave is soloed, only soloed waves which are not muted are heard
+            solo: bool = false,
+        };
 
-            fn to_wave(self: WaveInfo, allocator: std.mem.Allocator) std.mem.Allocator.Error!Wave(T) {
-                var padding_samples: []T = try allocator.alloc(T, self.start_point);
+        /// Errors that effects can return: the errors of lightmix's filters.
+        pub const EffectErrors = Error || Pan(T).PanErrors || Parameter(T).ParameterErrors || std.mem.Allocator.Error;
+
+        /// A filter applied to a rendered track or bus.
+        pub const Effect = struct {
+            context: *const anyopaque,
+            apply_fn: *const fn (context: *const anyopaque, wave: Wave(T)) EffectErrors!Wave(T),
+
+            /// Wraps a filter function usable with `Wave(T).filter_with`, and its arguments.
+            /// `args` is not copied, so it must outlive the composer.
+            /// The errors of the filter must belong to `EffectErrors`.
+            ///
+            /// ## Example
+            /// ```zig
+            /// const lowpass: Biquad(f64).Options = .{ .kind = .low_pass, .cutoff = 800.0 };
+            /// const effect = Composer(f64).Effect.init(Biquad(f64).Options, Biquad(f64).filter, &lowpass);
+            /// ```
+            pub fn init(comptime args_type: type, comptime filter_fn: anytype, args: *const args_type) Effect {
+                const Wrapper = struct {
+                    fn apply(context: *const anyopaque, wave: Wave(T)) EffectErrors!Wave(T) {
+                        const typed_args: *const args_type = @ptrCast(@alignCast(context));
+                        return filter_fn(T, wave, typed_args.*);
+                    }
+                };
 
-                for (0..padding_samples.len) |i| {
-                    padding_samples[i] = 0.0;
-                }
+                return Effect{
+                    .context = args,
+                    .apply_fn = Wrapper.apply,
+                };
+            }
 
-                const slices: []const []const T = &[_][]const T{ padding_samples, self.wave.samples };
-                const samples = std.mem.concat(allocator, T, slices);
+            /// Applies the effect, returning a new wave. `wave` is not freed.
+            pub fn apply(self: Effect, wave: Wave(T)) EffectErrors!Wave(T) {
+                return self.apply_fn(self.context, wave);
+            }
+        };
 
-                const result: Wave(T) = try Wave(T).init(samples, allocator, .{
-                    .sample_rate = self.wave.sample_rate,
-                    .channels = self.wave.channels,
-                });
+        /// A named track: a nested Composer holding the track's events, rendered when
+        /// the outer composer is finalized, followed by an effect chain.
+        pub const Track = struct {
+            /// Name of the track
+            name: []const u8,
+            /// Events of the track. It is not owned by the track, and must not contain the outer composer,
+            /// which `finalize` rejects with `error.TrackCycle`.
+            composer: *const Self,
+            /// Filters applied in order to the rendered track
+            effects: []const Effect = &.{},
+            /// Frame index where the track starts in the outer composer
+            start_point: usize = 0,
+            /// Level of the track
+            gain: Gain(T).Level = .{ .linear = 1.0 },
+            /// Stereo position of a mono track. See `WaveInfo.pan`.
+            pan: ?T = null,
+            /// Silences the track
+            mute: bool = false,
+            /// When any wave or track is soloed, only soloed ones which are not muted are heard
+            solo: bool = false,
+            /// Name of the bus the track is sent into. `null` sends it to the composer's output.
+            output: ?[]const u8 = null,
+        };
 
-                return result;
-            }
+        /// A bus: tracks sent into it are mixed together, then processed by its effect chain,
+        /// and the result is mixed into the composer's output.
+        pub const Bus = struct {
+            /// Name used by `Track.output`
+            name: []const u8,
+            /// Filters applied in order to the mixed bus
+            effects: []const Effect = &.{},
+            /// Level of the bus
+            gain: Gain(T).Level = .{ .linear = 1.0 },
+            /// Silences the bus
+            mute: bool = false,
         };
 
         /// Options for initializing a Composer instance.
         pub const InitOptions = struct {
             sample_rate: u32,
             channels: u16,
+            /// Interpolation used for waves whose sample rate differs from the composer's
+            resample_quality: Wave(T).ResampleQuality = .sinc,
+            /// Pan law used for waves with a `pan` position
+            pan_law: Pan(T).Law = .constant_power,
+            /// Tempo and time signature used by `frameAt`
+            timeline: Timeline(T) = .{},
         };
 
         /// Creates a new empty Composer instance.
@@ -83,18 +170,30 @@ pub fn inner(comptime T: type) type {
             return Self{
                 .allocator = allocator,
                 .info = &[_]WaveInfo{},
+                .tracks = &[_]Track{},
+                .buses = &[_]Bus{},
+                .rendered_waves = &[_]Wave(T){},
 
                 .sample_rate = options.sample_rate,
                 .channels = options.channels,
+                .resample_quality = options.resample_quality,
+                .pan_law = options.pan_law,
+                .timeline = options.timeline,
             };
         }
 
         /// Frees the memory allocated for the composer's internal data.
         ///
-        /// Note: This does not free the individual Wave instances stored in WaveInfo.
-        /// Those must be freed separately by the caller.
+        /// Note: This does not free the individual Wave instances stored in WaveInfo, except the ones
+        /// rendered by `appendMidi`, nor the Composers nested in tracks. Those must be freed separately by the caller.
         pub fn deinit(self: Self) void {
+            for (self.rendered_waves) |wave| {
+                wave.deinit();
+            }
+            self.allocator.free(self.rendered_waves);
             self.allocator.free(self.info);
+            self.allocator.free(self.tracks);
+            self.allocator.free(self.buses);
         }
 
         /// Creates a new Composer instance initialized with the provided wave information.
@@ -117,12 +216,39 @@ pub fn inner(comptime T: type) type {
             return Self{
                 .allocator = allocator,
                 .info = try list.toOwnedSlice(allocator),
+                .tracks = &[_]Track{},
+                .buses = &[_]Bus{},
+                .rendered_waves = &[_]Wave(T){},
 
                 .sample_rate = options.sample_rate,
                 .channels = options.channels,
+                .resample_quality = options.resample_quality,
+                .pan_law = options.pan_law,
+                .timeline = options.timeline,
             };
         }
 
+        /// Converts a position in seconds, milliseconds or bars, beats and ticks to a frame index,
+        /// using the composer's timeline and sample rate.
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The position is NaN or too far to be a frame index (see `Timeline(T).frameAt`)
+        ///
+        /// ## Example
+        /// ```zig
+        /// var composer = Composer(f64).init(allocator, .{
+        ///     .sample_rate = 44100,
+        ///     .channels = 1,
+        ///     .timeline = .{ .tempo = 90.0 },
+        /// });
+        ///
+        /// try composer.append(.{ .wave = kick, .start_point = try composer.frameAt(.{ .musical = .{ .bar = 2 } }) });
+        /// try composer.append(.{ .wave = snare, .start_point = try composer.frameAt(.{ .seconds = 1.5 }) });
+        /// ```
+        pub fn frameAt(self: Self, position: Timeline(T).Position) error{InvalidRange}!usize {
+            return self.timeline.frameAt(position, self.sample_rate);
+        }
+
         /// Appends a single wave to the composition. This method modifies the composer in-place.
         ///
         /// ## Parameters
@@ -150,16 +276,10 @@ pub fn inner(comptime T: type) type {
             try d.appendSlice(self.allocator, self.info);
             try d.append(self.allocator, waveinfo);
 
-            const result: Self = Self{
-                .allocator = self.allocator,
-                .info = try d.toOwnedSlice(self.allocator),
-
-                .sample_rate = self.sample_rate,
-                .channels = self.channels,
-            };
+            const info: []const WaveInfo = try d.toOwnedSlice(self.allocator);
 
-            self.deinit(); // Free the old one now
-            self.* = result; // Then copy the new one (result variable)
+            self.allocator.free(self.info); // Free the old one now
+            self.info = info; // Then use the new one
         }
 
         /// Appends multiple waves to the composition. This method modifies the composer in-place.
@@ -176,157 +296,500 @@ pub fn inner(comptime T: type) type {
             try d.appendSlice(self.allocator, self.info);
             try d.appendSlice(self.allocator, append_list);
 
-            const result: Self = Self{
-                .allocator = self.allocator,
-                .info = try d.toOwnedSlice(self.allocator),
+            const info: []const WaveInfo = try d.toOwnedSlice(self.allocator);
 
-                .sample_rate = self.sample_rate,
-                .channels = self.channels,
+            self.allocator.free(self.info);
+            self.info = info;
+        }
+
+        /// Options for `appendMidi`.
+        pub const AppendMidiOptions = struct {
+            /// Only renders the notes of this MIDI channel. `null` renders every channel.
+            channel: ?u4 = null,
+            /// Only renders the notes of this track. `null` renders every track.
+            track: ?u16 = null,
+        };
+
+        /// Renders the notes of a MIDI sequence with an instrument function, and appends them at
+        /// their positions in the sequence's tempo map. This method modifies the composer in-place.
+        ///
+        /// The composer owns the rendered waves, and frees them in `deinit`.
+        ///
+        /// ## Parameters
+        /// - `self`: Pointer to the composer to modify (will be updated in-place)
+        /// - `midi`: The notes and their tempo map
+        /// - `instrument`: A function `fn (note: u7, velocity: u7, duration_samples: usize, allocator: std.mem.Allocator) !Wave(T)`,
+        ///   called for each note with its length in frames at the composer's sample rate
+        /// - `options`: The channel and track of the notes to render
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: A note is too far to be a frame index, as with a tempo of 0
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the instrument
+        ///
+        /// ## Example
+        /// ```zig
+        /// fn piano(note: u7, velocity: u7, duration_samples: usize, allocator: std.mem.Allocator) !Wave(f64) {
+        ///     return Oscillator(f64).sine(allocator, .{
+        ///         .frequency = 440.0 * std.math.pow(f64, 2.0, (@as(f64, @floatFromInt(note)) - 69.0) / 12.0),
+        ///         .amplitude = @as(f64, @floatFromInt(velocity)) / 127.0,
+        ///         .duration = @as(f64, @floatFromInt(duration_samples)) / 44100.0,
+        ///         .sample_rate = 44100,
+        ///     });
+        /// }
+        ///
+        /// try composer.appendMidi(midi, piano, .{ .channel = 0 });
+        /// ```
+        pub fn appendMidi(self: *Self, midi: Midi(T), comptime instrument: anytype, options: AppendMidiOptions) anyerror!void {
+            var waves: std.array_list.Aligned(Wave(T), null) = .empty;
+            defer waves.deinit(self.allocator);
+            errdefer for (waves.items) |wave| {
+                wave.deinit();
             };
 
-            self.deinit();
-            self.* = result;
+            var rendered: std.array_list.Aligned(WaveInfo, null) = .empty;
+            defer rendered.deinit(self.allocator);
+
+            for (midi.notes) |note| {
+                if (options.channel != null and options.channel.? != note.channel)
+                    continue;
+                if (options.track != null and options.track.? != note.track)
+                    continue;
+
+                const start: usize = try midi.timeline.frameAt(.{ .musical = .{ .bar = 0, .tick = note.tick } }, self.sample_rate);
+                const end: usize = try midi.timeline.frameAt(.{ .musical = .{ .bar = 0, .tick = note.tick + note.duration } }, self.sample_rate);
+
+                const wave: Wave(T) = try instrument(note.key, note.velocity, end - start, self.allocator);
+                waves.append(self.allocator, wave) catch |err| {
+                    wave.deinit();
+                    return err;
+                };
+                try rendered.append(self.allocator, .{ .wave = wave, .start_point = start });
+            }
+
+            const rendered_waves: []Wave(T) = try self.allocator.alloc(Wave(T), self.rendered_waves.len + waves.items.len);
+            errdefer self.allocator.free(rendered_waves);
+            @memcpy(rendered_waves[0..self.rendered_waves.len], self.rendered_waves);
+            @memcpy(rendered_waves[self.rendered_waves.len..], waves.items);
+
+            try self.appendSlice(rendered.items);
+
+            self.allocator.free(self.rendered_waves);
+            self.rendered_waves = rendered_waves;
         }
 
-        /// Finalizes the composition by mixing all waves together.
+        /// Adds a track to the composition. This method modifies the composer in-place.
+        ///
+        /// ## Example
+        /// ```zig
+        /// var drums = Composer(f64).init(allocator, .{ .sample_rate = 44100, .channels = 2 });
+        /// defer drums.deinit();
+        /// try drums.append(.{ .wave = kick, .start_point = 0 });
+        ///
+        /// var song = Composer(f64).init(allocator, .{ .sample_rate = 44100, .channels = 2 });
+        /// defer song.deinit();
+        /// try song.addTrack(.{ .name = "drums", .composer = &drums, .gain = .{ .decibel = -3.0 } });
+        /// ```
+        pub fn addTrack(self: *Self, track: Track) std.mem.Allocator.Error!void {
+            const tracks: []Track = try self.allocator.alloc(Track, self.tracks.len + 1);
+            @memcpy(tracks[0..self.tracks.len], self.tracks);
+            tracks[self.tracks.len] = track;
+
+            self.allocator.free(self.tracks);
+            self.tracks = tracks;
+        }
+
+        /// Adds a bus to the composition. This method modifies the composer in-place.
+        pub fn addBus(self: *Self, bus: Bus) std.mem.Allocator.Error!void {
+            const buses: []Bus = try self.allocator.alloc(Bus, self.buses.len + 1);
+            @memcpy(buses[0..self.buses.len], self.buses);
+            buses[self.buses.len] = bus;
+
+            self.allocator.free(self.buses);
+            self.buses = buses;
+        }
+
+        /// Finalizes the composition by mixing all waves and tracks together.
         ///
         /// This creates a single Wave by:
-        /// 1. Calculating the total length needed
-        /// 2. Padding each wave to align with its start_point
-        /// 3. Mixing all waves together using the provided mixer function
+        /// 1. Rendering each audible track: finalizing its composer, applying its effects,
+        ///    then resampling and panning it like a wave
+        /// 2. Rendering each bus: mixing the tracks sent into it, then applying its effects
+        /// 3. Calculating the total length needed, including muted waves
+        /// 4. Allocating one silent output buffer of that length
+        /// 5. Converting each audible wave, one at a time: resampling it when its sample rate
+        ///    differs from the composer's (using `resample_quality`), panning it when it has
+        ///    a `pan` position (using `pan_law`), and copying a mono wave to every channel otherwise
+        /// 6. Mixing each wave, track and bus, with its gain applied, into the output at its start_point
+        ///
+        /// With the `.mixer` mode, the mixer function is called for every output sample a wave overlaps,
+        /// with the current output sample and the wave's sample. Output samples outside every wave stay silent.
+        /// The other mix modes sum the waves, tracks and buses mixed into the output, then scale the sum
+        /// by the number of them sounding at each sample (`.average`, `.headroom`) or saturate it (`.soft_clip`).
+        /// `.weighted` gains apply to the waves in `info` in order, then to the tracks, then to the buses,
+        /// so it must contain `info.len + tracks.len + buses.len` gains.
         ///
         /// ## Parameters
         /// - `self`: The composer containing all the waves to mix
-        /// - `options`: Mixing options (includes the mixer function)
+        /// - `options`: Mixing options (mix mode and mixer function), also used for tracks and buses.
+        ///   Nested composers and buses mix with `.sum` instead of `.weighted`.
         ///
         /// ## Returns
         /// A new Wave containing the final mixed composition
         ///
+        /// ## Errors
+        /// - `PanningWithoutStereo`: A wave has a `pan` position, but the composer doesn't have two channels
+        /// - `PanningMultiChannelWave`: A wave with a `pan` position is not mono
+        /// - `ChannelMismatch`: A wave, track or bus without a `pan` position has another channel count
+        ///   than the composer, and is not a mono wave which can be copied to every channel
+        /// - `UnknownBus`: A track is sent into a bus which doesn't exist
+        /// - `TrackCycle`: A track contains, directly or through other tracks, a composer it belongs to
+        /// - `LengthMismatch`: `.weighted` doesn't contain one gain per wave, track and bus
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by an effect
+        ///
         /// ## Performance Notes
-        /// Memory usage is proportional to: `number_of_waves × total_length × sample_size`
-        /// Each wave is temporarily padded to the full composition length before mixing.
-        /// Consider using this for up to ~100 overlapping waves on typical systems.
-        pub fn finalize(self: Self, options: Wave(T).mixOptions) std.mem.Allocator.Error!Wave(T) {
+        /// Memory usage is proportional to the output: `total_length × sample_size`, plus one
+        /// converted copy of the wave being mixed when it needs resampling or panning.
+        /// Waves which already match the composer are read in place.
+        /// Tracks and buses are rendered completely before mixing, so each of them also
+        /// takes memory proportional to its own length.
+        pub fn finalize(self: Self, options: Wave(T).mixOptions) (FinalizeErrors || Error || EffectErrors || std.mem.Allocator.Error)!Wave(T) {
+            return self.render(options, null);
+        }
+
+        /// The composers of the tracks being rendered, from the innermost one.
+        const Ancestor = struct {
+            composer: *const Self,
+            parent: ?*const Ancestor,
+        };
+
+        fn render(self: Self, options: Wave(T).mixOptions, ancestors: ?*const Ancestor) (FinalizeErrors || Error || EffectErrors || std.mem.Allocator.Error)!Wave(T) {
+            switch (options.mode) {
+                .weighted => |weights| if (weights.len != self.info.len + self.tracks.len + self.buses.len) return error.LengthMismatch,
+                else => {},
+            }
+
+            var soloing: bool = false;
+            for (self.info) |waveinfo| {
+                if (waveinfo.solo)
+                    soloing = true;
+            }
+            for (self.tracks) |track| {
+                if (track.solo)
+                    soloing = true;
+            }
+
+            // Tracks are rendered first, because their length is known only once rendered
+            const rendered_tracks: []?Wave(T) = try self.allocator.alloc(?Wave(T), self.tracks.len);
+            defer self.allocator.free(rendered_tracks);
+            @memset(rendered_tracks, null);
+            defer for (rendered_tracks) |rendered| {
+                if (rendered) |wave| wave.deinit();
+            };
+
+            for (self.tracks, rendered_tracks) |track, *rendered| {
+                if (track.output) |name| {
+                    if (self.findBus(name) == null)
+                        return error.UnknownBus;
+                }
+
+                if (track.mute or (soloing and !track.solo))
+                    continue;
+
+                rendered.* = try self.renderTrack(track, nestedOptions(options), ancestors);
+            }
+
+            const rendered_buses: []?Wave(T) = try self.allocator.alloc(?Wave(T), self.buses.len);
+            defer self.allocator.free(rendered_buses);
+            @memset(rendered_buses, null);
+            defer for (rendered_buses) |rendered| {
+                if (rendered) |wave| wave.deinit();
+            };
+
+            for (self.buses, rendered_buses) |bus, *rendered| {
+                if (bus.mute)
+                    continue;
+
+                rendered.* = try self.renderBus(bus, rendered_tracks, nestedOptions(options));
+            }
+
             var end_point: usize = 0;
 
             // Calculate the length for emitted wave
             for (self.info) |waveinfo| {
-                const ep = waveinfo.start_point + waveinfo.wave.samples.len;
+                const ep = waveinfo.start_point * self.channels + self.convertedLength(waveinfo.wave, waveinfo.pan);
 
                 if (end_point < ep)
                     end_point = ep;
             }
+            for (self.tracks, rendered_tracks) |track, rendered| {
+                const wave: Wave(T) = rendered orelse continue;
+                if (track.output != null)
+                    continue;
 
-            var padded_waveinfo_list: std.array_list.Aligned(WaveInfo, null) = .empty;
-            defer padded_waveinfo_list.deinit(self.allocator);
+                end_point = @max(end_point, track.start_point * self.channels + wave.samples.len);
+            }
+            for (rendered_buses) |rendered| {
+                const wave: Wave(T) = rendered orelse continue;
 
-            // Filter each WaveInfo to append padding both of start and last
-            for (self.info) |waveinfo| {
-                const padded_at_start: []const T = try padding_for_start(waveinfo.wave.samples, waveinfo.start_point, self.allocator);
-                defer self.allocator.free(padded_at_start);
+                end_point = @max(end_point, wave.samples.len);
+            }
 
-                const padded_at_start_and_last: []const T = try padding_for_last(padded_at_start, end_point, self.allocator);
-                defer self.allocator.free(padded_at_start_and_last);
+            const samples: []T = try self.allocator.alloc(T, end_point);
+            errdefer self.allocator.free(samples);
+            @memset(samples, 0.0);
 
-                const wave = try Wave(T).init(padded_at_start_and_last, self.allocator, .{
-                    .sample_rate = self.sample_rate,
-                    .channels = self.channels,
-                });
+            // Number of waves, tracks and buses mixed into each output sample
+            const counts: ?[]usize = try self.allocateCounts(end_point, options);
+            defer if (counts) |c| self.allocator.free(c);
 
-                const wi: WaveInfo = WaveInfo{
-                    .wave = wave,
-                    .start_point = waveinfo.start_point,
-                };
+            // Add each audible wave directly into the output
+            for (self.info, 0..) |waveinfo, index| {
+                if (waveinfo.mute or (soloing and !waveinfo.solo))
+                    continue;
+
+                const source: Wave(T) = try self.convert(waveinfo.wave, waveinfo.pan);
+                defer if (source.samples.ptr != waveinfo.wave.samples.ptr) source.deinit();
+
+                mixInto(samples, counts, source.samples, waveinfo.start_point * self.channels, waveinfo.gain.ratio() * options.mode.weight(index), options);
+            }
+
+            for (self.tracks, rendered_tracks, self.info.len..) |track, rendered, index| {
+                const wave: Wave(T) = rendered orelse continue;
+                if (track.output != null)
+                    continue;
+
+                mixInto(samples, counts, wave.samples, track.start_point * self.channels, track.gain.ratio() * options.mode.weight(index), options);
+            }
 
-                try padded_waveinfo_list.append(self.allocator, wi);
+            for (self.buses, rendered_buses, self.info.len + self.tracks.len..) |bus, rendered, index| {
+                const wave: Wave(T) = rendered orelse continue;
+
+                mixInto(samples, counts, wave.samples, 0, bus.gain.ratio() * options.mode.weight(index), options);
             }
 
-            const padded_waveinfo_slice: []const WaveInfo = try padded_waveinfo_list.toOwnedSlice(self.allocator);
-            defer self.allocator.free(padded_waveinfo_slice);
+            finish(samples, counts, options);
 
-            const empty_samples: []const T = try generate_soundless_samples(end_point, self.allocator);
-            defer self.allocator.free(empty_samples);
+            return Wave(T){
+                .samples = samples,
+                .allocator = self.allocator,
 
-            var result = try Wave(T).init(empty_samples, self.allocator, .{
                 .sample_rate = self.sample_rate,
                 .channels = self.channels,
-            });
+            };
+        }
 
-            for (padded_waveinfo_slice) |waveinfo| {
-                const wave = try result.mix(waveinfo.wave, options);
-                result.deinit();
-                waveinfo.wave.deinit();
-                result = wave;
+        /// Mixes `source`, scaled by `ratio`, into `destination` from the sample index `offset`,
+        /// and counts it in `counts` for each sample it covers.
+        /// Except with the `.mixer` mode, the samples are summed, and `finish` must be called once every source is mixed.
+        fn mixInto(destination: []T, counts: ?[]usize, source: []const T, offset: usize, ratio: T, options: Wave(T).mixOptions) void {
+            for (source, offset..) |sample, i| {
+                destination[i] = if (options.mode == .mixer)
+                    options.mixer(destination[i], sample * ratio)
+                else
+                    destination[i] + sample * ratio;
             }
 
-            return result;
+            if (counts) |c| {
+                for (c[offset .. offset + source.len]) |*n| {
+                    n.* += 1;
+                }
+            }
         }
 
-        fn padding_for_start(samples: []const T, start_point: usize, allocator: std.mem.Allocator) std.mem.Allocator.Error![]const T {
-            const padding_length: usize = start_point;
-            var padding: std.array_list.Aligned(T, null) = .empty;
-            defer padding.deinit(allocator);
+        /// Allocates the number of sources of each sample, for the modes which scale the sum by it.
+        /// Returns `null` for the other modes.
+        fn allocateCounts(self: Self, length: usize, options: Wave(T).mixOptions) std.mem.Allocator.Error!?[]usize {
+            switch (options.mode) {
+                .average, .headroom => {},
+                else => return null,
+            }
 
-            // Append padding
-            for (0..padding_length) |_|
-                try padding.append(allocator, 0.0);
+            const counts: []usize = try self.allocator.alloc(usize, length);
+            @memset(counts, 0);
+            return counts;
+        }
 
-            // Append samples slice
-            try padding.appendSlice(allocator, samples);
+        /// Turns the sums of the sources counted in `counts` into mixed samples, following the mix mode.
+        fn finish(samples: []T, counts: ?[]const usize, options: Wave(T).mixOptions) void {
+            switch (options.mode) {
+                .mixer, .sum, .weighted => {},
+                else => {
+                    for (samples, 0..) |*sample, i| {
+                        sample.* = options.mode.finish(sample.*, if (counts) |c| c[i] else 0);
+                    }
+                },
+            }
+        }
 
-            const result: []const T = try padding.toOwnedSlice(allocator);
+        /// Options for nested composers and buses, whose inputs have no weights.
+        fn nestedOptions(options: Wave(T).mixOptions) Wave(T).mixOptions {
+            var result: Wave(T).mixOptions = options;
+            if (options.mode == .weighted)
+                result.mode = .sum;
 
             return result;
         }
 
-        fn padding_for_last(samples: []const T, end_point: usize, allocator: std.mem.Allocator) std.mem.Allocator.Error![]const T {
-            std.debug.assert(samples.len <= end_point);
+        fn findBus(self: Self, name: []const u8) ?Bus {
+            for (self.buses) |bus| {
+                if (std.mem.eql(u8, bus.name, name))
+                    return bus;
+            }
 
-            const padding_length: usize = end_point - samples.len;
-            var padding: std.array_list.Aligned(T, null) = .empty;
-            defer padding.deinit(allocator);
+            return null;
+        }
 
-            // Append samples slice
-            try padding.appendSlice(allocator, samples);
+        /// Finalizes the composer of a track, then applies its effects, resampling and panning.
+        fn renderTrack(self: Self, track: Track, options: Wave(T).mixOptions, ancestors: ?*const Ancestor) (FinalizeErrors || Error || EffectErrors || std.mem.Allocator.Error)!Wave(T) {
+            var ancestor: ?*const Ancestor = ancestors;
+            while (ancestor) |node| : (ancestor = node.parent) {
+                if (node.composer == track.composer)
+                    return error.TrackCycle;
+            }
 
-            // Append padding
-            for (0..padding_length) |_|
-                try padding.append(allocator, 0.0);
+            const node: Ancestor = .{ .composer = track.composer, .parent = ancestors };
+            var wave: Wave(T) = try track.composer.render(options, &node);
+            errdefer wave.deinit();
 
-            const result: []const T = try padding.toOwnedSlice(allocator);
+            for (track.effects) |effect| {
+                const filtered: Wave(T) = try effect.apply(wave);
+                wave.deinit();
+                wave = filtered;
+            }
 
-            return result;
+            const converted: Wave(T) = try self.convert(wave, track.pan);
+            if (converted.samples.ptr != wave.samples.ptr)
+                wave.deinit();
+
+            return converted;
         }
 
-        fn generate_soundless_samples(length: usize, allocator: std.mem.Allocator) std.mem.Allocator.Error![]const T {
-            var list: std.array_list.Aligned(T, null) = .empty;
-            defer list.deinit(allocator);
+        /// Mixes the rendered tracks sent into a bus, then applies its effects.
+        /// Returns `null` when no audible track is sent into the bus.
+        fn renderBus(self: Self, bus: Bus, rendered_tracks: []const ?Wave(T), options: Wave(T).mixOptions) (FinalizeErrors || Error || EffectErrors || std.mem.Allocator.Error)!?Wave(T) {
+            var length: usize = 0;
+            var audible: bool = false;
+
+            for (self.tracks, rendered_tracks) |track, rendered| {
+                const wave: Wave(T) = rendered orelse continue;
+                const name: []const u8 = track.output orelse continue;
+                if (!std.mem.eql(u8, name, bus.name))
+                    continue;
 
-            // Append empty wave
-            for (0..length) |_|
-                try list.append(allocator, 0.0);
+                audible = true;
+                length = @max(length, track.start_point * self.channels + wave.samples.len);
+            }
 
-            const result: []const T = try list.toOwnedSlice(allocator);
+            if (!audible)
+                return null;
 
-            return result;
+            const counts: ?[]usize = try self.allocateCounts(length, options);
+            defer if (counts) |c| self.allocator.free(c);
+
+            const samples: []T = try self.allocator.alloc(T, length);
+            @memset(samples, 0.0);
+
+            for (self.tracks, rendered_tracks) |track, rendered| {
+                const wave: Wave(T) = rendered orelse continue;
+                const name: []const u8 = track.output orelse continue;
+                if (!std.mem.eql(u8, name, bus.name))
+                    continue;
+
+                mixInto(samples, counts, wave.samples, track.start_point * self.channels, track.gain.ratio(), options);
+            }
+            finish(samples, counts, options);
+
+            var result: Wave(T) = Wave(T){
+                .samples = samples,
+                .allocator = self.allocator,
+
+                .sample_rate = self.sample_rate,
+                .channels = self.channels,
+            };
+            errdefer result.deinit();
+
+            for (bus.effects) |effect| {
+                const filtered: Wave(T) = try effect.apply(result);
+                result.deinit();
+                result = filtered;
+            }
+
+            // Effects may change the channel count of the bus
+            const converted: Wave(T) = try self.convert(result, null);
+            if (converted.samples.ptr != result.samples.ptr)
+                result.deinit();
+
+            return converted;
         }
 
-        test "padding_for_start" {
-            const allocator = testing.allocator;
-            const samples: []const T = &[_]T{ 1.0, 1.0 };
-            const start_point: usize = 10;
+        /// Returns the number of samples of a wave once converted by `convert`, without converting it.
+        fn convertedLength(self: Self, wave: Wave(T), pan: ?T) usize {
+            if (pan != null or wave.channels != self.channels)
+                return wave.resampledFrameCount(self.sample_rate) * self.channels;
+
+            if (wave.sample_rate == self.sample_rate)
+                return wave.samples.len;
+
+            return wave.resampledFrameCount(self.sample_rate) * wave.channels;
+        }
+
+        /// Resamples, pans and upmixes a wave for the composer.
+        /// Returns `wave` itself when no conversion is needed.
+        fn convert(self: Self, wave: Wave(T), pan: ?T) (FinalizeErrors || Error || std.mem.Allocator.Error)!Wave(T) {
+            if (pan == null and wave.channels != self.channels and (wave.channels != 1 or self.channels < 2))
+                return error.ChannelMismatch;
+
+            var result: Wave(T) = wave;
+            errdefer if (result.samples.ptr != wave.samples.ptr) result.deinit();
+
+            // Waves recorded at another sample rate are converted to the composer's one
+            if (wave.sample_rate != self.sample_rate)
+                result = try wave.resample(self.sample_rate, self.resample_quality);
+
+            // Mono waves without a pan position are copied to every channel
+            if (pan == null and result.channels != self.channels) {
+                const upmixed: Wave(T) = try result.upmix(self.channels);
 
-            const result: []const T = try padding_for_start(samples, start_point, allocator);
-            defer allocator.free(result);
+                if (result.samples.ptr != wave.samples.ptr)
+                    result.deinit();
+                result = upmixed;
+            }
+
+            if (pan) |position| {
+                if (self.channels != 2)
+                    return error.PanningWithoutStereo;
 
-            try testing.expectEqual(samples.len + start_point, result.len);
+                const panned = Pan(T).filter(T, result, .{
+                    .position = .{ .constant = position },
+                    .law = self.pan_law,
+                }) catch |err| switch (err) {
+                    error.ControlLengthMismatch => unreachable,
+                    else => |e| return e,
+                };
 
-            const expected: []const T = &[_]T{ 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0 };
-            for (0..result.len) |i| {
-                try testing.expectApproxEqAbs(expected[i], result[i], 0.001);
+                if (result.samples.ptr != wave.samples.ptr)
+                    result.deinit();
+                result = panned;
             }
+
+            return result;
         }
 
+        /// Errors that can occur when finalizing a composition, besides effects' errors.
+        pub const FinalizeErrors = error{
+            /// A wave has a `pan` position, but the composer doesn't have two channels
+            PanningWithoutStereo,
+            /// A track is sent into a bus which doesn't exist
+            UnknownBus,
+            /// A track contains, directly or through other tracks, a composer it belongs to
+            TrackCycle,
+            /// A wave, track or bus without a `pan` position has another channel count than the composer,
+            /// and is not a mono wave which can be copied to every channel
+            ChannelMismatch,
+        } || Pan(T).PanErrors;
+
         test "init & deinit" {
             const allocator = testing.allocator;
             const composer = Self.init(allocator, .{
@@ -426,6 +889,567 @@ pub fn inner(comptime T: type) type {
             try testing.expectEqual(result.sample_rate, 44100);
             try testing.expectEqual(result.channels, 1);
         }
+
+        test "finalize places stereo waves by frame" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{ 1.0, -1.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+            try composer.append(.{ .wave = wave, .start_point = 1 });
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqualSlices(T, result.samples, &.{ 1.0, -1.0, 1.0, -1.0 });
+        }
+
+        test "finalize pans mono waves" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+                .pan_law = .linear,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{1.0};
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0, .pan = -1.0 });
+            try composer.append(.{ .wave = wave, .start_point = 1, .pan = 0.5 });
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqual(result.channels, 2);
+            try testing.expectEqualSlices(T, result.samples, &.{ 1.0, 0.0, 0.25, 0.75 });
+        }
+
+        test "finalize copies mono waves to every channel" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{ 1.0, 0.5 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 1 });
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqual(result.channels, 2);
+            try testing.expectEqualSlices(T, result.samples, &.{ 0.0, 0.0, 1.0, 1.0, 0.5, 0.5 });
+        }
+
+        test "finalize rejects stereo waves in a mono composer" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{ 1.0, -1.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+
+            try testing.expectError(error.ChannelMismatch, composer.finalize(.{}));
+        }
+
+        test "finalize rejects panning in a mono composer" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{1.0};
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0, .pan = 0.0 });
+
+            try testing.expectError(error.PanningWithoutStereo, composer.finalize(.{}));
+        }
+
+        test "finalize applies gain" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{ 1.0, 1.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0, .gain = .{ .linear = 0.25 } });
+            try composer.append(.{ .wave = wave, .start_point = 1, .gain = .{ .decibel = -6.020599913279624 } });
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqual(result.samples.len, 3);
+            try testing.expectApproxEqAbs(result.samples[0], 0.25, 0.000001);
+            try testing.expectApproxEqAbs(result.samples[1], 0.75, 0.000001);
+            try testing.expectApproxEqAbs(result.samples[2], 0.5, 0.000001);
+        }
+
+        test "finalize honours mute and solo" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{1.0};
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+            try composer.append(.{ .wave = wave, .start_point = 1, .mute = true });
+
+            const muted = try composer.finalize(.{});
+            defer muted.deinit();
+
+            // Muted waves still count for the length
+            try testing.expectEqualSlices(T, muted.samples, &.{ 1.0, 0.0 });
+
+            try composer.append(.{ .wave = wave, .start_point = 2, .solo = true });
+            try composer.append(.{ .wave = wave, .start_point = 3, .solo = true, .mute = true });
+
+            const soloed = try composer.finalize(.{});
+            defer soloed.deinit();
+
+            try testing.expectEqualSlices(T, soloed.samples, &.{ 0.0, 0.0, 1.0, 0.0 });
+        }
+
+        test "finalize allocates memory for the output only" {
+            var counting = std.testing.FailingAllocator.init(testing.allocator, .{});
+            const allocator = counting.allocator();
+
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            // 1000 overlapping events of 0.01 seconds each, over about 9 seconds
+            const samples: []T = try testing.allocator.alloc(T, 441);
+            defer testing.allocator.free(samples);
+            @memset(samples, 0.5);
+
+            const wave = try Wave(T).init(samples, testing.allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            var append_list: std.array_list.Aligned(WaveInfo, null) = .empty;
+            defer append_list.deinit(testing.allocator);
+            for (0..1000) |i| {
+                try append_list.append(testing.allocator, .{ .wave = wave, .start_point = i * 400 });
+            }
+            try composer.appendSlice(append_list.items);
+
+            const allocated_before: usize = counting.allocated_bytes;
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqual(result.samples.len, 999 * 400 + 441);
+            try testing.expectEqual(counting.allocated_bytes - allocated_before, result.samples.len * @sizeOf(T));
+        }
+
+        test "frameAt" {
+            const allocator = testing.allocator;
+            const composer = Self.init(allocator, .{
+                .sample_rate = 48000,
+                .channels = 1,
+                .timeline = .{ .tempo = 60.0 },
+            });
+            defer composer.deinit();
+
+            try testing.expectEqual(try composer.frameAt(.{ .musical = .{ .bar = 1, .beat = 1 } }), 240000);
+            try testing.expectEqual(try composer.frameAt(.{ .milliseconds = 250.0 }), 12000);
+        }
+
+        test "tracks nest composers" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 1.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            var drums = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer drums.deinit();
+            try drums.append(.{ .wave = wave, .start_point = 1 });
+
+            var song = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer song.deinit();
+            try song.append(.{ .wave = wave, .start_point = 0 });
+            try song.addTrack(.{
+                .name = "drums",
+                .composer = &drums,
+                .start_point = 2,
+                .gain = .{ .linear = 0.5 },
+            });
+
+            const result = try song.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqualSlices(T, result.samples, &.{ 1.0, 1.0, 0.0, 0.5, 0.5 });
+        }
+
+        test "tracks apply their effects in order" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{1.0};
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            var lead = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer lead.deinit();
+            try lead.append(.{ .wave = wave, .start_point = 0 });
+
+            const half: Gain(T).Options = .{ .gain = .{ .constant = 0.5 } };
+            const right: Pan(T).Options = .{ .position = .{ .constant = 1.0 }, .law = .linear };
+            const effects = [_]Effect{
+                Effect.init(Gain(T).Options, Gain(T).filter, &half),
+                Effect.init(Pan(T).Options, Pan(T).filter, &right),
+            };
+
+            var song = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer song.deinit();
+            try song.addTrack(.{ .name = "lead", .composer = &lead, .effects = &effects });
+
+            const result = try song.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqualSlices(T, result.samples, &.{ 0.0, 0.5 });
+        }
+
+        test "tracks are sent into buses" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{1.0};
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            var part = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer part.deinit();
+            try part.append(.{ .wave = wave, .start_point = 0 });
+
+            const half: Gain(T).Options = .{ .gain = .{ .constant = 0.5 } };
+            const effects = [_]Effect{Effect.init(Gain(T).Options, Gain(T).filter, &half)};
+
+            var song = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer song.deinit();
+            try song.addBus(.{ .name = "group", .effects = &effects, .gain = .{ .linear = 0.5 } });
+            try song.addTrack(.{ .name = "first", .composer = &part, .output = "group" });
+            try song.addTrack(.{ .name = "second", .composer = &part, .start_point = 1, .output = "group" });
+            try song.addTrack(.{ .name = "third", .composer = &part, .start_point = 2 });
+
+            const result = try song.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqualSlices(T, result.samples, &.{ 0.25, 0.25, 1.0 });
+        }
+
+        test "tracks honour mute and solo" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{1.0};
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            var part = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer part.deinit();
+            try part.append(.{ .wave = wave, .start_point = 0 });
+
+            var song = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer song.deinit();
+            try song.append(.{ .wave = wave, .start_point = 0 });
+            try song.addTrack(.{ .name = "muted", .composer = &part, .start_point = 1, .mute = true });
+            try song.addTrack(.{ .name = "soloed", .composer = &part, .start_point = 2, .solo = true });
+
+            const result = try song.finalize(.{});
+            defer result.deinit();
+
+            try testing.expectEqualSlices(T, result.samples, &.{ 0.0, 0.0, 1.0 });
+        }
+
+        test "tracks sent into an unknown bus" {
+            const allocator = testing.allocator;
+
+            var part = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer part.deinit();
+
+            var song = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer song.deinit();
+            try song.addTrack(.{ .name = "lost", .composer = &part, .output = "nowhere" });
+
+            try testing.expectError(error.UnknownBus, song.finalize(.{}));
+        }
+
+        test "tracks containing their own composer" {
+            const allocator = testing.allocator;
+
+            var loop = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer loop.deinit();
+            try loop.addTrack(.{ .name = "itself", .composer = &loop });
+
+            try testing.expectError(error.TrackCycle, loop.finalize(.{}));
+
+            var verse = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer verse.deinit();
+
+            var chorus = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer chorus.deinit();
+
+            try verse.addTrack(.{ .name = "chorus", .composer = &chorus });
+            try chorus.addTrack(.{ .name = "verse", .composer = &verse });
+
+            try testing.expectError(error.TrackCycle, verse.finalize(.{}));
+        }
+
+        test "finalize with mix modes" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{ 1.0, 1.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+            try composer.append(.{ .wave = wave, .start_point = 0, .mute = true });
+
+            const average = try composer.finalize(.{ .mode = .average });
+            defer average.deinit();
+            try testing.expectApproxEqAbs(average.samples[0], 1.0, 0.000001);
+
+            const headroom = try composer.finalize(.{ .mode = .headroom });
+            defer headroom.deinit();
+            try testing.expectApproxEqAbs(headroom.samples[0], @sqrt(@as(T, 3.0)), 0.000001);
+
+            const soft_clip = try composer.finalize(.{ .mode = .soft_clip });
+            defer soft_clip.deinit();
+            try testing.expectApproxEqAbs(soft_clip.samples[0], 0.9950547536867305, 0.000001);
+
+            const weighted = try composer.finalize(.{ .mode = .{ .weighted = &.{ 0.5, 0.25, 0.0, 1.0 } } });
+            defer weighted.deinit();
+            try testing.expectApproxEqAbs(weighted.samples[1], 0.75, 0.000001);
+
+            try testing.expectError(error.LengthMismatch, composer.finalize(.{ .mode = .{ .weighted = &.{ 0.5, 0.25 } } }));
+        }
+
+        test "finalize averages the waves sounding at each sample" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            const samples: []const T = &[_]T{ 1.0, 1.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            // A melody of notes which never overlap keeps its level
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+            try composer.append(.{ .wave = wave, .start_point = 2 });
+            try composer.append(.{ .wave = wave, .start_point = 4 });
+
+            const melody = try composer.finalize(.{ .mode = .average });
+            defer melody.deinit();
+            try testing.expectEqualSlices(T, melody.samples, &.{ 1.0, 1.0, 1.0, 1.0, 1.0, 1.0 });
+
+            const melody_headroom = try composer.finalize(.{ .mode = .headroom });
+            defer melody_headroom.deinit();
+            try testing.expectEqualSlices(T, melody_headroom.samples, &.{ 1.0, 1.0, 1.0, 1.0, 1.0, 1.0 });
+
+            // Only the sample where two notes overlap is divided by two
+            try composer.append(.{ .wave = wave, .start_point = 5, .gain = .{ .linear = 0.5 } });
+
+            const overlapping = try composer.finalize(.{ .mode = .average });
+            defer overlapping.deinit();
+            try testing.expectEqualSlices(T, overlapping.samples, &.{ 1.0, 1.0, 1.0, 1.0, 1.0, 0.75, 0.5 });
+        }
+
+        test "finalize resamples waves with another sample rate" {
+            const allocator = testing.allocator;
+            var composer = Self.init(allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer composer.deinit();
+
+            // One second of silence at 22050 Hz
+            const samples: []T = try allocator.alloc(T, 22050);
+            defer allocator.free(samples);
+
+            for (0..samples.len) |i| {
+                samples[i] = 0.0;
+            }
+
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 22050,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try composer.append(.{ .wave = wave, .start_point = 0 });
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+
+            // Still one second long
+            try testing.expectEqual(result.samples.len, 44100);
+            try testing.expectEqual(result.sample_rate, 44100);
+        }
+
+        test "appendMidi renders notes at their positions" {
+            const allocator = testing.allocator;
+            const Instrument = struct {
+                fn play(note: u7, velocity: u7, duration_samples: usize, a: std.mem.Allocator) !Wave(T) {
+                    const samples: []T = try a.alloc(T, duration_samples);
+                    @memset(samples, @as(T, @floatFromInt(velocity)) / 127.0);
+                    _ = note;
+                    return .{ .samples = samples, .allocator = a, .sample_rate = 1000, .channels = 1 };
+                }
+            };
+
+            // 120 BPM: one beat of 480 ticks lasts 500 frames
+            const midi: Midi(T) = .{
+                .notes = &.{
+                    .{ .tick = 0, .duration = 480, .key = 60, .velocity = 127 },
+                    .{ .tick = 480, .duration = 240, .key = 64, .velocity = 127, .channel = 1 },
+                },
+                .timeline = .{},
+                .allocator = allocator,
+            };
+
+            var composer = Self.init(allocator, .{ .sample_rate = 1000, .channels = 1 });
+            defer composer.deinit();
+            try composer.appendMidi(midi, Instrument.play, .{});
+
+            try testing.expectEqual(composer.info.len, 2);
+            try testing.expectEqual(composer.rendered_waves.len, 2);
+            try testing.expectEqual(composer.info[1].start_point, 500);
+            try testing.expectEqual(composer.info[1].wave.samples.len, 250);
+
+            const result = try composer.finalize(.{});
+            defer result.deinit();
+            try testing.expectEqual(result.samples.len, 750);
+            try testing.expectEqual(result.samples[0], 1.0);
+            try testing.expectEqual(result.samples[600], 1.0);
+
+            var filtered = Self.init(allocator, .{ .sample_rate = 1000, .channels = 1 });
+            defer filtered.deinit();
+            try filtered.appendMidi(midi, Instrument.play, .{ .channel = 1 });
+
+            try testing.expectEqual(filtered.info.len, 1);
+            try testing.expectEqual(filtered.info[0].start_point, 500);
+        }
     };
 }
 
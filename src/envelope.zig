@@ -0,0 +1,420 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+const Oscillator = @import("./root.zig").Oscillator;
+const Error = @import("./error.zig").Error;
+
+/// Envelope type function: Creates an Envelope type for the specified sample type.
+///
+/// Envelope describes how a level changes over the duration of a wave.
+/// It can be applied to a wave as a filter (multiplying every sample by the level),
+/// or rendered as its own control wave.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Envelope = lightmix.Envelope;
+/// const envelope: Envelope(f64) = .{ .adsr = .{
+///     .attack = 0.01,
+///     .decay = 0.1,
+///     .sustain = 0.7,
+///     .release = 0.2,
+/// } };
+///
+/// // Shape the amplitude of a wave
+/// try wave.filter_with(Envelope(f64), Envelope(f64).filter, envelope);
+/// ```
+pub fn inner(comptime T: type) type {
+    return union(enum) {
+        /// Attack, decay, sustain and release
+        adsr: ADSR,
+        /// Attack, hold, decay, sustain and release
+        ahdsr: AHDSR,
+        /// Arbitrary segments between breakpoints
+        breakpoints: Breakpoints,
+
+        const Self = @This();
+
+        /// How fast `.exponential` and `.logarithmic` curves bend.
+        const steepness: T = 5.0;
+
+        /// Shape of the transition between two levels.
+        pub const Curve = enum {
+            /// Constant rate of change
+            linear,
+            /// Changes slowly at first, then quickly
+            exponential,
+            /// Changes quickly at first, then slowly
+            logarithmic,
+
+            /// Interpolates between `from` and `to` at `progress` (0.0 to 1.0).
+            pub fn interpolate(self: Curve, from: T, to: T, progress: T) T {
+                const x: T = @max(0.0, @min(1.0, progress));
+                const shaped: T = switch (self) {
+                    .linear => x,
+                    .exponential => (@exp(steepness * x) - 1.0) / (@exp(steepness) - 1.0),
+                    .logarithmic => 1.0 - (@exp(steepness * (1.0 - x)) - 1.0) / (@exp(steepness) - 1.0),
+                };
+
+                return from + (to - from) * shaped;
+            }
+        };
+
+        /// Envelope with attack, decay, sustain and release stages.
+        ///
+        /// The release stage is placed at the end of the wave, so the wave length
+        /// should include the release time.
+        pub const ADSR = struct {
+            /// Time to rise from 0.0 to 1.0, in seconds
+            attack: T,
+            /// Time to fall from 1.0 to `sustain`, in seconds
+            decay: T,
+            /// Level held until the release stage
+            sustain: T,
+            /// Time to fall to 0.0 at the end of the wave, in seconds
+            release: T,
+
+            attack_curve: Curve = .linear,
+            decay_curve: Curve = .linear,
+            release_curve: Curve = .linear,
+        };
+
+        /// Envelope with attack, hold, decay, sustain and release stages.
+        ///
+        /// The release stage is placed at the end of the wave, so the wave length
+        /// should include the release time.
+        pub const AHDSR = struct {
+            /// Time to rise from 0.0 to 1.0, in seconds
+            attack: T,
+            /// Time to stay at 1.0, in seconds
+            hold: T,
+            /// Time to fall from 1.0 to `sustain`, in seconds
+            decay: T,
+            /// Level held until the release stage
+            sustain: T,
+            /// Time to fall to 0.0 at the end of the wave, in seconds
+            release: T,
+
+            attack_curve: Curve = .linear,
+            decay_curve: Curve = .linear,
+            release_curve: Curve = .linear,
+        };
+
+        /// Envelope made of consecutive segments.
+        ///
+        /// After the last segment, its target level is held until the end of the wave.
+        pub const Breakpoints = struct {
+            /// Level at the start of the wave
+            start_level: T = 0.0,
+            /// Segments in order. The slice is not owned by the envelope.
+            segments: []const Segment,
+        };
+
+        /// A transition to a target level.
+        pub const Segment = struct {
+            /// Length of the transition, in seconds
+            duration: T,
+            /// Level at the end of the transition
+            level: T,
+            /// Shape of the transition
+            curve: Curve = .linear,
+        };
+
+        /// Returns the level of the envelope at a point in time.
+        ///
+        /// ## Parameters
+        /// - `self`: The envelope
+        /// - `time`: Time from the start of the wave, in seconds
+        /// - `length`: Length of the whole wave, in seconds
+        ///
+        /// ## Returns
+        /// The level at `time`
+        pub fn levelAt(self: Self, time: T, length: T) T {
+            return switch (self) {
+                .adsr => |v| gatedLevelWithRelease(.{
+                    .attack = v.attack,
+                    .hold = 0.0,
+                    .decay = v.decay,
+                    .sustain = v.sustain,
+                    .release = v.release,
+                    .attack_curve = v.attack_curve,
+                    .decay_curve = v.decay_curve,
+                    .release_curve = v.release_curve,
+                }, time, length),
+                .ahdsr => |v| gatedLevelWithRelease(v, time, length),
+                .breakpoints => |v| breakpointsLevel(v, time),
+            };
+        }
+
+        fn gatedLevelWithRelease(v: AHDSR, time: T, length: T) T {
+            const release_start: T = @max(length - v.release, 0.0);
+            if (time < release_start)
+                return gatedLevel(v, time);
+
+            // The release stage starts from wherever the gated stages had reached
+            const from: T = gatedLevel(v, release_start);
+            if (v.release <= 0.0)
+                return 0.0;
+
+            return v.release_curve.interpolate(from, 0.0, (time - release_start) / v.release);
+        }
+
+        fn gatedLevel(v: AHDSR, time: T) T {
+            var t: T = time;
+
+            if (t < v.attack)
+                return v.attack_curve.interpolate(0.0, 1.0, t / v.attack);
+            t -= v.attack;
+
+            if (t < v.hold)
+                return 1.0;
+            t -= v.hold;
+
+            if (t < v.decay)
+                return v.decay_curve.interpolate(1.0, v.sustain, t / v.decay);
+
+            return v.sustain;
+        }
+
+        fn breakpointsLevel(v: Breakpoints, time: T) T {
+            var level: T = v.start_level;
+            var t: T = time;
+
+            for (v.segments) |segment| {
+                if (t < segment.duration)
+                    return segment.curve.interpolate(level, segment.level, t / segment.duration);
+
+                t -= segment.duration;
+                level = segment.level;
+            }
+
+            return level;
+        }
+
+        /// Options for rendering an envelope as a control wave.
+        pub const RenderOptions = struct {
+            /// Length of the wave in seconds
+            duration: T,
+            /// Samples per second
+            sample_rate: u32,
+            /// Number of channels. Every channel receives the same level.
+            channels: u16 = 1,
+        };
+
+        /// Renders the envelope as a control wave, whose samples are the levels.
+        ///
+        /// ## Parameters
+        /// - `self`: The envelope to render
+        /// - `allocator`: Memory allocator for the sample data
+        /// - `options`: Duration and format of the control wave
+        ///
+        /// ## Returns
+        /// A new Wave containing `round(duration * sample_rate)` frames
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The duration is negative, NaN or too long to allocate
+        /// - Allocator error (errors.OutOfMemory)
+        pub fn render(
+            self: Self,
+            allocator: std.mem.Allocator,
+            options: RenderOptions,
+        ) (Error || std.mem.Allocator.Error)!Wave(T) {
+            const frames: usize = try Oscillator(T).frameCount(options.duration, options.sample_rate);
+            const channels: usize = options.channels;
+
+            const samples: []T = try allocator.alloc(T, std.math.mul(usize, frames, channels) catch return error.InvalidRange);
+            self.fill(samples, channels, options.sample_rate, null);
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = allocator,
+
+                .sample_rate = options.sample_rate,
+                .channels = options.channels,
+            };
+        }
+
+        /// A filter function which multiplies every sample of a wave by the envelope level.
+        ///
+        /// The envelope spans the whole wave. Use it through `Wave(T).filter_with`.
+        ///
+        /// ## Example
+        /// ```zig
+        /// try wave.filter_with(Envelope(f64), Envelope(f64).filter, .{ .adsr = .{
+        ///     .attack = 0.01,
+        ///     .decay = 0.1,
+        ///     .sustain = 0.7,
+        ///     .release = 0.2,
+        /// } });
+        /// ```
+        pub fn filter(comptime _: type, original_wave: Wave(T), envelope: Self) std.mem.Allocator.Error!Wave(T) {
+            const samples: []T = try original_wave.allocator.alloc(T, original_wave.samples.len);
+            envelope.fill(samples, original_wave.channels, original_wave.sample_rate, original_wave.samples);
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = original_wave.allocator,
+
+                .sample_rate = original_wave.sample_rate,
+                .channels = original_wave.channels,
+            };
+        }
+
+        /// Writes the envelope level of every frame into `dest`, multiplied by the
+        /// matching sample of `source` when it is given.
+        fn fill(self: Self, dest: []T, channels: usize, sample_rate: u32, source: ?[]const T) void {
+            if (channels == 0)
+                return;
+
+            const frames: usize = dest.len / channels;
+            const rate: T = @floatFromInt(sample_rate);
+            const length: T = @as(T, @floatFromInt(frames)) / rate;
+
+            for (0..frames) |i| {
+                const level: T = self.levelAt(@as(T, @floatFromInt(i)) / rate, length);
+
+                for (0..channels) |ch| {
+                    const index: usize = i * channels + ch;
+                    dest[index] = if (source) |s| s[index] * level else level;
+                }
+            }
+        }
+
+        test "adsr" {
+            const allocator = testing.allocator;
+            const envelope: Self = .{ .adsr = .{
+                .attack = 0.2,
+                .decay = 0.2,
+                .sustain = 0.5,
+                .release = 0.2,
+            } };
+
+            const wave = try envelope.render(allocator, .{
+                .duration = 1.0,
+                .sample_rate = 10,
+            });
+            defer wave.deinit();
+
+            const expected: []const T = &[_]T{ 0.0, 0.5, 1.0, 0.75, 0.5, 0.5, 0.5, 0.5, 0.5, 0.25 };
+            try testing.expectEqual(wave.samples.len, expected.len);
+            for (expected, wave.samples) |e, actual| {
+                try testing.expectApproxEqAbs(e, actual, 0.000001);
+            }
+        }
+
+        test "invalid durations" {
+            const allocator = testing.allocator;
+            const envelope: Self = .{ .adsr = .{
+                .attack = 0.2,
+                .decay = 0.2,
+                .sustain = 0.5,
+                .release = 0.2,
+            } };
+
+            for ([_]T{ -1.0, std.math.nan(T), std.math.inf(T), 1e30 }) |duration| {
+                try testing.expectError(error.InvalidRange, envelope.render(allocator, .{
+                    .duration = duration,
+                    .sample_rate = 44100,
+                }));
+            }
+        }
+
+        test "ahdsr" {
+            const allocator = testing.allocator;
+            const envelope: Self = .{ .ahdsr = .{
+                .attack = 0.2,
+                .hold = 0.2,
+                .decay = 0.2,
+                .sustain = 0.5,
+                .release = 0.2,
+            } };
+
+            const wave = try envelope.render(allocator, .{
+                .duration = 1.0,
+                .sample_rate = 10,
+            });
+            defer wave.deinit();
+
+            const expected: []const T = &[_]T{ 0.0, 0.5, 1.0, 1.0, 1.0, 0.75, 0.5, 0.5, 0.5, 0.25 };
+            try testing.expectEqual(wave.samples.len, expected.len);
+            for (expected, wave.samples) |e, actual| {
+                try testing.expectApproxEqAbs(e, actual, 0.000001);
+            }
+        }
+
+        test "release starts from the current level of a short wave" {
+            const envelope: Self = .{ .adsr = .{
+                .attack = 1.0,
+                .decay = 0.5,
+                .sustain = 0.5,
+                .release = 0.5,
+            } };
+
+            // The wave ends before the attack finishes: the release starts at 0.5s from level 0.5
+            try testing.expectApproxEqAbs(envelope.levelAt(0.5, 1.0), 0.5, 0.000001);
+            try testing.expectApproxEqAbs(envelope.levelAt(0.75, 1.0), 0.25, 0.000001);
+        }
+
+        test "breakpoints" {
+            const segments: []const Segment = &[_]Segment{
+                .{ .duration = 1.0, .level = 1.0 },
+                .{ .duration = 2.0, .level = 0.0, .curve = .exponential },
+                .{ .duration = 2.0, .level = 1.0, .curve = .logarithmic },
+            };
+            const envelope: Self = .{ .breakpoints = .{ .start_level = 0.5, .segments = segments } };
+
+            try testing.expectApproxEqAbs(envelope.levelAt(0.0, 10.0), 0.5, 0.000001);
+            try testing.expectApproxEqAbs(envelope.levelAt(0.5, 10.0), 0.75, 0.000001);
+            try testing.expectApproxEqAbs(envelope.levelAt(1.0, 10.0), 1.0, 0.000001);
+
+            // An exponential curve moves slower than a linear one at first
+            try testing.expect(envelope.levelAt(2.0, 10.0) > 0.5);
+            try testing.expectApproxEqAbs(envelope.levelAt(3.0, 10.0), 0.0, 0.000001);
+
+            // A logarithmic curve moves faster than a linear one at first
+            try testing.expect(envelope.levelAt(4.0, 10.0) > 0.5);
+
+            // The last level is held
+            try testing.expectApproxEqAbs(envelope.levelAt(5.0, 10.0), 1.0, 0.000001);
+            try testing.expectApproxEqAbs(envelope.levelAt(9.0, 10.0), 1.0, 0.000001);
+        }
+
+        test "curves keep their end points" {
+            for (std.enums.values(Curve)) |curve| {
+                try testing.expectApproxEqAbs(curve.interpolate(0.2, 0.8, 0.0), 0.2, 0.000001);
+                try testing.expectApproxEqAbs(curve.interpolate(0.2, 0.8, 1.0), 0.8, 0.000001);
+            }
+        }
+
+        test "filter_with applies the envelope to every channel" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0 };
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 4,
+                .channels = 2,
+            });
+            try wave.filter_with(Self, Self.filter, .{ .breakpoints = .{
+                .segments = &[_]Segment{.{ .duration = 1.0, .level = 1.0 }},
+            } });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.sample_rate, 4);
+            try testing.expectEqual(wave.channels, 2);
+
+            const expected: []const T = &[_]T{ 0.0, -0.0, 0.25, -0.25, 0.5, -0.5, 0.75, -0.75 };
+            for (expected, wave.samples) |e, actual| {
+                try testing.expectApproxEqAbs(e, actual, 0.000001);
+            }
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
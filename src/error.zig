@@ -0,0 +1,26 @@
+/// Errors returned by lightmix operations when their input is invalid.
+///
+/// Operations which combine waves, or take indices and lengths, check their input and
+/// return one of these errors, instead of relying on assertions which vanish in release builds.
+///
+/// ## Usage
+/// ```zig
+/// const result = wave.mix(other, .{}) catch |err| switch (err) {
+///     error.SampleRateMismatch => retry: {
+///         const resampled = try other.resample(wave.sample_rate, .sinc);
+///         defer resampled.deinit();
+///         break :retry try wave.mix(resampled, .{});
+///     },
+///     else => return err,
+/// };
+/// ```
+pub const Error = error{
+    /// Waves which must share a sample rate don't
+    SampleRateMismatch,
+    /// Waves which must share a channel count or layout don't
+    ChannelMismatch,
+    /// Waves or slices which must have the same length don't
+    LengthMismatch,
+    /// An index, point or count is outside of the valid range
+    InvalidRange,
+};
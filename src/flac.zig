@@ -0,0 +1,1291 @@
+const std = @import("std");
+const testing = std.testing;
+const Md5 = std.crypto.hash.Md5;
+const Crc8 = std.hash.crc.Crc8Smbus;
+const Crc16 = std.hash.crc.Crc16Umts;
+
+/// Flac type function: Creates a FLAC codec for the specified sample type.
+///
+/// Reads every FLAC stream with a fixed bit depth, and writes FLAC streams with
+/// constant, verbatim, fixed and LPC subframes, choosing the smallest one for every channel
+/// of every frame. Decoded streams are checked against their CRCs and MD5 signature,
+/// so a round trip through `write` and `read` gives back the same integer samples.
+/// Integer samples are scaled to the range from -1.0 to 1.0, as in WAV files.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// Use it through `Wave(T).LowLevelInterfaces.flac`:
+/// ```zig
+/// const wave = try Wave(f64).read(.flac, allocator, &reader);
+/// try wave.write(.flac, &writer.interface, .{ .bits = 16, .compression_level = 8 });
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// Errors that can occur when reading or writing FLAC streams.
+        pub const FlacErrors = error{
+            /// The data is not a FLAC stream, or one of its blocks or frames is malformed
+            InvalidFlacFile,
+            /// The bit depth is not supported
+            UnsupportedBits,
+            /// A frame's CRC or the stream's MD5 signature doesn't match the decoded data
+            ChecksumMismatch,
+        };
+
+        /// Options for writing a FLAC stream.
+        pub const WriteOptions = struct {
+            /// Bits per sample, from 4 to 32
+            bits: u16,
+            /// From 0 (fastest, largest) to 8 (slowest, smallest), as in the reference encoder
+            compression_level: u4 = 5,
+        };
+
+        /// Samples and format decoded from a FLAC stream.
+        pub const Decoded = struct {
+            samples: []const T,
+            sample_rate: u32,
+            channels: u16,
+        };
+
+        /// Reads a FLAC stream.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for sample data
+        /// - `reader`: A reader interface providing the raw file bytes
+        ///
+        /// ## Returns
+        /// The decoded samples, owned by the caller, with the sample rate and channel count
+        ///
+        /// ## Errors
+        /// - `InvalidFlacFile`: The data is not a FLAC stream, or a block or frame is malformed
+        /// - `UnsupportedBits`: The stream has fewer than 4 bits per sample
+        /// - `ChecksumMismatch`: A frame or the whole stream is corrupted
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn read(allocator: std.mem.Allocator, reader: anytype) anyerror!Decoded {
+            const bytes: []u8 = try reader.allocRemaining(allocator, .unlimited);
+            defer allocator.free(bytes);
+
+            if (bytes.len < 4 or !std.mem.eql(u8, bytes[0..4], "fLaC"))
+                return error.InvalidFlacFile;
+
+            var stream_info: ?StreamInfo = null;
+            var position: usize = 4;
+            while (true) {
+                if (bytes.len < position + 4)
+                    return error.InvalidFlacFile;
+
+                const header: u8 = bytes[position];
+                const length: usize = std.mem.readInt(u24, bytes[position + 1 ..][0..3], .big);
+                position += 4;
+                if (bytes.len < position + length)
+                    return error.InvalidFlacFile;
+
+                if (header & 0x7F == 0)
+                    stream_info = try StreamInfo.parse(bytes[position..][0..length]);
+
+                position += length;
+                if (header & 0x80 != 0)
+                    break;
+            }
+
+            const info: StreamInfo = stream_info orelse return error.InvalidFlacFile;
+            const max: T = @floatFromInt((@as(i64, 1) << @intCast(info.bits - 1)) - 1);
+            const sample_bytes: usize = (@as(usize, info.bits) + 7) / 8;
+
+            const block: []i64 = try allocator.alloc(i64, @as(usize, info.max_block_size) * info.channels);
+            defer allocator.free(block);
+
+            var samples: std.array_list.Aligned(T, null) = .empty;
+            errdefer samples.deinit(allocator);
+
+            var md5 = Md5.init(.{});
+            var decoded: u64 = 0;
+            while (if (info.total_samples == 0) position < bytes.len else decoded < info.total_samples) {
+                const frame = try decodeFrame(bytes, position, info, block);
+                position = frame.end;
+
+                for (0..frame.block_size) |i| {
+                    for (0..info.channels) |ch| {
+                        const value: i64 = block[ch * info.max_block_size + i];
+
+                        var buffer: [4]u8 = undefined;
+                        std.mem.writeInt(u32, &buffer, @truncate(@as(u64, @bitCast(value))), .little);
+                        md5.update(buffer[0..sample_bytes]);
+
+                        try samples.append(allocator, @as(T, @floatFromInt(value)) / max);
+                    }
+                }
+                decoded += frame.block_size;
+            }
+
+            if (!std.mem.allEqual(u8, &info.md5, 0)) {
+                var digest: [Md5.digest_length]u8 = undefined;
+                md5.final(&digest);
+                if (!std.mem.eql(u8, &digest, &info.md5))
+                    return error.ChecksumMismatch;
+            }
+
+            return .{
+                .samples = try samples.toOwnedSlice(allocator),
+                .sample_rate = info.sample_rate,
+                .channels = info.channels,
+            };
+        }
+
+        /// Writes samples as a FLAC stream.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for the encoded stream, freed before returning
+        /// - `samples`: Interleaved samples
+        /// - `sample_rate`: Samples per second
+        /// - `channels`: Number of channels, from 1 to 8
+        /// - `writer`: A writer interface for the output bytes
+        /// - `options`: Bit depth and compression level
+        ///
+        /// ## Errors
+        /// - `UnsupportedBits`: `bits` is not between 4 and 32
+        /// - `InvalidRange`: The compression level, channel count or sample rate is out of range
+        /// - `LengthMismatch`: The sample count is not a multiple of `channels`
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the writer
+        pub fn write(
+            allocator: std.mem.Allocator,
+            samples: []const T,
+            sample_rate: u32,
+            channels: u16,
+            writer: anytype,
+            options: WriteOptions,
+        ) anyerror!void {
+            if (options.bits < 4 or options.bits > 32)
+                return error.UnsupportedBits;
+            if (options.compression_level >= levels.len or channels == 0 or channels > 8)
+                return error.InvalidRange;
+            if (sample_rate == 0 or sample_rate > std.math.maxInt(u20))
+                return error.InvalidRange;
+            if (samples.len % channels != 0)
+                return error.LengthMismatch;
+
+            const level: Level = levels[options.compression_level];
+            const bits: u7 = @intCast(options.bits);
+            const frame_count: usize = samples.len / channels;
+            const max: T = @floatFromInt((@as(i64, 1) << @intCast(bits - 1)) - 1);
+            const sample_bytes: usize = (@as(usize, bits) + 7) / 8;
+
+            // One slice per channel, then the side and mid channels
+            const block: []i64 = try allocator.alloc(i64, level.block_size * (channels + 2));
+            defer allocator.free(block);
+            const residual: []i64 = try allocator.alloc(i64, level.block_size);
+            defer allocator.free(residual);
+            const windowed: []f64 = try allocator.alloc(f64, level.block_size);
+            defer allocator.free(windowed);
+
+            var output: BitWriter = .{ .allocator = allocator };
+            defer output.bytes.deinit(allocator);
+
+            var md5 = Md5.init(.{});
+            var min_frame_size: usize = 0;
+            var max_frame_size: usize = 0;
+
+            var start: usize = 0;
+            var frame_number: u64 = 0;
+            while (start < frame_count) : (frame_number += 1) {
+                const n: usize = @min(level.block_size, frame_count - start);
+
+                var inputs: [8][]i64 = undefined;
+                for (0..channels) |ch| {
+                    inputs[ch] = block[ch * level.block_size ..][0..n];
+                }
+                for (0..n) |i| {
+                    for (0..channels) |ch| {
+                        const sample: T = std.math.clamp(samples[(start + i) * channels + ch], -1.0, 1.0);
+                        const value: i64 = @intFromFloat(@round(sample * max));
+                        inputs[ch][i] = value;
+
+                        var buffer: [4]u8 = undefined;
+                        std.mem.writeInt(u32, &buffer, @truncate(@as(u64, @bitCast(value))), .little);
+                        md5.update(buffer[0..sample_bytes]);
+                    }
+                }
+
+                const frame_start: usize = output.bytes.items.len;
+                try encodeFrame(&output, .{
+                    .inputs = inputs[0..channels],
+                    .side = block[channels * level.block_size ..][0..n],
+                    .mid = block[(channels + 1) * level.block_size ..][0..n],
+                    .bits = bits,
+                    .sample_rate = sample_rate,
+                    .frame_number = frame_number,
+                    .level = level,
+                    .scratch = .{ .residual = residual, .windowed = windowed },
+                });
+
+                const frame_size: usize = output.bytes.items.len - frame_start;
+                min_frame_size = if (frame_number == 0) frame_size else @min(min_frame_size, frame_size);
+                max_frame_size = @max(max_frame_size, frame_size);
+                start += n;
+            }
+
+            var info: BitWriter = .{ .allocator = allocator };
+            defer info.bytes.deinit(allocator);
+            try info.writeBits(level.block_size, 16);
+            try info.writeBits(level.block_size, 16);
+            try info.writeBits(min_frame_size, 24);
+            try info.writeBits(max_frame_size, 24);
+            try info.writeBits(sample_rate, 20);
+            try info.writeBits(channels - 1, 3);
+            try info.writeBits(bits - 1, 5);
+            try info.writeBits(frame_count, 36);
+
+            var digest: [Md5.digest_length]u8 = undefined;
+            md5.final(&digest);
+
+            try writer.writeAll("fLaC");
+            // The last metadata block, STREAMINFO, 34 bytes long
+            try writer.writeAll(&.{ 0x80, 0x00, 0x00, 0x22 });
+            try writer.writeAll(info.bytes.items);
+            try writer.writeAll(&digest);
+            try writer.writeAll(output.bytes.items);
+        }
+
+        fn testSignal(allocator: std.mem.Allocator, frames: usize, channels: u16) ![]T {
+            const samples: []T = try allocator.alloc(T, frames * channels);
+            var prng = std.Random.DefaultPrng.init(0);
+            const random = prng.random();
+
+            for (0..frames) |i| {
+                for (0..channels) |ch| {
+                    const t: T = @as(T, @floatFromInt(i)) / 44100.0;
+                    const frequency: T = 440.0 * @as(T, @floatFromInt(ch + 1));
+                    const noise: T = @floatCast(random.float(f64) * 0.02 - 0.01);
+                    samples[i * channels + ch] = 0.6 * @sin(2.0 * std.math.pi * frequency * t) + noise;
+                }
+            }
+
+            return samples;
+        }
+
+        fn expectRoundTrip(samples: []const T, channels: u16, options: WriteOptions) !void {
+            const allocator = testing.allocator;
+
+            var writer = std.Io.Writer.Allocating.init(allocator);
+            defer writer.deinit();
+            try Self.write(allocator, samples, 44100, channels, &writer.writer, options);
+
+            var reader = std.Io.Reader.fixed(writer.written());
+            const decoded = try Self.read(allocator, &reader);
+            defer allocator.free(decoded.samples);
+
+            try testing.expectEqual(decoded.sample_rate, 44100);
+            try testing.expectEqual(decoded.channels, channels);
+            try testing.expectEqual(decoded.samples.len, samples.len);
+
+            // Lossless: every sample comes back as the integer it was quantized to
+            const max: T = @floatFromInt((@as(i64, 1) << @intCast(options.bits - 1)) - 1);
+            for (samples, decoded.samples) |expected, actual| {
+                try testing.expectEqual(@round(std.math.clamp(expected, -1.0, 1.0) * max) / max, actual);
+            }
+        }
+
+        test "write & read every compression level" {
+            const allocator = testing.allocator;
+            const samples: []T = try testSignal(allocator, 5000, 2);
+            defer allocator.free(samples);
+
+            for (0..levels.len) |level| {
+                try expectRoundTrip(samples, 2, .{ .bits = 16, .compression_level = @intCast(level) });
+            }
+        }
+
+        test "write & read every bit depth" {
+            const allocator = testing.allocator;
+            const samples: []T = try testSignal(allocator, 1500, 1);
+            defer allocator.free(samples);
+
+            for ([_]u16{ 4, 8, 12, 16, 20, 24, 32 }) |bits| {
+                try expectRoundTrip(samples, 1, .{ .bits = bits, .compression_level = 0 });
+                try expectRoundTrip(samples, 1, .{ .bits = bits, .compression_level = 8 });
+            }
+        }
+
+        test "write & read odd shapes" {
+            const allocator = testing.allocator;
+            const samples: []T = try testSignal(allocator, 4099, 3);
+            defer allocator.free(samples);
+
+            try expectRoundTrip(samples, 3, .{ .bits = 16 });
+            try expectRoundTrip(samples[0..3], 3, .{ .bits = 16 });
+            try expectRoundTrip(&[_]T{}, 1, .{ .bits = 16 });
+            try expectRoundTrip(&[_]T{ 0.5, 0.5, 0.5, 0.5, 0.5 }, 1, .{ .bits = 24 });
+            try expectRoundTrip(&[_]T{ 2.0, -2.0, 1.0, -1.0 }, 2, .{ .bits = 16 });
+        }
+
+        test "compression" {
+            const allocator = testing.allocator;
+            const samples: []T = try testSignal(allocator, 10000, 1);
+            defer allocator.free(samples);
+
+            var fast = std.Io.Writer.Allocating.init(allocator);
+            defer fast.deinit();
+            try Self.write(allocator, samples, 44100, 1, &fast.writer, .{ .bits = 16, .compression_level = 0 });
+
+            var best = std.Io.Writer.Allocating.init(allocator);
+            defer best.deinit();
+            try Self.write(allocator, samples, 44100, 1, &best.writer, .{ .bits = 16, .compression_level = 8 });
+
+            try testing.expect(fast.written().len < samples.len * 2);
+            try testing.expect(best.written().len < fast.written().len);
+        }
+
+        test "invalid files and options" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 0.0, 0.5, -0.5, 0.25 };
+
+            var writer = std.Io.Writer.Allocating.init(allocator);
+            defer writer.deinit();
+            try testing.expectError(error.UnsupportedBits, Self.write(allocator, samples, 44100, 1, &writer.writer, .{ .bits = 3 }));
+            try testing.expectError(error.UnsupportedBits, Self.write(allocator, samples, 44100, 1, &writer.writer, .{ .bits = 33 }));
+            try testing.expectError(error.InvalidRange, Self.write(allocator, samples, 44100, 1, &writer.writer, .{ .bits = 16, .compression_level = 9 }));
+            try testing.expectError(error.InvalidRange, Self.write(allocator, samples, 44100, 9, &writer.writer, .{ .bits = 16 }));
+            try testing.expectError(error.LengthMismatch, Self.write(allocator, samples, 44100, 3, &writer.writer, .{ .bits = 16 }));
+
+            var not_flac = std.Io.Reader.fixed("RIFF\x00\x00\x00\x00WAVE");
+            try testing.expectError(error.InvalidFlacFile, Self.read(allocator, &not_flac));
+
+            try Self.write(allocator, samples, 44100, 1, &writer.writer, .{ .bits = 16 });
+            const written: []u8 = writer.written();
+
+            var truncated = std.Io.Reader.fixed(written[0 .. written.len - 1]);
+            try testing.expectError(error.InvalidFlacFile, Self.read(allocator, &truncated));
+
+            // The frame's CRC-16
+            written[written.len - 1] ^= 0x01;
+            var bad_crc = std.Io.Reader.fixed(written);
+            try testing.expectError(error.ChecksumMismatch, Self.read(allocator, &bad_crc));
+            written[written.len - 1] ^= 0x01;
+
+            // The MD5 signature in STREAMINFO
+            written[26] ^= 0x01;
+            var bad_md5 = std.Io.Reader.fixed(written);
+            try testing.expectError(error.ChecksumMismatch, Self.read(allocator, &bad_md5));
+        }
+    };
+}
+
+/// Quantized samples of one frame, with buffers for the side and mid channels.
+const FrameInput = struct {
+    inputs: []const []i64,
+    side: []i64,
+    mid: []i64,
+    bits: u7,
+    sample_rate: u32,
+    frame_number: u64,
+    level: Level,
+    scratch: Scratch,
+};
+
+fn encodeFrame(output: *BitWriter, frame: FrameInput) std.mem.Allocator.Error!void {
+    const n: usize = frame.inputs[0].len;
+    const frame_start: usize = output.bytes.items.len;
+
+    const Encoded = struct { signal: []const i64, bits: u7, subframe: Subframe };
+    var encoded: [8]Encoded = undefined;
+    var assignment: u4 = @intCast(frame.inputs.len - 1);
+
+    if (frame.inputs.len == 2 and frame.level.stereo) {
+        const left: []const i64 = frame.inputs[0];
+        const right: []const i64 = frame.inputs[1];
+        for (0..n) |i| {
+            frame.side[i] = left[i] - right[i];
+            frame.mid[i] = (left[i] + right[i]) >> 1;
+        }
+
+        const l: Encoded = .{ .signal = left, .bits = frame.bits, .subframe = analyze(left, frame.bits, frame.level, frame.scratch) };
+        const r: Encoded = .{ .signal = right, .bits = frame.bits, .subframe = analyze(right, frame.bits, frame.level, frame.scratch) };
+        const s: Encoded = .{ .signal = frame.side, .bits = frame.bits + 1, .subframe = analyze(frame.side, frame.bits + 1, frame.level, frame.scratch) };
+        const m: Encoded = .{ .signal = frame.mid, .bits = frame.bits, .subframe = analyze(frame.mid, frame.bits, frame.level, frame.scratch) };
+
+        const options = [_]struct { u4, Encoded, Encoded }{
+            .{ 1, l, r }, // Independent
+            .{ 8, l, s }, // Left/side
+            .{ 9, s, r }, // Side/right
+            .{ 10, m, s }, // Mid/side
+        };
+
+        var best: usize = 0;
+        for (options, 0..) |option, i| {
+            const size: u64 = option[1].subframe.bits + option[2].subframe.bits;
+            if (size < options[best][1].subframe.bits + options[best][2].subframe.bits)
+                best = i;
+        }
+
+        assignment = options[best][0];
+        encoded[0] = options[best][1];
+        encoded[1] = options[best][2];
+    } else {
+        for (frame.inputs, 0..) |signal, ch| {
+            encoded[ch] = .{ .signal = signal, .bits = frame.bits, .subframe = analyze(signal, frame.bits, frame.level, frame.scratch) };
+        }
+    }
+
+    // Frame header: sync code with a fixed block size
+    try output.writeBits(0xFFF8, 16);
+
+    const block_size_code: u4 = blockSizeCode(n);
+    try output.writeBits(block_size_code, 4);
+    try output.writeBits(sampleRateCode(frame.sample_rate), 4);
+    try output.writeBits(assignment, 4);
+    try output.writeBits(sampleSizeCode(frame.bits), 3);
+    try output.writeBits(0, 1);
+    try writeUtf8(output, frame.frame_number);
+    switch (block_size_code) {
+        6 => try output.writeBits(n - 1, 8),
+        7 => try output.writeBits(n - 1, 16),
+        else => {},
+    }
+    try output.writeBits(Crc8.hash(output.bytes.items[frame_start..]), 8);
+
+    for (encoded[0..frame.inputs.len]) |e| {
+        try writeSubframe(output, e.signal, e.bits, e.subframe, frame.scratch.residual);
+    }
+
+    try output.alignToByte();
+    try output.writeBits(Crc16.hash(output.bytes.items[frame_start..]), 16);
+}
+
+fn decodeFrame(bytes: []const u8, start: usize, info: StreamInfo, block: []i64) !struct { block_size: usize, end: usize } {
+    var reader: BitReader = .{ .bytes = bytes, .position = start * 8 };
+
+    if (try reader.readBits(16) & 0xFFFE != 0xFFF8)
+        return error.InvalidFlacFile;
+
+    const block_size_code: u64 = try reader.readBits(4);
+    const sample_rate_code: u64 = try reader.readBits(4);
+    const assignment: u64 = try reader.readBits(4);
+    const sample_size_code: u64 = try reader.readBits(3);
+    _ = try reader.readBits(1);
+
+    // Frame or sample number, coded like UTF-8
+    const first: u8 = @intCast(try reader.readBits(8));
+    const leading_ones: u4 = @clz(~first);
+    if (leading_ones == 1 or leading_ones == 8)
+        return error.InvalidFlacFile;
+    if (leading_ones > 1)
+        _ = try reader.readBits(8 * @as(u7, leading_ones - 1));
+
+    const block_size: usize = switch (block_size_code) {
+        0 => return error.InvalidFlacFile,
+        1 => 192,
+        2...5 => @as(usize, 576) << @intCast(block_size_code - 2),
+        6 => @intCast(try reader.readBits(8) + 1),
+        7 => @intCast(try reader.readBits(16) + 1),
+        else => @as(usize, 256) << @intCast(block_size_code - 8),
+    };
+    switch (sample_rate_code) {
+        12 => _ = try reader.readBits(8),
+        13, 14 => _ = try reader.readBits(16),
+        15 => return error.InvalidFlacFile,
+        else => {},
+    }
+
+    const bits: u7 = switch (sample_size_code) {
+        0 => info.bits,
+        1 => 8,
+        2 => 12,
+        4 => 16,
+        5 => 20,
+        6 => 24,
+        7 => 32,
+        else => return error.InvalidFlacFile,
+    };
+    const channels: usize = switch (assignment) {
+        0...7 => @intCast(assignment + 1),
+        8...10 => 2,
+        else => return error.InvalidFlacFile,
+    };
+    if (bits != info.bits or channels != info.channels or block_size > info.max_block_size)
+        return error.InvalidFlacFile;
+
+    const header_end: usize = reader.position / 8;
+    if (try reader.readBits(8) != Crc8.hash(bytes[start..header_end]))
+        return error.ChecksumMismatch;
+
+    var outputs: [8][]i64 = undefined;
+    for (0..channels) |ch| {
+        outputs[ch] = block[ch * info.max_block_size ..][0..block_size];
+
+        const is_side: bool = (assignment == 8 and ch == 1) or (assignment == 9 and ch == 0) or (assignment == 10 and ch == 1);
+        try decodeSubframe(&reader, if (is_side) bits + 1 else bits, outputs[ch]);
+    }
+
+    switch (assignment) {
+        // Left/side
+        8 => for (outputs[0], outputs[1]) |left, *side| {
+            side.* = left -% side.*;
+        },
+        // Side/right
+        9 => for (outputs[0], outputs[1]) |*side, right| {
+            side.* = side.* +% right;
+        },
+        // Mid/side
+        10 => for (outputs[0], outputs[1]) |*mid, *side| {
+            const sum: i64 = (mid.* << 1) | (side.* & 1);
+            const difference: i64 = side.*;
+            mid.* = (sum +% difference) >> 1;
+            side.* = (sum -% difference) >> 1;
+        },
+        else => {},
+    }
+
+    reader.alignToByte();
+    const frame_end: usize = reader.position / 8;
+    if (try reader.readBits(16) != Crc16.hash(bytes[start..frame_end]))
+        return error.ChecksumMismatch;
+
+    return .{ .block_size = block_size, .end = frame_end + 2 };
+}
+
+/// Encoder settings of a compression level.
+const Level = struct {
+    block_size: usize,
+    /// Whether stereo frames try the left/side, side/right and mid/side channel assignments
+    stereo: bool,
+    /// Highest LPC order to try, or 0 to use fixed predictors only
+    max_lpc_order: usize,
+    max_partition_order: u4,
+};
+
+const levels = [_]Level{
+    .{ .block_size = 1152, .stereo = false, .max_lpc_order = 0, .max_partition_order = 3 },
+    .{ .block_size = 1152, .stereo = true, .max_lpc_order = 0, .max_partition_order = 3 },
+    .{ .block_size = 1152, .stereo = true, .max_lpc_order = 0, .max_partition_order = 3 },
+    .{ .block_size = 4096, .stereo = false, .max_lpc_order = 6, .max_partition_order = 4 },
+    .{ .block_size = 4096, .stereo = true, .max_lpc_order = 8, .max_partition_order = 4 },
+    .{ .block_size = 4096, .stereo = true, .max_lpc_order = 8, .max_partition_order = 5 },
+    .{ .block_size = 4096, .stereo = true, .max_lpc_order = 8, .max_partition_order = 6 },
+    .{ .block_size = 4096, .stereo = true, .max_lpc_order = 12, .max_partition_order = 6 },
+    .{ .block_size = 4096, .stereo = true, .max_lpc_order = 12, .max_partition_order = 8 },
+};
+
+const max_lpc_order = 12;
+
+/// Buffers shared by every subframe of a stream.
+const Scratch = struct {
+    residual: []i64,
+    windowed: []f64,
+};
+
+const StreamInfo = struct {
+    max_block_size: u16,
+    sample_rate: u32,
+    channels: u16,
+    bits: u7,
+    total_samples: u64,
+    md5: [16]u8,
+
+    fn parse(bytes: []const u8) !StreamInfo {
+        if (bytes.len < 34)
+            return error.InvalidFlacFile;
+
+        var reader: BitReader = .{ .bytes = bytes };
+        _ = try reader.readBits(16); // Minimum block size
+        const max_block_size: u16 = @intCast(try reader.readBits(16));
+        _ = try reader.readBits(48); // Minimum and maximum frame sizes
+        const sample_rate: u32 = @intCast(try reader.readBits(20));
+        const channels: u16 = @intCast(try reader.readBits(3) + 1);
+        const bits: u7 = @intCast(try reader.readBits(5) + 1);
+        const total_samples: u64 = try reader.readBits(36);
+
+        if (max_block_size == 0 or sample_rate == 0)
+            return error.InvalidFlacFile;
+        if (bits < 4)
+            return error.UnsupportedBits;
+
+        return .{
+            .max_block_size = max_block_size,
+            .sample_rate = sample_rate,
+            .channels = channels,
+            .bits = bits,
+            .total_samples = total_samples,
+            .md5 = bytes[18..34].*,
+        };
+    }
+};
+
+const Predictor = union(enum) {
+    constant,
+    verbatim,
+    fixed: usize,
+    lpc: Lpc,
+};
+
+const Lpc = struct {
+    coefficients: [max_lpc_order]i64,
+    order: usize,
+    precision: u5,
+    shift: u5,
+};
+
+const Partition = struct {
+    escape: bool,
+    /// The Rice parameter, or the bit count of escaped residuals
+    parameter: u6,
+};
+
+/// Partitioned Rice coding of a residual.
+const RicePlan = struct {
+    bits: u64,
+    order: u4,
+    /// Whether parameters take 5 bits (RICE2) instead of 4
+    wide: bool,
+    partitions: [1 << 8]Partition,
+};
+
+const Subframe = struct {
+    bits: u64,
+    predictor: Predictor,
+    plan: RicePlan,
+};
+
+/// Finds the smallest subframe for a signal.
+fn analyze(signal: []const i64, bits: u7, level: Level, scratch: Scratch) Subframe {
+    const n: usize = signal.len;
+
+    if (std.mem.allEqual(i64, signal, signal[0]))
+        return .{ .bits = 8 + @as(u64, bits), .predictor = .constant, .plan = undefined };
+
+    var best: Subframe = .{ .bits = 8 + @as(u64, bits) * n, .predictor = .verbatim, .plan = undefined };
+
+    for (0..@min(4, n - 1) + 1) |order| {
+        const predictor: Predictor = .{ .fixed = order };
+        const residual: []const i64 = computeResidual(signal, predictor, scratch.residual);
+        const plan: RicePlan = planRice(residual, order, n, level.max_partition_order);
+
+        const size: u64 = 8 + order * bits + plan.bits;
+        if (size < best.bits)
+            best = .{ .bits = size, .predictor = predictor, .plan = plan };
+    }
+
+    if (level.max_lpc_order > 0 and n > level.max_lpc_order) {
+        const precision: u5 = if (bits <= 16) 12 else 15;
+
+        var coefficients: [max_lpc_order][max_lpc_order]f64 = undefined;
+        const orders: usize = lpCoefficients(signal, level.max_lpc_order, scratch.windowed, &coefficients);
+
+        for (0..orders) |i| {
+            const lpc: Lpc = quantizeCoefficients(coefficients[i][0 .. i + 1], precision) orelse continue;
+            const predictor: Predictor = .{ .lpc = lpc };
+            const residual: []const i64 = computeResidual(signal, predictor, scratch.residual);
+            const plan: RicePlan = planRice(residual, lpc.order, n, level.max_partition_order);
+
+            const size: u64 = 8 + lpc.order * bits + 4 + 5 + lpc.order * precision + plan.bits;
+            if (size < best.bits)
+                best = .{ .bits = size, .predictor = predictor, .plan = plan };
+        }
+    }
+
+    return best;
+}
+
+/// Computes the residual of a predictor into `buffer`, and returns it.
+fn computeResidual(signal: []const i64, predictor: Predictor, buffer: []i64) []const i64 {
+    switch (predictor) {
+        .constant, .verbatim => return buffer[0..0],
+        .fixed => |order| {
+            for (order..signal.len) |i| {
+                buffer[i - order] = signal[i] - fixedPrediction(signal, order, i);
+            }
+            return buffer[0 .. signal.len - order];
+        },
+        .lpc => |lpc| {
+            for (lpc.order..signal.len) |i| {
+                buffer[i - lpc.order] = signal[i] - lpcPrediction(signal, lpc, i);
+            }
+            return buffer[0 .. signal.len - lpc.order];
+        },
+    }
+}
+
+fn fixedPrediction(signal: []const i64, order: usize, i: usize) i64 {
+    return switch (order) {
+        0 => 0,
+        1 => signal[i - 1],
+        2 => 2 *% signal[i - 1] -% signal[i - 2],
+        3 => 3 *% signal[i - 1] -% 3 *% signal[i - 2] +% signal[i - 3],
+        else => 4 *% signal[i - 1] -% 6 *% signal[i - 2] +% 4 *% signal[i - 3] -% signal[i - 4],
+    };
+}
+
+fn lpcPrediction(signal: []const i64, lpc: Lpc, i: usize) i64 {
+    var sum: i64 = 0;
+    for (lpc.coefficients[0..lpc.order], 1..) |coefficient, j| {
+        sum +%= coefficient *% signal[i - j];
+    }
+    return sum >> lpc.shift;
+}
+
+/// Computes the LPC coefficients of every order up to `max_order` from the autocorrelation
+/// of the Tukey-windowed signal, with the Levinson-Durbin recursion.
+///
+/// ## Returns
+/// The number of orders computed; `coefficients[i]` holds the `i + 1` coefficients of order `i + 1`.
+fn lpCoefficients(signal: []const i64, max_order: usize, windowed: []f64, coefficients: *[max_lpc_order][max_lpc_order]f64) usize {
+    const n: usize = signal.len;
+    const taper: usize = n / 4;
+
+    for (signal, 0..) |sample, i| {
+        const distance: usize = @min(i, n - 1 - i);
+        const window: f64 = if (distance < taper)
+            0.5 - 0.5 * @cos(std.math.pi * @as(f64, @floatFromInt(distance)) / @as(f64, @floatFromInt(taper)))
+        else
+            1.0;
+        windowed[i] = @as(f64, @floatFromInt(sample)) * window;
+    }
+
+    var autocorrelation: [max_lpc_order + 1]f64 = undefined;
+    for (0..max_order + 1) |lag| {
+        var sum: f64 = 0.0;
+        for (lag..n) |i| {
+            sum += windowed[i] * windowed[i - lag];
+        }
+        autocorrelation[lag] = sum;
+    }
+
+    if (autocorrelation[0] == 0.0)
+        return 0;
+
+    var lpc: [max_lpc_order]f64 = undefined;
+    var err: f64 = autocorrelation[0];
+    for (0..max_order) |i| {
+        var r: f64 = -autocorrelation[i + 1];
+        for (0..i) |j| {
+            r -= lpc[j] * autocorrelation[i - j];
+        }
+        r /= err;
+
+        lpc[i] = r;
+        var j: usize = 0;
+        while (j < i / 2) : (j += 1) {
+            const tmp: f64 = lpc[j];
+            lpc[j] += r * lpc[i - 1 - j];
+            lpc[i - 1 - j] += r * tmp;
+        }
+        if (i % 2 == 1)
+            lpc[j] += lpc[j] * r;
+
+        err *= 1.0 - r * r;
+
+        for (0..i + 1) |k| {
+            coefficients[i][k] = -lpc[k];
+        }
+        if (err <= 0.0)
+            return i + 1;
+    }
+
+    return max_order;
+}
+
+/// Quantizes LPC coefficients to `precision` bits, carrying the rounding error to the next coefficient.
+fn quantizeCoefficients(coefficients: []const f64, precision: u5) ?Lpc {
+    var max_coefficient: f64 = 0.0;
+    for (coefficients) |coefficient| {
+        max_coefficient = @max(max_coefficient, @abs(coefficient));
+    }
+    if (max_coefficient <= 0.0)
+        return null;
+
+    const exponent: i32 = std.math.frexp(max_coefficient).exponent;
+    const shift: u5 = @intCast(std.math.clamp(@as(i32, precision) - 1 - exponent, 0, 15));
+    const max_value: i64 = (@as(i64, 1) << (precision - 1)) - 1;
+
+    var result: Lpc = .{
+        .coefficients = undefined,
+        .order = coefficients.len,
+        .precision = precision,
+        .shift = shift,
+    };
+
+    var err: f64 = 0.0;
+    for (coefficients, 0..) |coefficient, i| {
+        err += coefficient * @as(f64, @floatFromInt(@as(u32, 1) << shift));
+        const value: i64 = std.math.clamp(@as(i64, @intFromFloat(@round(err))), -max_value - 1, max_value);
+        err -= @floatFromInt(value);
+        result.coefficients[i] = value;
+    }
+
+    return result;
+}
+
+fn zigzag(value: i64) u64 {
+    return (@as(u64, @bitCast(value)) << 1) ^ @as(u64, @bitCast(value >> 63));
+}
+
+/// Number of bits needed to store a value as a two's complement integer.
+fn signedBits(value: i64) u7 {
+    const magnitude: u64 = @bitCast(if (value < 0) ~value else value);
+    return 65 - @as(u7, @clz(magnitude));
+}
+
+/// Chooses the partition order and the parameters of every partition which code a residual in the fewest bits.
+fn planRice(residual: []const i64, predictor_order: usize, n: usize, max_order: u4) RicePlan {
+    var best: RicePlan = undefined;
+    best.bits = std.math.maxInt(u64);
+
+    var order: u4 = 0;
+    while (true) : (order += 1) {
+        const partitions: usize = @as(usize, 1) << order;
+        if (n % partitions != 0 or (order > 0 and (n >> order) <= predictor_order))
+            break;
+
+        var plan: RicePlan = .{ .bits = 6, .order = order, .wide = false, .partitions = undefined };
+        const size: usize = n >> order;
+        var start: usize = 0;
+
+        for (0..partitions) |part| {
+            const count: usize = if (part == 0) size - predictor_order else size;
+            const segment: []const i64 = residual[start..][0..count];
+            start += count;
+
+            var sum: u64 = 0;
+            var raw_bits: u7 = 0;
+            for (segment) |value| {
+                sum += zigzag(value);
+                if (value != 0)
+                    raw_bits = @max(raw_bits, signedBits(value));
+            }
+
+            var parameter: u6 = 0;
+            var rice_bits: u64 = std.math.maxInt(u64);
+            for (0..31) |k| {
+                const bits: u64 = count * (k + 1) + (sum >> @intCast(k));
+                if (bits < rice_bits) {
+                    rice_bits = bits;
+                    parameter = @intCast(k);
+                }
+            }
+
+            const escape_bits: u64 = 5 + count * @as(u64, raw_bits);
+            if (raw_bits <= 31 and escape_bits < rice_bits) {
+                plan.partitions[part] = .{ .escape = true, .parameter = @intCast(raw_bits) };
+                plan.bits += escape_bits;
+            } else {
+                plan.partitions[part] = .{ .escape = false, .parameter = parameter };
+                plan.bits += rice_bits;
+                if (parameter > 14)
+                    plan.wide = true;
+            }
+        }
+
+        plan.bits += partitions * @as(u64, if (plan.wide) 5 else 4);
+        if (plan.bits < best.bits)
+            best = plan;
+
+        if (order == max_order)
+            break;
+    }
+
+    return best;
+}
+
+fn writeSubframe(output: *BitWriter, signal: []const i64, bits: u7, subframe: Subframe, buffer: []i64) std.mem.Allocator.Error!void {
+    switch (subframe.predictor) {
+        .constant => {
+            try output.writeBits(0b0000000_0, 8);
+            try output.writeSigned(signal[0], bits);
+        },
+        .verbatim => {
+            try output.writeBits(0b0000001_0, 8);
+            for (signal) |sample| {
+                try output.writeSigned(sample, bits);
+            }
+        },
+        .fixed => |order| {
+            try output.writeBits((0b001000 | order) << 1, 8);
+            for (signal[0..order]) |sample| {
+                try output.writeSigned(sample, bits);
+            }
+            try writeResidual(output, computeResidual(signal, subframe.predictor, buffer), order, signal.len, subframe.plan);
+        },
+        .lpc => |lpc| {
+            try output.writeBits((0b100000 | (lpc.order - 1)) << 1, 8);
+            for (signal[0..lpc.order]) |sample| {
+                try output.writeSigned(sample, bits);
+            }
+            try output.writeBits(lpc.precision - 1, 4);
+            try output.writeSigned(lpc.shift, 5);
+            for (lpc.coefficients[0..lpc.order]) |coefficient| {
+                try output.writeSigned(coefficient, lpc.precision);
+            }
+            try writeResidual(output, computeResidual(signal, subframe.predictor, buffer), lpc.order, signal.len, subframe.plan);
+        },
+    }
+}
+
+fn writeResidual(output: *BitWriter, residual: []const i64, predictor_order: usize, n: usize, plan: RicePlan) std.mem.Allocator.Error!void {
+    const parameter_bits: u7 = if (plan.wide) 5 else 4;
+    const escape_code: u64 = if (plan.wide) 0b11111 else 0b1111;
+
+    try output.writeBits(@intFromBool(plan.wide), 2);
+    try output.writeBits(plan.order, 4);
+
+    const size: usize = n >> plan.order;
+    var start: usize = 0;
+    for (plan.partitions[0 .. @as(usize, 1) << plan.order], 0..) |partition, part| {
+        const count: usize = if (part == 0) size - predictor_order else size;
+        const segment: []const i64 = residual[start..][0..count];
+        start += count;
+
+        if (partition.escape) {
+            try output.writeBits(escape_code, parameter_bits);
+            try output.writeBits(partition.parameter, 5);
+            for (segment) |value| {
+                try output.writeSigned(value, partition.parameter);
+            }
+        } else {
+            const k: u6 = partition.parameter;
+            try output.writeBits(k, parameter_bits);
+            for (segment) |value| {
+                const folded: u64 = zigzag(value);
+                try output.writeUnary(folded >> k);
+                try output.writeBits(folded & ((@as(u64, 1) << k) - 1), k);
+            }
+        }
+    }
+}
+
+fn decodeSubframe(reader: *BitReader, bits: u7, output: []i64) !void {
+    if (try reader.readBits(1) != 0)
+        return error.InvalidFlacFile;
+
+    const kind: u64 = try reader.readBits(6);
+
+    var wasted_bits: u64 = 0;
+    if (try reader.readBits(1) == 1)
+        wasted_bits = try reader.readUnary() + 1;
+    if (wasted_bits >= bits)
+        return error.InvalidFlacFile;
+    const sample_bits: u7 = bits - @as(u7, @intCast(wasted_bits));
+
+    switch (kind) {
+        0 => @memset(output, try reader.readSigned(sample_bits)),
+        1 => for (output) |*sample| {
+            sample.* = try reader.readSigned(sample_bits);
+        },
+        8...12 => {
+            const order: usize = @intCast(kind - 8);
+            if (order > output.len)
+                return error.InvalidFlacFile;
+
+            for (output[0..order]) |*sample| {
+                sample.* = try reader.readSigned(sample_bits);
+            }
+            try readResidual(reader, order, output);
+
+            for (order..output.len) |i| {
+                output[i] +%= fixedPrediction(output, order, i);
+            }
+        },
+        32...63 => {
+            var lpc: Lpc = .{ .coefficients = undefined, .order = @intCast(kind - 31), .precision = undefined, .shift = undefined };
+            if (lpc.order > output.len)
+                return error.InvalidFlacFile;
+
+            for (output[0..lpc.order]) |*sample| {
+                sample.* = try reader.readSigned(sample_bits);
+            }
+
+            const precision: u64 = try reader.readBits(4) + 1;
+            const shift: i64 = try reader.readSigned(5);
+            if (precision == 16 or shift < 0)
+                return error.InvalidFlacFile;
+            lpc.precision = @intCast(precision);
+            lpc.shift = @intCast(shift);
+
+            var coefficients: [32]i64 = undefined;
+            for (coefficients[0..lpc.order]) |*coefficient| {
+                coefficient.* = try reader.readSigned(lpc.precision);
+            }
+            try readResidual(reader, lpc.order, output);
+
+            for (lpc.order..output.len) |i| {
+                var sum: i64 = 0;
+                for (coefficients[0..lpc.order], 1..) |coefficient, j| {
+                    sum +%= coefficient *% output[i - j];
+                }
+                output[i] +%= sum >> lpc.shift;
+            }
+        },
+        else => return error.InvalidFlacFile,
+    }
+
+    if (wasted_bits > 0) {
+        for (output) |*sample| {
+            sample.* <<= @intCast(wasted_bits);
+        }
+    }
+}
+
+fn readResidual(reader: *BitReader, predictor_order: usize, output: []i64) !void {
+    const method: u64 = try reader.readBits(2);
+    if (method > 1)
+        return error.InvalidFlacFile;
+
+    const parameter_bits: u7 = if (method == 1) 5 else 4;
+    const escape_code: u64 = if (method == 1) 0b11111 else 0b1111;
+
+    const order: u6 = @intCast(try reader.readBits(4));
+    const partitions: usize = @as(usize, 1) << order;
+    const size: usize = output.len >> order;
+    if (output.len % partitions != 0 or size < predictor_order)
+        return error.InvalidFlacFile;
+
+    var i: usize = predictor_order;
+    for (0..partitions) |part| {
+        const count: usize = if (part == 0) size - predictor_order else size;
+        const parameter: u64 = try reader.readBits(parameter_bits);
+
+        if (parameter == escape_code) {
+            const raw_bits: u7 = @intCast(try reader.readBits(5));
+            for (output[i..][0..count]) |*value| {
+                value.* = try reader.readSigned(raw_bits);
+            }
+        } else {
+            const k: u6 = @intCast(parameter);
+            for (output[i..][0..count]) |*value| {
+                const folded: u64 = (try reader.readUnary() << k) | try reader.readBits(k);
+                value.* = @as(i64, @intCast(folded >> 1)) ^ -@as(i64, @intCast(folded & 1));
+            }
+        }
+        i += count;
+    }
+}
+
+fn blockSizeCode(block_size: usize) u4 {
+    return switch (block_size) {
+        192 => 1,
+        576 => 2,
+        1152 => 3,
+        2304 => 4,
+        4608 => 5,
+        256 => 8,
+        512 => 9,
+        1024 => 10,
+        2048 => 11,
+        4096 => 12,
+        8192 => 13,
+        16384 => 14,
+        32768 => 15,
+        // The block size minus 1 follows the header, in 8 or 16 bits
+        else => if (block_size <= 256) 6 else 7,
+    };
+}
+
+fn sampleRateCode(sample_rate: u32) u4 {
+    return switch (sample_rate) {
+        88200 => 1,
+        176400 => 2,
+        192000 => 3,
+        8000 => 4,
+        16000 => 5,
+        22050 => 6,
+        24000 => 7,
+        32000 => 8,
+        44100 => 9,
+        48000 => 10,
+        96000 => 11,
+        // Read from STREAMINFO
+        else => 0,
+    };
+}
+
+fn sampleSizeCode(bits: u7) u3 {
+    return switch (bits) {
+        8 => 1,
+        12 => 2,
+        16 => 4,
+        20 => 5,
+        24 => 6,
+        32 => 7,
+        // Read from STREAMINFO
+        else => 0,
+    };
+}
+
+/// Writes a frame number in the UTF-8 like coding of FLAC frame headers.
+fn writeUtf8(output: *BitWriter, value: u64) std.mem.Allocator.Error!void {
+    if (value < 0x80)
+        return output.writeBits(value, 8);
+
+    const limits = [_]u64{ 0x800, 0x10000, 0x200000, 0x4000000, 0x80000000, 1 << 36 };
+    var length: u6 = 2;
+    for (limits) |limit| {
+        if (value < limit)
+            break;
+        length += 1;
+    }
+
+    const prefix: u64 = (@as(u64, 0xFF) << @intCast(8 - length)) & 0xFF;
+    try output.writeBits(prefix | (value >> (6 * (length - 1))), 8);
+
+    var i: u6 = length - 1;
+    while (i > 0) {
+        i -= 1;
+        try output.writeBits(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+/// Writes bits from the most significant one.
+const BitWriter = struct {
+    bytes: std.array_list.Aligned(u8, null) = .empty,
+    allocator: std.mem.Allocator,
+    pending: u8 = 0,
+    pending_bits: u4 = 0,
+
+    fn writeBits(self: *BitWriter, value: u64, count: u7) std.mem.Allocator.Error!void {
+        var remaining: u7 = count;
+        while (remaining > 0) {
+            const take: u7 = @min(remaining, 8 - @as(u7, self.pending_bits));
+            remaining -= take;
+
+            const chunk: u64 = (value >> @intCast(remaining)) & ((@as(u64, 1) << @intCast(take)) - 1);
+            self.pending = @intCast((@as(u64, self.pending) << @intCast(take)) | chunk);
+            self.pending_bits += @intCast(take);
+
+            if (self.pending_bits == 8) {
+                try self.bytes.append(self.allocator, self.pending);
+                self.pending = 0;
+                self.pending_bits = 0;
+            }
+        }
+    }
+
+    fn writeSigned(self: *BitWriter, value: i64, count: u7) std.mem.Allocator.Error!void {
+        const mask: u64 = (@as(u64, 1) << @intCast(count)) - 1;
+        try self.writeBits(@as(u64, @bitCast(value)) & mask, count);
+    }
+
+    fn writeUnary(self: *BitWriter, value: u64) std.mem.Allocator.Error!void {
+        var zeros: u64 = value;
+        while (zeros > 32) : (zeros -= 32) {
+            try self.writeBits(0, 32);
+        }
+        try self.writeBits(1, @intCast(zeros + 1));
+    }
+
+    fn alignToByte(self: *BitWriter) std.mem.Allocator.Error!void {
+        if (self.pending_bits != 0)
+            try self.writeBits(0, 8 - @as(u7, self.pending_bits));
+    }
+};
+
+/// Reads bits from the most significant one.
+const BitReader = struct {
+    bytes: []const u8,
+    position: usize = 0,
+
+    fn readBits(self: *BitReader, count: u7) error{InvalidFlacFile}!u64 {
+        if (self.bytes.len * 8 < self.position + count)
+            return error.InvalidFlacFile;
+
+        var result: u64 = 0;
+        var remaining: u7 = count;
+        while (remaining > 0) {
+            const available: u7 = 8 - @as(u7, @intCast(self.position % 8));
+            const take: u7 = @min(remaining, available);
+            const byte: u8 = self.bytes[self.position / 8];
+
+            const chunk: u64 = (byte >> @intCast(available - take)) & ((@as(u64, 1) << @intCast(take)) - 1);
+            result = (result << @intCast(take)) | chunk;
+
+            self.position += take;
+            remaining -= take;
+        }
+
+        return result;
+    }
+
+    fn readSigned(self: *BitReader, count: u7) error{InvalidFlacFile}!i64 {
+        if (count == 0)
+            return 0;
+
+        const shift: u6 = @intCast(64 - @as(u8, count));
+        const value: u64 = try self.readBits(count);
+        return @as(i64, @bitCast(value << shift)) >> shift;
+    }
+
+    fn readUnary(self: *BitReader) error{InvalidFlacFile}!u64 {
+        var zeros: u64 = 0;
+        while (try self.readBits(1) == 0) {
+            zeros += 1;
+        }
+        return zeros;
+    }
+
+    fn alignToByte(self: *BitReader) void {
+        self.position = (self.position + 7) / 8 * 8;
+    }
+};
+
+test "zigzag and signedBits" {
+    try testing.expectEqual(zigzag(0), 0);
+    try testing.expectEqual(zigzag(-1), 1);
+    try testing.expectEqual(zigzag(1), 2);
+    try testing.expectEqual(zigzag(-2), 3);
+
+    try testing.expectEqual(signedBits(0), 1);
+    try testing.expectEqual(signedBits(1), 2);
+    try testing.expectEqual(signedBits(-1), 1);
+    try testing.expectEqual(signedBits(-2), 2);
+    try testing.expectEqual(signedBits(127), 8);
+    try testing.expectEqual(signedBits(-128), 8);
+}
+
+test "frame numbers in UTF-8 coding" {
+    const allocator = testing.allocator;
+    const cases = [_]struct { u64, []const u8 }{
+        .{ 0x00, &.{0x00} },
+        .{ 0x7F, &.{0x7F} },
+        .{ 0x80, &.{ 0xC2, 0x80 } },
+        .{ 0x800, &.{ 0xE0, 0xA0, 0x80 } },
+        .{ 0x10FFFF, &.{ 0xF4, 0x8F, 0xBF, 0xBF } },
+    };
+
+    for (cases) |case| {
+        var output: BitWriter = .{ .allocator = allocator };
+        defer output.bytes.deinit(allocator);
+
+        try writeUtf8(&output, case[0]);
+        try testing.expectEqualSlices(u8, case[1], output.bytes.items);
+    }
+}
+
+test "bit writer & bit reader" {
+    const allocator = testing.allocator;
+    var output: BitWriter = .{ .allocator = allocator };
+    defer output.bytes.deinit(allocator);
+
+    try output.writeBits(0b101, 3);
+    try output.writeSigned(-3, 5);
+    try output.writeUnary(40);
+    try output.writeBits(0xABCDEF, 24);
+    try output.alignToByte();
+
+    var reader: BitReader = .{ .bytes = output.bytes.items };
+    try testing.expectEqual(try reader.readBits(3), 0b101);
+    try testing.expectEqual(try reader.readSigned(5), -3);
+    try testing.expectEqual(try reader.readUnary(), 40);
+    try testing.expectEqual(try reader.readBits(24), 0xABCDEF);
+    reader.alignToByte();
+    try testing.expectEqual(reader.position, output.bytes.items.len * 8);
+    try testing.expectError(error.InvalidFlacFile, reader.readBits(1));
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
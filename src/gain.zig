@@ -0,0 +1,199 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+const Parameter = @import("./root.zig").Parameter;
+
+/// Gain type function: Creates a Gain filter type for the specified sample type.
+///
+/// Gain multiplies every sample of a wave by a constant or time-varying factor,
+/// given either as a linear ratio or in decibels.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Gain = lightmix.Gain;
+///
+/// // Halve the volume
+/// try wave.filter_with(Gain(f64).Options, Gain(f64).filter, .{
+///     .gain = .{ .constant = -6.0 },
+///     .unit = .decibel,
+/// });
+///
+/// // Tremolo driven by a control wave
+/// try wave.filter_with(Gain(f64).Options, Gain(f64).filter, .{
+///     .gain = .{ .control = lfo_wave },
+/// });
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// How a gain value is interpreted.
+        pub const Unit = enum {
+            /// A ratio: 1.0 keeps the level, 0.5 halves it
+            linear,
+            /// Decibels: 0.0 keeps the level, -6.02 halves it
+            decibel,
+        };
+
+        /// A constant gain value together with its unit.
+        pub const Level = union(Unit) {
+            linear: T,
+            decibel: T,
+
+            /// Converts the level to a linear ratio.
+            pub fn ratio(self: Level) T {
+                return switch (self) {
+                    .linear => |value| toLinear(value, .linear),
+                    .decibel => |value| toLinear(value, .decibel),
+                };
+            }
+        };
+
+        /// Options for applying a gain.
+        pub const Options = struct {
+            /// Gain for every frame. Control waves must have as many frames as the filtered wave.
+            gain: Parameter(T),
+            /// Unit of the gain values
+            unit: Unit = .linear,
+        };
+
+        /// Converts a gain value to a linear ratio.
+        pub fn toLinear(value: T, unit: Unit) T {
+            return switch (unit) {
+                .linear => value,
+                .decibel => @exp(value / 20.0 * std.math.ln10),
+            };
+        }
+
+        /// A filter function which multiplies every frame of a wave by a gain.
+        ///
+        /// Use it through `Wave(T).filter_with`.
+        ///
+        /// ## Errors
+        /// - `ControlLengthMismatch`: The control wave doesn't have as many frames as the filtered wave
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn filter(
+            comptime _: type,
+            original_wave: Wave(T),
+            options: Options,
+        ) (Parameter(T).ParameterErrors || std.mem.Allocator.Error)!Wave(T) {
+            const channels: usize = original_wave.channels;
+            const frames: usize = if (channels == 0) 0 else original_wave.samples.len / channels;
+
+            try options.gain.validate(frames);
+
+            const samples: []T = try original_wave.allocator.alloc(T, original_wave.samples.len);
+
+            for (0..frames) |frame| {
+                const ratio: T = toLinear(options.gain.at(frame), options.unit);
+
+                for (0..channels) |ch| {
+                    const i: usize = frame * channels + ch;
+                    samples[i] = original_wave.samples[i] * ratio;
+                }
+            }
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = original_wave.allocator,
+
+                .sample_rate = original_wave.sample_rate,
+                .channels = original_wave.channels,
+            };
+        }
+
+        test "constant linear gain" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, -1.0, 0.5, -0.5 };
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            try wave.filter_with(Options, Self.filter, .{ .gain = .{ .constant = 0.5 } });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.channels, 2);
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ 0.5, -0.5, 0.25, -0.25 });
+        }
+
+        test "constant decibel gain" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, -1.0 };
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            try wave.filter_with(Options, Self.filter, .{
+                .gain = .{ .constant = -6.020599913279624 },
+                .unit = .decibel,
+            });
+            defer wave.deinit();
+
+            try testing.expectApproxEqAbs(wave.samples[0], 0.5, 0.000001);
+            try testing.expectApproxEqAbs(wave.samples[1], -0.5, 0.000001);
+        }
+
+        test "Level" {
+            const unity: Level = .{ .linear = 1.0 };
+            const half: Level = .{ .decibel = -6.020599913279624 };
+
+            try testing.expectEqual(unity.ratio(), 1.0);
+            try testing.expectApproxEqAbs(half.ratio(), 0.5, 0.000001);
+        }
+
+        test "gain following a control wave" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 1.0, 1.0, 1.0, 1.0, 1.0 };
+            const gains: []const T = &[_]T{ 0.0, 0.5, 1.0 };
+
+            const control = try Wave(T).init(gains, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer control.deinit();
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            try wave.filter_with(Options, Self.filter, .{ .gain = .{ .control = control } });
+            defer wave.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ 0.0, 0.0, 0.5, 0.5, 1.0, 1.0 });
+        }
+
+        test "gain rejects a control wave with a different length" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 1.0 };
+            const gains: []const T = &[_]T{ 0.0, 0.5, 1.0 };
+
+            const control = try Wave(T).init(gains, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer control.deinit();
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            try testing.expectError(error.ControlLengthMismatch, wave.filter_with(Options, Self.filter, .{
+                .gain = .{ .control = control },
+            }));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -0,0 +1,695 @@
+const std = @import("std");
+const testing = std.testing;
+const Timeline = @import("./root.zig").Timeline;
+
+/// Midi type function: Creates a note sequence type for the specified sample type.
+///
+/// A Midi holds notes placed in ticks, and a timeline which carries the tempo map.
+/// It is read from and written into Standard MIDI Files of type 0 (one track) or 1 (several tracks
+/// played together), and `Composer(T).appendMidi` renders it with an instrument function.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// var reader = std.Io.Reader.fixed(@embedFile("./melody.mid"));
+/// const midi = try Midi(f64).read(allocator, &reader);
+/// defer midi.deinit();
+///
+/// for (midi.notes) |note| {
+///     std.debug.print("{d} at {d}s\n", .{ note.key, midi.secondsAt(note.tick) });
+/// }
+///
+/// const melody = try Midi(f64).init(&.{
+///     .{ .tick = 0, .duration = 480, .key = 60 },
+///     .{ .tick = 480, .duration = 480, .key = 64 },
+/// }, allocator, .{ .timeline = .{ .tempo = 90.0 } });
+/// defer melody.deinit();
+/// try melody.write(&writer.interface);
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        /// Notes sorted by start tick
+        notes: []const Note,
+        /// Tempo map of the notes. Its beats are quarter notes, divided into `ticks_per_beat` ticks.
+        timeline: Timeline(T),
+        allocator: std.mem.Allocator,
+
+        const Self = @This();
+
+        /// A note, from its note on event to its note off event.
+        pub const Note = struct {
+            /// Start, in ticks from the beginning
+            tick: usize,
+            /// Length, in ticks
+            duration: usize,
+            /// MIDI note number, where 60 is the middle C
+            key: u7,
+            /// Strength of the note, from 1 to 127
+            velocity: u7 = 100,
+            /// MIDI channel, from 0 to 15
+            channel: u4 = 0,
+            /// Index of the track holding the note
+            track: u16 = 0,
+        };
+
+        /// Errors that can occur when reading MIDI files.
+        pub const MidiErrors = error{
+            /// The data is not a Standard MIDI File, or one of its events is malformed
+            InvalidMidiFile,
+            /// The file is of type 2, whose tracks are independent sequences
+            UnsupportedFormat,
+        };
+
+        /// Options for initializing a Midi instance.
+        pub const InitOptions = struct {
+            /// Tempo map of the notes. Its beats are quarter notes.
+            timeline: Timeline(T) = .{},
+        };
+
+        /// Creates a note sequence from a slice of notes.
+        ///
+        /// ## Parameters
+        /// - `notes`: The notes, in any order. They are copied and sorted by start tick.
+        /// - `allocator`: Memory allocator for the notes and the tempo changes
+        /// - `options`: Initialization options (the tempo map, whose tempo changes are copied)
+        ///
+        /// ## Returns
+        /// A new Midi instance owning copies of the notes and the tempo changes
+        ///
+        /// ## Errors
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn init(notes: []const Note, allocator: std.mem.Allocator, options: InitOptions) std.mem.Allocator.Error!Self {
+            const owned_notes: []Note = try allocator.dupe(Note, notes);
+            errdefer allocator.free(owned_notes);
+            std.mem.sort(Note, owned_notes, {}, noteLessThan);
+
+            var timeline: Timeline(T) = options.timeline;
+            timeline.tempo_changes = try allocator.dupe(Timeline(T).TempoChange, options.timeline.tempo_changes);
+
+            return Self{
+                .notes = owned_notes,
+                .timeline = timeline,
+                .allocator = allocator,
+            };
+        }
+
+        /// Frees the notes and the tempo changes.
+        pub fn deinit(self: Self) void {
+            self.allocator.free(self.notes);
+            self.allocator.free(self.timeline.tempo_changes);
+        }
+
+        /// Returns the number of seconds from the beginning to a tick, following the tempo map.
+        pub fn secondsAt(self: Self, tick: usize) T {
+            return self.timeline.secondsAt(.{ .musical = .{ .bar = 0, .tick = tick } }, 0);
+        }
+
+        /// Reads a Standard MIDI File of type 0 or 1.
+        ///
+        /// Note on events are paired with the first unfinished note of the same key and channel,
+        /// and notes still playing at the end of their track end there.
+        /// Tempo events of every track make up the tempo map, and the first time signature
+        /// gives the bars of the timeline when it is a whole number of quarter notes.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for the notes and the tempo map
+        /// - `reader`: A reader interface providing the raw file bytes
+        ///
+        /// ## Returns
+        /// The notes of every track, owned by the caller, with their tempo map
+        ///
+        /// ## Errors
+        /// - `InvalidMidiFile`: The data is not a Standard MIDI File, or an event is malformed
+        /// - `UnsupportedFormat`: The file is of type 2
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn read(allocator: std.mem.Allocator, reader: anytype) anyerror!Self {
+            const bytes: []u8 = try reader.allocRemaining(allocator, .unlimited);
+            defer allocator.free(bytes);
+
+            var file: ByteReader = .{ .bytes = bytes };
+            if (!std.mem.eql(u8, try file.take(4), "MThd"))
+                return error.InvalidMidiFile;
+
+            const header_length: u32 = try file.int(u32);
+            if (header_length < 6)
+                return error.InvalidMidiFile;
+            const format: u16 = try file.int(u16);
+            _ = try file.int(u16); // Number of tracks
+            const division: u16 = try file.int(u16);
+            _ = try file.take(header_length - 6);
+
+            if (format > 1)
+                return error.UnsupportedFormat;
+
+            var timeline: Timeline(T) = .{};
+            const smpte: bool = division & 0x8000 != 0;
+            if (smpte) {
+                // Ticks are subdivisions of SMPTE frames, so the tempo is a fixed second per beat
+                const frames_per_second: i8 = -%@as(i8, @bitCast(@as(u8, @intCast(division >> 8))));
+                const ticks_per_frame: u32 = division & 0xFF;
+                if (ticks_per_frame == 0)
+                    return error.InvalidMidiFile;
+
+                timeline.tempo = switch (frames_per_second) {
+                    24, 25, 30 => 60.0,
+                    29 => 60.0 * 29.97 / 30.0,
+                    else => return error.InvalidMidiFile,
+                };
+                timeline.ticks_per_beat = @as(u32, if (frames_per_second == 29) 30 else @intCast(frames_per_second)) * ticks_per_frame;
+            } else {
+                if (division == 0)
+                    return error.InvalidMidiFile;
+                timeline.ticks_per_beat = division;
+            }
+
+            var notes: std.array_list.Aligned(Note, null) = .empty;
+            defer notes.deinit(allocator);
+            var tempo_events: std.array_list.Aligned(TempoEvent, null) = .empty;
+            defer tempo_events.deinit(allocator);
+            var time_signature: ?Timeline(T).TimeSignature = null;
+
+            var track_index: u16 = 0;
+            while (file.bytes.len - file.position >= 8) {
+                const id: []const u8 = try file.take(4);
+                const length: u32 = try file.int(u32);
+                const chunk: []const u8 = try file.take(length);
+
+                // Unknown chunks are skipped, as the specification asks
+                if (!std.mem.eql(u8, id, "MTrk"))
+                    continue;
+
+                try readTrack(allocator, chunk, track_index, &notes, &tempo_events, &time_signature);
+                track_index +|= 1;
+            }
+
+            std.mem.sort(Note, notes.items, {}, noteLessThan);
+            std.mem.sort(TempoEvent, tempo_events.items, {}, struct {
+                fn lessThan(_: void, a: TempoEvent, b: TempoEvent) bool {
+                    return a.tick < b.tick;
+                }
+            }.lessThan);
+
+            var tempo_changes: std.array_list.Aligned(Timeline(T).TempoChange, null) = .empty;
+            defer tempo_changes.deinit(allocator);
+            if (!smpte) {
+                for (tempo_events.items) |event| {
+                    const tempo: T = 60_000_000.0 / @as(T, @floatFromInt(event.microseconds_per_beat));
+                    if (event.tick == 0) {
+                        timeline.tempo = tempo;
+                    } else {
+                        try tempo_changes.append(allocator, .{ .at = .{ .bar = 0, .tick = event.tick }, .tempo = tempo });
+                    }
+                }
+            }
+            if (time_signature) |signature|
+                timeline.time_signature = signature;
+
+            timeline.tempo_changes = try tempo_changes.toOwnedSlice(allocator);
+            errdefer allocator.free(timeline.tempo_changes);
+
+            return Self{
+                .notes = try notes.toOwnedSlice(allocator),
+                .timeline = timeline,
+                .allocator = allocator,
+            };
+        }
+
+        fn noteLessThan(_: void, a: Note, b: Note) bool {
+            return a.tick < b.tick;
+        }
+
+        const TempoEvent = struct {
+            tick: usize,
+            microseconds_per_beat: u24,
+        };
+
+        /// A note on event waiting for its note off event.
+        const Pending = struct {
+            tick: usize,
+            key: u7,
+            velocity: u7,
+            channel: u4,
+        };
+
+        fn readTrack(
+            allocator: std.mem.Allocator,
+            chunk: []const u8,
+            track_index: u16,
+            notes: *std.array_list.Aligned(Note, null),
+            tempo_events: *std.array_list.Aligned(TempoEvent, null),
+            time_signature: *?Timeline(T).TimeSignature,
+        ) anyerror!void {
+            var track: ByteReader = .{ .bytes = chunk };
+            var pending: std.array_list.Aligned(Pending, null) = .empty;
+            defer pending.deinit(allocator);
+
+            var tick: usize = 0;
+            var running_status: u8 = 0;
+            while (track.position < track.bytes.len) {
+                tick += try track.varInt();
+                const first: u8 = try track.byte();
+
+                switch (first) {
+                    0xFF => {
+                        const kind: u8 = try track.byte();
+                        const data: []const u8 = try track.take(try track.varInt());
+                        running_status = 0;
+
+                        switch (kind) {
+                            0x2F => break, // End of track
+                            0x51 => {
+                                if (data.len != 3)
+                                    return error.InvalidMidiFile;
+                                const microseconds_per_beat: u24 = std.mem.readInt(u24, data[0..3], .big);
+                                if (microseconds_per_beat == 0)
+                                    return error.InvalidMidiFile;
+                                try tempo_events.append(allocator, .{ .tick = tick, .microseconds_per_beat = microseconds_per_beat });
+                            },
+                            0x58 => {
+                                if (data.len < 2)
+                                    return error.InvalidMidiFile;
+                                // Beats are quarter notes, so 6/8 becomes 3/4, and 7/8 can't be expressed
+                                const quarters: u32 = @as(u32, data[0]) * 4;
+                                const denominator: u32 = if (data[1] < 8) @as(u32, 1) << @intCast(data[1]) else 0;
+                                if (time_signature.* == null and denominator != 0 and quarters % denominator == 0 and quarters > 0)
+                                    time_signature.* = .{ .beats_per_bar = quarters / denominator, .beat_unit = 4 };
+                            },
+                            else => {},
+                        }
+                    },
+                    0xF0, 0xF7 => {
+                        // System exclusive messages
+                        _ = try track.take(try track.varInt());
+                        running_status = 0;
+                    },
+                    else => {
+                        // Channel messages, whose status may be omitted after the first one
+                        var data1: u8 = first;
+                        if (first & 0x80 != 0) {
+                            running_status = first;
+                            data1 = try track.byte();
+                        }
+                        if (running_status == 0 or running_status >= 0xF0 or data1 & 0x80 != 0)
+                            return error.InvalidMidiFile;
+
+                        const kind: u4 = @intCast(running_status >> 4);
+                        const channel: u4 = @intCast(running_status & 0x0F);
+                        var data2: u8 = 0;
+                        if (kind != 0xC and kind != 0xD) {
+                            data2 = try track.byte();
+                            if (data2 & 0x80 != 0)
+                                return error.InvalidMidiFile;
+                        }
+
+                        const key: u7 = @intCast(data1);
+                        if (kind == 0x9 and data2 > 0) {
+                            try pending.append(allocator, .{ .tick = tick, .key = key, .velocity = @intCast(data2), .channel = channel });
+                        } else if (kind == 0x8 or kind == 0x9) {
+                            for (pending.items, 0..) |note, i| {
+                                if (note.key != key or note.channel != channel)
+                                    continue;
+
+                                try notes.append(allocator, noteOf(note, tick, track_index));
+                                _ = pending.orderedRemove(i);
+                                break;
+                            }
+                        }
+                    },
+                }
+            }
+
+            for (pending.items) |note| {
+                try notes.append(allocator, noteOf(note, tick, track_index));
+            }
+        }
+
+        fn noteOf(pending: Pending, end: usize, track_index: u16) Note {
+            return .{
+                .tick = pending.tick,
+                .duration = end - pending.tick,
+                .key = pending.key,
+                .velocity = pending.velocity,
+                .channel = pending.channel,
+                .track = track_index,
+            };
+        }
+
+        /// Writes the sequence as a Standard MIDI File.
+        ///
+        /// When every note is in track 0, the file is of type 0. Otherwise it is of type 1, with a track
+        /// for each index up to the last one used. The first track also holds the time signature and
+        /// the tempo map, whose beats are written as quarter notes, so `read` gives back the same notes.
+        ///
+        /// ## Parameters
+        /// - `self`: The sequence to write
+        /// - `writer`: A writer interface for the file bytes
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: A value can't be written into the file: `ticks_per_beat` is 0 or above 32767,
+        ///   a tempo is not between about 3.6 and 60,000,000 BPM, the time signature is not `n/2^k`
+        ///   with `n` up to 255, a note has a velocity of 0, or two events are too far apart
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the writer
+        pub fn write(self: Self, writer: anytype) anyerror!void {
+            const timeline: Timeline(T) = self.timeline;
+            const signature: Timeline(T).TimeSignature = timeline.time_signature;
+            if (timeline.ticks_per_beat == 0 or timeline.ticks_per_beat > 0x7FFF)
+                return error.InvalidRange;
+            if (signature.beats_per_bar == 0 or signature.beats_per_bar > 0xFF)
+                return error.InvalidRange;
+            if (signature.beat_unit == 0 or !std.math.isPowerOfTwo(signature.beat_unit))
+                return error.InvalidRange;
+
+            var tracks: usize = 1;
+            for (self.notes) |note| {
+                if (note.velocity == 0)
+                    return error.InvalidRange;
+                tracks = @max(tracks, @as(usize, note.track) + 1);
+            }
+            if (tracks > std.math.maxInt(u16))
+                return error.InvalidRange;
+
+            try writer.writeAll("MThd");
+            try writer.writeInt(u32, 6, .big);
+            try writer.writeInt(u16, if (tracks == 1) 0 else 1, .big);
+            try writer.writeInt(u16, @intCast(tracks), .big);
+            try writer.writeInt(u16, @intCast(timeline.ticks_per_beat), .big);
+
+            for (0..tracks) |track_index| {
+                var events: std.array_list.Aligned(Event, null) = .empty;
+                defer events.deinit(self.allocator);
+
+                if (track_index == 0) {
+                    // 24 MIDI clocks per metronome click, and 8 thirty-second notes per quarter note
+                    try events.append(self.allocator, .init(0, 0, &.{ 0xFF, 0x58, 0x04, @intCast(signature.beats_per_bar), @intCast(@ctz(signature.beat_unit)), 24, 8 }));
+                    try events.append(self.allocator, try tempoEvent(0, timeline.tempo));
+                    for (timeline.tempo_changes) |change| {
+                        const beats: usize = change.at.bar * signature.beats_per_bar + change.at.beat;
+                        try events.append(self.allocator, try tempoEvent(beats * timeline.ticks_per_beat + change.at.tick, change.tempo));
+                    }
+                }
+
+                for (self.notes) |note| {
+                    if (note.track != track_index)
+                        continue;
+
+                    const channel: u8 = note.channel;
+                    try events.append(self.allocator, .init(note.tick, 1, &.{ 0x90 | channel, note.key, note.velocity }));
+                    try events.append(self.allocator, .init(note.tick + note.duration, if (note.duration == 0) 2 else 0, &.{ 0x80 | channel, note.key, 0x40 }));
+                }
+
+                std.mem.sort(Event, events.items, {}, Event.lessThan);
+
+                var chunk = std.Io.Writer.Allocating.init(self.allocator);
+                defer chunk.deinit();
+
+                var tick: usize = 0;
+                for (events.items) |event| {
+                    try writeVarInt(&chunk.writer, event.tick - tick);
+                    try chunk.writer.writeAll(event.data[0..event.length]);
+                    tick = event.tick;
+                }
+                try chunk.writer.writeAll("\x00\xFF\x2F\x00"); // End of track
+
+                try writer.writeAll("MTrk");
+                try writer.writeInt(u32, @intCast(chunk.written().len), .big);
+                try writer.writeAll(chunk.written());
+            }
+        }
+
+        /// An event to write, with its status byte.
+        const Event = struct {
+            tick: usize,
+            /// Events of a tick are written from the lowest order: note offs and meta events,
+            /// then note ons, then note offs of notes which have no length
+            order: u2,
+            data: [7]u8 = undefined,
+            length: usize,
+
+            fn init(tick: usize, order: u2, data: []const u8) Event {
+                var event: Event = .{ .tick = tick, .order = order, .length = data.len };
+                @memcpy(event.data[0..data.len], data);
+                return event;
+            }
+
+            fn lessThan(_: void, a: Event, b: Event) bool {
+                if (a.tick != b.tick)
+                    return a.tick < b.tick;
+                return a.order < b.order;
+            }
+        };
+
+        fn tempoEvent(tick: usize, tempo: T) error{InvalidRange}!Event {
+            const microseconds_per_beat: T = @round(60_000_000.0 / tempo);
+            if (!(microseconds_per_beat >= 1.0 and microseconds_per_beat <= 0xFFFFFF))
+                return error.InvalidRange;
+
+            const value: u24 = @intFromFloat(microseconds_per_beat);
+            return .init(tick, 0, &.{ 0xFF, 0x51, 0x03, @truncate(value >> 16), @truncate(value >> 8), @truncate(value) });
+        }
+
+        test "read a type 0 file" {
+            const allocator = testing.allocator;
+            const bytes = "MThd\x00\x00\x00\x06\x00\x00\x00\x01\x00\x60" ++
+                "MTrk\x00\x00\x00\x19" ++
+                "\x00\xFF\x51\x03\x07\xA1\x20" ++ // 120 BPM
+                "\x00\x90\x3C\x64" ++ // C4 on
+                "\x3C\x3C\x00" ++ // C4 off, with running status and a zero velocity
+                "\x00\x40\x50" ++ // E4 on
+                "\x60\x80\x40\x00" ++ // E4 off
+                "\x00\xFF\x2F\x00";
+
+            var reader = std.Io.Reader.fixed(bytes);
+            const midi = try Self.read(allocator, &reader);
+            defer midi.deinit();
+
+            try testing.expectEqual(midi.timeline.ticks_per_beat, 96);
+            try testing.expectApproxEqAbs(midi.timeline.tempo, 120.0, 0.000001);
+            try testing.expectEqualSlices(Note, &.{
+                .{ .tick = 0, .duration = 60, .key = 60, .velocity = 100 },
+                .{ .tick = 60, .duration = 96, .key = 64, .velocity = 80 },
+            }, midi.notes);
+            try testing.expectApproxEqAbs(midi.secondsAt(96), 0.5, 0.000001);
+        }
+
+        test "read a type 1 file with a tempo map" {
+            const allocator = testing.allocator;
+            const bytes = "MThd\x00\x00\x00\x06\x00\x01\x00\x02\x00\x60" ++
+                "MTrk\x00\x00\x00\x16" ++
+                "\x00\xFF\x58\x04\x06\x03\x18\x08" ++ // 6/8
+                "\x00\xFF\x51\x03\x07\xA1\x20" ++ // 120 BPM
+                "\x60\xFF\x51\x03\x0F\x42\x40" ++ // 60 BPM from the second beat
+                "MTrk\x00\x00\x00\x12" ++
+                "\x00\x92\x45\x40" ++ // A4 on, on channel 2
+                "\x00\xF0\x02\x7E\xF7" ++ // A system exclusive message
+                "\x81\x40\x82\x45\x00" ++ // A4 off, after 192 ticks
+                "\x00\x92\x47\x40"; // B4 on, until the end of the track
+
+            var reader = std.Io.Reader.fixed(bytes);
+            const midi = try Self.read(allocator, &reader);
+            defer midi.deinit();
+
+            try testing.expectEqualSlices(Note, &.{
+                .{ .tick = 0, .duration = 192, .key = 69, .velocity = 64, .channel = 2, .track = 1 },
+                .{ .tick = 192, .duration = 0, .key = 71, .velocity = 64, .channel = 2, .track = 1 },
+            }, midi.notes);
+
+            // A beat at 120 BPM, then a beat at 60 BPM
+            try testing.expectApproxEqAbs(midi.secondsAt(192), 1.5, 0.000001);
+            try testing.expectEqual(midi.timeline.time_signature.beats_per_bar, 3);
+            try testing.expectEqual(midi.timeline.time_signature.beat_unit, 4);
+        }
+
+        test "SMPTE division" {
+            const allocator = testing.allocator;
+            // 25 frames per second, 40 ticks per frame
+            const bytes = "MThd\x00\x00\x00\x06\x00\x00\x00\x01\xE7\x28" ++
+                "MTrk\x00\x00\x00\x0B" ++
+                "\x00\xFF\x51\x03\x0F\x42\x40" ++ // Ignored
+                "\x00\xFF\x2F\x00";
+
+            var reader = std.Io.Reader.fixed(bytes);
+            const midi = try Self.read(allocator, &reader);
+            defer midi.deinit();
+
+            try testing.expectApproxEqAbs(midi.secondsAt(500), 0.5, 0.000001);
+        }
+
+        test "invalid files" {
+            const allocator = testing.allocator;
+
+            var wav = std.Io.Reader.fixed("RIFF\x00\x00\x00\x00WAVE");
+            try testing.expectError(error.InvalidMidiFile, Self.read(allocator, &wav));
+
+            var type2 = std.Io.Reader.fixed("MThd\x00\x00\x00\x06\x00\x02\x00\x01\x00\x60");
+            try testing.expectError(error.UnsupportedFormat, Self.read(allocator, &type2));
+
+            var truncated = std.Io.Reader.fixed("MThd\x00\x00\x00\x06\x00\x00\x00\x01\x00\x60MTrk\x00\x00\x00\x08\x00\x90\x3C");
+            try testing.expectError(error.InvalidMidiFile, Self.read(allocator, &truncated));
+
+            // A data byte without any status byte before it
+            var no_status = std.Io.Reader.fixed("MThd\x00\x00\x00\x06\x00\x00\x00\x01\x00\x60MTrk\x00\x00\x00\x03\x00\x3C\x64");
+            try testing.expectError(error.InvalidMidiFile, Self.read(allocator, &no_status));
+        }
+
+        test "write a type 0 file" {
+            const allocator = testing.allocator;
+            const midi = try Self.init(&.{
+                .{ .tick = 96, .duration = 0, .key = 62, .velocity = 90, .channel = 1 },
+                .{ .tick = 0, .duration = 96, .key = 60 },
+            }, allocator, .{ .timeline = .{ .ticks_per_beat = 96 } });
+            defer midi.deinit();
+
+            var writer = std.Io.Writer.Allocating.init(allocator);
+            defer writer.deinit();
+            try midi.write(&writer.writer);
+
+            try testing.expectEqualSlices(u8, "MThd\x00\x00\x00\x06\x00\x00\x00\x01\x00\x60" ++
+                "MTrk\x00\x00\x00\x23" ++
+                "\x00\xFF\x58\x04\x04\x02\x18\x08" ++ // 4/4
+                "\x00\xFF\x51\x03\x07\xA1\x20" ++ // 120 BPM
+                "\x00\x90\x3C\x64" ++ // C4 on
+                "\x60\x80\x3C\x40" ++ // C4 off
+                "\x00\x91\x3E\x5A" ++ // D4 on, on channel 1
+                "\x00\x81\x3E\x40" ++ // D4 off, at the same tick
+                "\x00\xFF\x2F\x00", writer.written());
+
+            var reader = std.Io.Reader.fixed(writer.written());
+            const read_back = try Self.read(allocator, &reader);
+            defer read_back.deinit();
+
+            try testing.expectEqualSlices(Note, midi.notes, read_back.notes);
+        }
+
+        test "write a type 1 file with a tempo map" {
+            const allocator = testing.allocator;
+            const midi = try Self.init(&.{
+                .{ .tick = 0, .duration = 288, .key = 48, .velocity = 70 },
+                .{ .tick = 0, .duration = 96, .key = 72, .velocity = 110, .channel = 9, .track = 2 },
+                .{ .tick = 96, .duration = 192, .key = 72, .velocity = 110, .channel = 9, .track = 2 },
+                .{ .tick = 288, .duration = 96, .key = 50, .velocity = 70 },
+            }, allocator, .{ .timeline = .{
+                .tempo = 100.0,
+                .time_signature = .{ .beats_per_bar = 3, .beat_unit = 4 },
+                .ticks_per_beat = 96,
+                .tempo_changes = &.{.{ .at = .{ .bar = 1 }, .tempo = 150.0 }},
+            } });
+            defer midi.deinit();
+
+            var writer = std.Io.Writer.Allocating.init(allocator);
+            defer writer.deinit();
+            try midi.write(&writer.writer);
+
+            // Type 1 with 3 tracks, the second of which is empty
+            try testing.expectEqualSlices(u8, "MThd\x00\x00\x00\x06\x00\x01\x00\x03\x00\x60", writer.written()[0..14]);
+            try testing.expect(std.mem.indexOf(u8, writer.written(), "MTrk\x00\x00\x00\x04\x00\xFF\x2F\x00") != null);
+
+            var reader = std.Io.Reader.fixed(writer.written());
+            const read_back = try Self.read(allocator, &reader);
+            defer read_back.deinit();
+
+            try testing.expectEqualSlices(Note, midi.notes, read_back.notes);
+            try testing.expectEqual(read_back.timeline.ticks_per_beat, 96);
+            try testing.expectEqual(read_back.timeline.time_signature.beats_per_bar, 3);
+            try testing.expectApproxEqAbs(read_back.timeline.tempo, 100.0, 0.0001);
+            try testing.expectEqual(read_back.timeline.tempo_changes.len, 1);
+            try testing.expectApproxEqAbs(read_back.timeline.tempo_changes[0].tempo, 150.0, 0.0001);
+            for ([_]usize{ 0, 96, 288, 384 }) |tick| {
+                try testing.expectApproxEqAbs(midi.secondsAt(tick), read_back.secondsAt(tick), 0.000001);
+            }
+        }
+
+        test "values which can't be written" {
+            const allocator = testing.allocator;
+            var writer = std.Io.Writer.Allocating.init(allocator);
+            defer writer.deinit();
+
+            const invalid = [_]Self{
+                .{ .notes = &.{}, .timeline = .{ .ticks_per_beat = 0 }, .allocator = allocator },
+                .{ .notes = &.{}, .timeline = .{ .tempo = 0.0 }, .allocator = allocator },
+                .{ .notes = &.{}, .timeline = .{ .time_signature = .{ .beats_per_bar = 5, .beat_unit = 6 } }, .allocator = allocator },
+                .{ .notes = &.{.{ .tick = 0, .duration = 1, .key = 60, .velocity = 0 }}, .timeline = .{}, .allocator = allocator },
+                .{ .notes = &.{.{ .tick = 0x10000000, .duration = 1, .key = 60 }}, .timeline = .{}, .allocator = allocator },
+            };
+            for (invalid) |midi| {
+                try testing.expectError(error.InvalidRange, midi.write(&writer.writer));
+            }
+        }
+    };
+}
+
+/// Reads big-endian integers and variable-length quantities from a chunk.
+const ByteReader = struct {
+    bytes: []const u8,
+    position: usize = 0,
+
+    fn take(self: *ByteReader, length: usize) error{InvalidMidiFile}![]const u8 {
+        if (self.bytes.len - self.position < length)
+            return error.InvalidMidiFile;
+
+        defer self.position += length;
+        return self.bytes[self.position..][0..length];
+    }
+
+    fn byte(self: *ByteReader) error{InvalidMidiFile}!u8 {
+        return (try self.take(1))[0];
+    }
+
+    fn int(self: *ByteReader, comptime I: type) error{InvalidMidiFile}!I {
+        return std.mem.readInt(I, (try self.take(@sizeOf(I)))[0..@sizeOf(I)], .big);
+    }
+
+    /// Reads a quantity of up to 4 bytes, 7 bits each, from the most significant ones.
+    fn varInt(self: *ByteReader) error{InvalidMidiFile}!u32 {
+        var result: u32 = 0;
+        for (0..4) |_| {
+            const value: u8 = try self.byte();
+            result = (result << 7) | (value & 0x7F);
+            if (value & 0x80 == 0)
+                return result;
+        }
+
+        return error.InvalidMidiFile;
+    }
+};
+
+/// Writes a quantity of up to 4 bytes, 7 bits each, from the most significant ones.
+fn writeVarInt(writer: *std.Io.Writer, value: usize) (std.Io.Writer.Error || error{InvalidRange})!void {
+    if (value > 0x0FFFFFFF)
+        return error.InvalidRange;
+
+    var shift: u5 = 21;
+    while (shift > 0 and value >> shift == 0) : (shift -= 7) {}
+    while (shift > 0) : (shift -= 7) {
+        try writer.writeByte(@as(u8, @intCast((value >> shift) & 0x7F)) | 0x80);
+    }
+    try writer.writeByte(@intCast(value & 0x7F));
+}
+
+test "variable-length quantities" {
+    var reader: ByteReader = .{ .bytes = "\x00\x7F\x81\x00\xC0\x00\xFF\xFF\xFF\x7F\x80\x80\x80\x80" };
+    try testing.expectEqual(try reader.varInt(), 0);
+    try testing.expectEqual(try reader.varInt(), 127);
+    try testing.expectEqual(try reader.varInt(), 128);
+    try testing.expectEqual(try reader.varInt(), 8192);
+    try testing.expectEqual(try reader.varInt(), 0x0FFFFFFF);
+    try testing.expectError(error.InvalidMidiFile, reader.varInt());
+
+    var writer = std.Io.Writer.Allocating.init(testing.allocator);
+    defer writer.deinit();
+    for ([_]usize{ 0, 127, 128, 8192, 0x0FFFFFFF }) |value| {
+        try writeVarInt(&writer.writer, value);
+    }
+    try testing.expectEqualSlices(u8, "\x00\x7F\x81\x00\xC0\x00\xFF\xFF\xFF\x7F", writer.written());
+    try testing.expectError(error.InvalidRange, writeVarInt(&writer.writer, 0x10000000));
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -0,0 +1,378 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+const Error = @import("./error.zig").Error;
+const Oscillator = @import("./root.zig").Oscillator;
+
+/// Noise type function: Creates a Noise type for the specified sample type.
+///
+/// Noise generates white, pink, brown, blue, violet and velvet noise as `Wave(T)` instances.
+/// Every generator takes an explicit seed, so the same options always produce the same samples.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Noise = lightmix.Noise;
+/// const wave = try Noise(f64).pink(allocator, .{
+///     .amplitude = 0.5,
+///     .duration = 1.0,
+///     .sample_rate = 44100,
+///     .seed = 0,
+/// });
+/// defer wave.deinit();
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// Spectral colors supported by the noise generator.
+        pub const Color = enum {
+            /// Equal energy across all frequencies
+            white,
+            /// Equal energy per octave (-3 dB/octave)
+            pink,
+            /// Integrated white noise (-6 dB/octave)
+            brown,
+            /// Differentiated pink noise (+3 dB/octave)
+            blue,
+            /// Differentiated white noise (+6 dB/octave)
+            violet,
+            /// Sparse random impulses of random sign
+            velvet,
+        };
+
+        /// Options for generating noise.
+        pub const Options = struct {
+            /// Peak amplitude of the noise (pink, brown and blue noise stay roughly within it)
+            amplitude: T = 1.0,
+            /// Length of the wave in seconds
+            duration: T,
+            /// Samples per second
+            sample_rate: u32,
+            /// Number of channels. Every channel receives independent noise.
+            channels: u16 = 1,
+            /// Seed for the pseudo-random number generator
+            seed: u64,
+            /// Average number of impulses per second, above 0 and up to `sample_rate`. Only used by `.velvet`.
+            velvet_density: T = 2000.0,
+        };
+
+        /// Generates noise with the given color.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for the sample data
+        /// - `color`: The noise color to generate
+        /// - `options`: Amplitude, duration, format and seed of the noise
+        ///
+        /// ## Returns
+        /// A new Wave containing `round(duration * sample_rate)` frames
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The duration is negative, NaN or too long to allocate, or the velvet
+        ///   density of `.velvet` noise is not above 0 and up to the sample rate
+        /// - Allocator error (errors.OutOfMemory)
+        pub fn generate(
+            allocator: std.mem.Allocator,
+            color: Color,
+            options: Options,
+        ) (Error || std.mem.Allocator.Error)!Wave(T) {
+            const rate: T = @floatFromInt(options.sample_rate);
+            if (color == .velvet and !(options.velvet_density > 0.0 and options.velvet_density <= rate))
+                return error.InvalidRange;
+
+            const frames: usize = try Oscillator(T).frameCount(options.duration, options.sample_rate);
+            const channels: usize = options.channels;
+
+            const samples: []T = try allocator.alloc(T, std.math.mul(usize, frames, channels) catch return error.InvalidRange);
+
+            var prng = std.Random.DefaultPrng.init(options.seed);
+            const rand = prng.random();
+
+            // Channels are generated one after another, so each has its own filter state
+            for (0..channels) |ch| {
+                var state: State = .{};
+
+                if (color == .velvet) {
+                    for (0..frames) |i| {
+                        samples[i * channels + ch] = 0.0;
+                    }
+
+                    const period: T = rate / options.velvet_density;
+                    var m: usize = 0;
+                    while (true) : (m += 1) {
+                        const start: usize = @intFromFloat(@floor(@as(T, @floatFromInt(m)) * period));
+                        const end: usize = @intFromFloat(@floor(@as(T, @floatFromInt(m + 1)) * period));
+                        if (start >= frames)
+                            break;
+                        if (end == start)
+                            continue;
+
+                        const position: usize = start + rand.uintLessThan(usize, end - start);
+                        if (position >= frames)
+                            break;
+
+                        const sign: T = if (rand.boolean()) 1.0 else -1.0;
+                        samples[position * channels + ch] = options.amplitude * sign;
+                    }
+
+                    continue;
+                }
+
+                for (0..frames) |i| {
+                    const white_sample: T = @floatCast(rand.float(f64) * 2.0 - 1.0);
+                    samples[i * channels + ch] = options.amplitude * state.next(color, white_sample);
+                }
+            }
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = allocator,
+
+                .sample_rate = options.sample_rate,
+                .channels = options.channels,
+            };
+        }
+
+        /// Generates white noise. See `generate`.
+        pub fn white(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .white, options);
+        }
+
+        /// Generates pink noise. See `generate`.
+        pub fn pink(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .pink, options);
+        }
+
+        /// Generates brown noise. See `generate`.
+        pub fn brown(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .brown, options);
+        }
+
+        /// Generates blue noise. See `generate`.
+        pub fn blue(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .blue, options);
+        }
+
+        /// Generates violet noise. See `generate`.
+        pub fn violet(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .violet, options);
+        }
+
+        /// Generates velvet noise using `options.velvet_density`. See `generate`.
+        pub fn velvet(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .velvet, options);
+        }
+
+        /// Filter state which turns white noise into colored noise.
+        const State = struct {
+            // Paul Kellett's refined pink noise filter
+            b: [7]T = [_]T{0.0} ** 7,
+            // Leaky integrator for brown noise
+            integrated: T = 0.0,
+            // Previous input for differentiated noise
+            previous: T = 0.0,
+
+            fn next(self: *State, color: Color, white_sample: T) T {
+                return switch (color) {
+                    .white => white_sample,
+                    .pink => self.nextPink(white_sample),
+                    .brown => inner: {
+                        self.integrated = (self.integrated + 0.02 * white_sample) / 1.02;
+                        break :inner self.integrated * 3.5;
+                    },
+                    .blue => inner: {
+                        const p: T = self.nextPink(white_sample);
+                        const result: T = (p - self.previous) * 3.0;
+                        self.previous = p;
+                        break :inner result;
+                    },
+                    .violet => inner: {
+                        const result: T = (white_sample - self.previous) * 0.5;
+                        self.previous = white_sample;
+                        break :inner result;
+                    },
+                    .velvet => unreachable,
+                };
+            }
+
+            fn nextPink(self: *State, white_sample: T) T {
+                self.b[0] = 0.99886 * self.b[0] + white_sample * 0.0555179;
+                self.b[1] = 0.99332 * self.b[1] + white_sample * 0.0750759;
+                self.b[2] = 0.96900 * self.b[2] + white_sample * 0.1538520;
+                self.b[3] = 0.86650 * self.b[3] + white_sample * 0.3104856;
+                self.b[4] = 0.55000 * self.b[4] + white_sample * 0.5329522;
+                self.b[5] = -0.7616 * self.b[5] - white_sample * 0.0168980;
+                const result: T = self.b[0] + self.b[1] + self.b[2] + self.b[3] + self.b[4] + self.b[5] + self.b[6] + white_sample * 0.5362;
+                self.b[6] = white_sample * 0.115926;
+
+                return result * 0.11;
+            }
+        };
+
+        test "white" {
+            const allocator = testing.allocator;
+            const wave = try Self.white(allocator, .{
+                .amplitude = 0.5,
+                .duration = 1.0,
+                .sample_rate = 44100,
+                .seed = 0,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.sample_rate, 44100);
+            try testing.expectEqual(wave.channels, 1);
+            try testing.expectEqual(wave.samples.len, 44100);
+
+            for (wave.samples) |sample| {
+                try testing.expect(@abs(sample) <= 0.5);
+            }
+        }
+
+        test "same seed generates same samples" {
+            const allocator = testing.allocator;
+
+            for (std.enums.values(Color)) |color| {
+                const options: Options = .{
+                    .duration = 0.1,
+                    .sample_rate = 44100,
+                    .seed = 42,
+                };
+
+                const first = try Self.generate(allocator, color, options);
+                defer first.deinit();
+                const second = try Self.generate(allocator, color, options);
+                defer second.deinit();
+
+                try testing.expectEqualSlices(T, first.samples, second.samples);
+            }
+        }
+
+        test "different seeds generate different samples" {
+            const allocator = testing.allocator;
+
+            const first = try Self.white(allocator, .{ .duration = 0.1, .sample_rate = 44100, .seed = 1 });
+            defer first.deinit();
+            const second = try Self.white(allocator, .{ .duration = 0.1, .sample_rate = 44100, .seed = 2 });
+            defer second.deinit();
+
+            try testing.expect(!std.mem.eql(T, first.samples, second.samples));
+        }
+
+        test "channels receive independent noise" {
+            const allocator = testing.allocator;
+            const wave = try Self.white(allocator, .{
+                .duration = 0.1,
+                .sample_rate = 44100,
+                .channels = 2,
+                .seed = 0,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.samples.len, 4410 * 2);
+
+            var equal_frames: usize = 0;
+            for (0..4410) |i| {
+                if (wave.samples[i * 2] == wave.samples[i * 2 + 1])
+                    equal_frames += 1;
+            }
+            try testing.expect(equal_frames < 10);
+        }
+
+        test "colors have their spectral tilt" {
+            const allocator = testing.allocator;
+            const colors = [_]Color{ .brown, .pink, .white, .blue, .violet };
+            var ratios: [colors.len]T = undefined;
+
+            for (colors, 0..) |color, i| {
+                const wave = try Self.generate(allocator, color, .{
+                    .duration = 1.0,
+                    .sample_rate = 44100,
+                    .seed = 0,
+                });
+                defer wave.deinit();
+
+                // The energy of the first difference grows with the high-frequency content
+                var energy: T = 0.0;
+                var difference_energy: T = 0.0;
+                for (wave.samples, 0..) |sample, n| {
+                    energy += sample * sample;
+                    if (n > 0) {
+                        const d: T = sample - wave.samples[n - 1];
+                        difference_energy += d * d;
+                    }
+                }
+                ratios[i] = difference_energy / energy;
+            }
+
+            // White noise: E[(x[n] - x[n-1])^2] = 2 * E[x^2]
+            try testing.expectApproxEqAbs(ratios[2], 2.0, 0.05);
+
+            for (1..ratios.len) |i| {
+                try testing.expect(ratios[i - 1] < ratios[i]);
+            }
+        }
+
+        test "velvet" {
+            const allocator = testing.allocator;
+            const wave = try Self.velvet(allocator, .{
+                .amplitude = 0.5,
+                .duration = 1.0,
+                .sample_rate = 44100,
+                .seed = 0,
+                .velvet_density = 2205.0,
+            });
+            defer wave.deinit();
+
+            // One impulse in every 20 samples
+            var impulses: usize = 0;
+            for (wave.samples) |sample| {
+                if (sample != 0.0) {
+                    try testing.expectEqual(@abs(sample), 0.5);
+                    impulses += 1;
+                }
+            }
+            try testing.expectEqual(impulses, 2205);
+        }
+
+        test "invalid durations and velvet densities" {
+            const allocator = testing.allocator;
+            for ([_]T{ -1.0, std.math.nan(T), std.math.inf(T), 1e30 }) |duration| {
+                try testing.expectError(error.InvalidRange, Self.white(allocator, .{
+                    .duration = duration,
+                    .sample_rate = 44100,
+                    .seed = 0,
+                }));
+            }
+
+            for ([_]T{ 0.0, -2000.0, std.math.nan(T), 44101.0, std.math.inf(T) }) |density| {
+                try testing.expectError(error.InvalidRange, Self.velvet(allocator, .{
+                    .duration = 1.0,
+                    .sample_rate = 44100,
+                    .seed = 0,
+                    .velvet_density = density,
+                }));
+            }
+        }
+
+        test "zero duration" {
+            const allocator = testing.allocator;
+            const wave = try Self.pink(allocator, .{
+                .duration = 0.0,
+                .sample_rate = 44100,
+                .seed = 0,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.samples.len, 0);
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -0,0 +1,1570 @@
+const std = @import("std");
+const testing = std.testing;
+const Complex = std.math.Complex(f32);
+
+/// Ogg type function: Creates an Ogg Vorbis decoder for the specified sample type.
+///
+/// Reads the first logical stream of an Ogg file and decodes it as Vorbis I.
+/// Opus streams, and Vorbis streams using the obsolete floor type 0, are recognised
+/// but not decoded. Leading and trailing samples are trimmed according to the
+/// granule positions of the pages, as the reference decoder does.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// Use it through `Wave(T).LowLevelInterfaces.ogg`:
+/// ```zig
+/// var reader = std.Io.Reader.fixed(@embedFile("./sample.ogg"));
+/// const wave = try Wave(f64).read(.ogg, allocator, &reader);
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// Errors that can occur when reading Ogg files.
+        pub const OggErrors = error{
+            /// The data is not an Ogg file, or one of its pages is malformed
+            InvalidOggFile,
+            /// The Vorbis headers or audio packets are malformed
+            InvalidVorbisStream,
+            /// The stream is not Vorbis (for example Opus), or uses floor type 0
+            UnsupportedCodec,
+            /// A page's CRC doesn't match its contents
+            ChecksumMismatch,
+        };
+
+        /// Samples and format decoded from an Ogg file.
+        pub const Decoded = struct {
+            samples: []const T,
+            sample_rate: u32,
+            channels: u16,
+        };
+
+        /// Reads an Ogg Vorbis file.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for sample data
+        /// - `reader`: A reader interface providing the raw file bytes
+        ///
+        /// ## Returns
+        /// The decoded samples, owned by the caller, with the sample rate and channel count
+        ///
+        /// ## Errors
+        /// - `InvalidOggFile`: The data is not an Ogg file, or a page is malformed
+        /// - `InvalidVorbisStream`: The Vorbis headers or packets are malformed
+        /// - `UnsupportedCodec`: The stream is Opus or another codec, or uses floor type 0
+        /// - `ChecksumMismatch`: A page is corrupted
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn read(allocator: std.mem.Allocator, reader: anytype) anyerror!Decoded {
+            const bytes: []u8 = try reader.allocRemaining(allocator, .unlimited);
+            defer allocator.free(bytes);
+
+            // Headers, packets and decoder buffers live until the samples are decoded
+            var arena_state = std.heap.ArenaAllocator.init(allocator);
+            defer arena_state.deinit();
+            const arena = arena_state.allocator();
+
+            const packets: []const Packet = try readPackets(arena, bytes);
+            if (packets.len == 0)
+                return error.InvalidOggFile;
+            if (!std.mem.startsWith(u8, packets[0].data, "\x01vorbis"))
+                return error.UnsupportedCodec;
+            if (packets.len < 3)
+                return error.InvalidVorbisStream;
+
+            const identification: Identification = Identification.parse(packets[0].data) catch return error.InvalidVorbisStream;
+            if (!std.mem.startsWith(u8, packets[1].data, "\x03vorbis"))
+                return error.InvalidVorbisStream;
+            const setup: Setup = Setup.parse(arena, packets[2].data, identification) catch |err| switch (err) {
+                error.EndOfPacket => return error.InvalidVorbisStream,
+                else => |e| return e,
+            };
+
+            var decoder: Decoder = try .init(arena, identification, setup);
+            const channels: usize = identification.channels;
+
+            var samples: std.array_list.Aligned(T, null) = .empty;
+            errdefer samples.deinit(allocator);
+
+            // Frames decoded before the first granule position, and the position of the last one
+            var first_granule: ?struct { position: u64, frames: usize } = null;
+            var last_granule: ?u64 = null;
+
+            for (packets[3..]) |packet| {
+                try decoder.decodePacket(packet.data);
+                for (0..decoder.frames) |i| {
+                    for (decoder.outputs) |output| {
+                        try samples.append(allocator, @floatCast(output[i]));
+                    }
+                }
+
+                if (packet.granule) |granule| {
+                    if (first_granule == null)
+                        first_granule = .{ .position = granule, .frames = samples.items.len / channels };
+                    last_granule = granule;
+                }
+            }
+
+            // The first granule position tells how many leading frames to drop, and the last one the stream's length
+            var start: usize = 0;
+            var end: usize = samples.items.len / channels;
+            if (first_granule) |first| {
+                const first_position: i128 = @as(i128, first.position) - first.frames;
+                if (first_position < 0)
+                    start = @min(end, @as(usize, @intCast(-first_position)));
+
+                const length: i128 = @as(i128, last_granule.?) - @max(first_position, 0);
+                if (length >= 0)
+                    end = @min(end, start + @as(usize, @intCast(@min(length, end))));
+            }
+
+            std.mem.copyForwards(T, samples.items, samples.items[start * channels .. end * channels]);
+            samples.shrinkRetainingCapacity((end - start) * channels);
+
+            return .{
+                .samples = try samples.toOwnedSlice(allocator),
+                .sample_rate = identification.sample_rate,
+                .channels = identification.channels,
+            };
+        }
+
+        test "reject files which aren't Ogg Vorbis" {
+            const allocator = testing.allocator;
+
+            var wav = std.Io.Reader.fixed("RIFF\x00\x00\x00\x00WAVE");
+            try testing.expectError(error.InvalidOggFile, Self.read(allocator, &wav));
+
+            const opus: []u8 = try testStream(allocator, &.{"OpusHead\x01\x01\x38\x01\x80\xbb\x00\x00\x00\x00\x00"});
+            defer allocator.free(opus);
+            var opus_reader = std.Io.Reader.fixed(opus);
+            try testing.expectError(error.UnsupportedCodec, Self.read(allocator, &opus_reader));
+
+            const truncated: []u8 = try testStream(allocator, &.{"\x01vorbis\x00\x00\x00\x00\x01"});
+            defer allocator.free(truncated);
+            var truncated_reader = std.Io.Reader.fixed(truncated);
+            try testing.expectError(error.InvalidVorbisStream, Self.read(allocator, &truncated_reader));
+
+            truncated[truncated.len - 1] ^= 0x01;
+            var corrupted_reader = std.Io.Reader.fixed(truncated);
+            try testing.expectError(error.ChecksumMismatch, Self.read(allocator, &corrupted_reader));
+        }
+    };
+}
+
+/// A packet of the first logical stream.
+const Packet = struct {
+    data: []const u8,
+    /// The granule position of the page, if the packet is the last one completed on it
+    granule: ?u64,
+};
+
+/// Splits the first logical stream of an Ogg file into packets.
+fn readPackets(allocator: std.mem.Allocator, bytes: []const u8) ![]const Packet {
+    var packets: std.array_list.Aligned(Packet, null) = .empty;
+    var pending: std.array_list.Aligned(u8, null) = .empty;
+    var serial: ?u32 = null;
+
+    var position: usize = 0;
+    while (position < bytes.len) {
+        if (bytes.len < position + 27 or !std.mem.eql(u8, bytes[position..][0..4], "OggS") or bytes[position + 4] != 0)
+            return error.InvalidOggFile;
+
+        const header_type: u8 = bytes[position + 5];
+        const granule: u64 = std.mem.readInt(u64, bytes[position + 6 ..][0..8], .little);
+        const page_serial: u32 = std.mem.readInt(u32, bytes[position + 14 ..][0..4], .little);
+        const crc: u32 = std.mem.readInt(u32, bytes[position + 22 ..][0..4], .little);
+        const segment_count: usize = bytes[position + 26];
+        if (bytes.len < position + 27 + segment_count)
+            return error.InvalidOggFile;
+
+        const lacing: []const u8 = bytes[position + 27 ..][0..segment_count];
+        var body_length: usize = 0;
+        for (lacing) |length| {
+            body_length += length;
+        }
+
+        const body_start: usize = position + 27 + segment_count;
+        if (bytes.len < body_start + body_length)
+            return error.InvalidOggFile;
+
+        // The CRC is computed with its own field set to zero
+        var computed: u32 = oggCrc(0, bytes[position .. position + 22]);
+        computed = oggCrc(computed, &.{ 0, 0, 0, 0 });
+        computed = oggCrc(computed, bytes[position + 26 .. body_start + body_length]);
+        if (computed != crc)
+            return error.ChecksumMismatch;
+
+        if (serial == null)
+            serial = page_serial;
+
+        if (page_serial == serial.?) {
+            const continued: bool = header_type & 0x01 != 0;
+            // A packet is lost if the page which should continue it doesn't
+            if (!continued)
+                pending.clearRetainingCapacity();
+            var skip: bool = continued and pending.items.len == 0;
+
+            var offset: usize = body_start;
+            var completed: bool = false;
+            for (lacing) |length| {
+                if (!skip)
+                    try pending.appendSlice(allocator, bytes[offset..][0..length]);
+                offset += length;
+
+                if (length < 255) {
+                    if (!skip) {
+                        try packets.append(allocator, .{ .data = try pending.toOwnedSlice(allocator), .granule = null });
+                        completed = true;
+                    }
+                    skip = false;
+                }
+            }
+
+            // -1 means that no packet ends on the page
+            if (completed and granule != std.math.maxInt(u64))
+                packets.items[packets.items.len - 1].granule = granule;
+        }
+
+        position = body_start + body_length;
+    }
+
+    return packets.toOwnedSlice(allocator);
+}
+
+const ogg_crc_table: [256]u32 = blk: {
+    var table: [256]u32 = undefined;
+    for (&table, 0..) |*entry, i| {
+        var r: u32 = @as(u32, i) << 24;
+        for (0..8) |_| {
+            r = if (r & 0x80000000 != 0) (r << 1) ^ 0x04C11DB7 else r << 1;
+        }
+        entry.* = r;
+    }
+    break :blk table;
+};
+
+/// CRC-32 of Ogg pages: polynomial 0x04C11DB7, initial value 0, no reflection.
+fn oggCrc(crc: u32, bytes: []const u8) u32 {
+    var result: u32 = crc;
+    for (bytes) |byte| {
+        result = (result << 8) ^ ogg_crc_table[@as(u8, @truncate(result >> 24)) ^ byte];
+    }
+    return result;
+}
+
+/// Reads bits from the least significant one, as Vorbis packets are packed.
+const BitReader = struct {
+    bytes: []const u8,
+    position: usize = 0,
+
+    fn readBits(self: *BitReader, count: u6) error{EndOfPacket}!u32 {
+        var result: u64 = 0;
+        var done: u6 = 0;
+        while (done < count) {
+            if (self.position / 8 >= self.bytes.len)
+                return error.EndOfPacket;
+
+            const offset: u3 = @intCast(self.position % 8);
+            const take: u6 = @min(count - done, 8 - @as(u6, offset));
+            const chunk: u64 = (self.bytes[self.position / 8] >> offset) & ((@as(u64, 1) << take) - 1);
+
+            result |= chunk << done;
+            done += take;
+            self.position += take;
+        }
+
+        return @intCast(result);
+    }
+
+    fn readFlag(self: *BitReader) error{EndOfPacket}!bool {
+        return try self.readBits(1) == 1;
+    }
+
+    /// Returns the number of bits left in the packet.
+    fn remaining(self: BitReader) u64 {
+        return (self.bytes.len * 8) -| self.position;
+    }
+};
+
+fn ilog(value: u32) u6 {
+    return @intCast(32 - @clz(value));
+}
+
+const Identification = struct {
+    channels: u16,
+    sample_rate: u32,
+    block_sizes: [2]usize,
+
+    fn parse(packet: []const u8) !Identification {
+        var reader: BitReader = .{ .bytes = packet, .position = 7 * 8 };
+        const version: u32 = try reader.readBits(32);
+        const channels: u16 = @intCast(try reader.readBits(8));
+        const sample_rate: u32 = try reader.readBits(32);
+        _ = try reader.readBits(32); // Maximum bitrate
+        _ = try reader.readBits(32); // Nominal bitrate
+        _ = try reader.readBits(32); // Minimum bitrate
+        const short_exponent: u6 = @intCast(try reader.readBits(4));
+        const long_exponent: u6 = @intCast(try reader.readBits(4));
+
+        if (version != 0 or channels == 0 or sample_rate == 0 or !try reader.readFlag())
+            return error.InvalidVorbisStream;
+        if (short_exponent < 6 or long_exponent > 13 or short_exponent > long_exponent)
+            return error.InvalidVorbisStream;
+
+        return .{
+            .channels = channels,
+            .sample_rate = sample_rate,
+            .block_sizes = .{ @as(usize, 1) << short_exponent, @as(usize, 1) << long_exponent },
+        };
+    }
+};
+
+const Codebook = struct {
+    dimensions: u16,
+    lengths: []const u8,
+    /// Children of every node of the Huffman tree: 0 for none, a node index, or `-(entry + 1)` for a leaf
+    tree: []const [2]i32,
+    /// The only used entry of a single-entry codebook, read from one bit
+    single_entry: ?u32,
+    lookup_type: u4,
+    /// `multiplicand * delta + minimum` for every multiplicand
+    values: []const f32,
+    lookup_values: u32,
+    sequence_p: bool,
+
+    fn parse(allocator: std.mem.Allocator, reader: *BitReader) !Codebook {
+        if (try reader.readBits(24) != 0x564342)
+            return error.InvalidVorbisStream;
+
+        const dimensions: u16 = @intCast(try reader.readBits(16));
+        const entries: u32 = try reader.readBits(24);
+        // As in libvorbis, the values of a codebook can't exceed 24 bits of indexes
+        if (@as(u7, ilog(dimensions)) + ilog(entries) > 24)
+            return error.InvalidVorbisStream;
+
+        const ordered: bool = try reader.readFlag();
+        const sparse: bool = !ordered and try reader.readFlag();
+        // Unordered lengths take 5 bits per entry, or a flag per entry when sparse,
+        // so more entries than the packet's bits can hold are invalid
+        if (!ordered and @as(u64, entries) * @as(u64, if (sparse) 1 else 5) > reader.remaining())
+            return error.InvalidVorbisStream;
+
+        const lengths: []u8 = try allocator.alloc(u8, entries);
+        if (ordered) {
+            // Ordered: runs of entries with increasing lengths
+            var length: u32 = try reader.readBits(5) + 1;
+            var entry: u32 = 0;
+            while (entry < entries) : (length += 1) {
+                const count: u32 = try reader.readBits(ilog(entries - entry));
+                if (length > 32 or count > entries - entry)
+                    return error.InvalidVorbisStream;
+                @memset(lengths[entry..][0..count], @intCast(length));
+                entry += count;
+            }
+        } else {
+            for (lengths) |*length| {
+                length.* = if (!sparse or try reader.readFlag()) @intCast(try reader.readBits(5) + 1) else 0;
+            }
+        }
+
+        var codebook: Codebook = .{
+            .dimensions = dimensions,
+            .lengths = lengths,
+            .tree = &.{},
+            .single_entry = null,
+            .lookup_type = @intCast(try reader.readBits(4)),
+            .values = &.{},
+            .lookup_values = 0,
+            .sequence_p = false,
+        };
+
+        switch (codebook.lookup_type) {
+            0 => {},
+            1, 2 => {
+                const minimum: f32 = float32Unpack(try reader.readBits(32));
+                const delta: f32 = float32Unpack(try reader.readBits(32));
+                const value_bits: u6 = @intCast(try reader.readBits(4) + 1);
+                codebook.sequence_p = try reader.readFlag();
+                codebook.lookup_values = if (codebook.lookup_type == 1)
+                    lookup1Values(entries, dimensions)
+                else
+                    std.math.mul(u32, entries, dimensions) catch return error.InvalidVorbisStream;
+
+                // Every multiplicand is stored in the packet, so a count larger than its bits is invalid
+                if (@as(u64, codebook.lookup_values) * value_bits > reader.remaining())
+                    return error.InvalidVorbisStream;
+
+                const values: []f32 = try allocator.alloc(f32, codebook.lookup_values);
+                for (values) |*value| {
+                    value.* = @as(f32, @floatFromInt(try reader.readBits(value_bits))) * delta + minimum;
+                }
+                codebook.values = values;
+            },
+            else => return error.InvalidVorbisStream,
+        }
+
+        try codebook.buildTree(allocator);
+        return codebook;
+    }
+
+    /// Assigns codewords to the entries in order, each one the lowest available of its length.
+    fn buildTree(self: *Codebook, allocator: std.mem.Allocator) !void {
+        var used: usize = 0;
+        for (self.lengths, 0..) |length, entry| {
+            if (length > 0) {
+                used += 1;
+                self.single_entry = @intCast(entry);
+            }
+        }
+        if (used == 1) {
+            if (self.lengths[self.single_entry.?] != 1)
+                return error.InvalidVorbisStream;
+            return;
+        }
+        self.single_entry = null;
+
+        var tree: std.array_list.Aligned([2]i32, null) = .empty;
+        try tree.append(allocator, .{ 0, 0 });
+
+        var markers: [33]u64 = @splat(0);
+        for (self.lengths, 0..) |length, entry| {
+            if (length == 0)
+                continue;
+
+            var codeword: u64 = markers[length];
+            if (codeword >> @intCast(length) != 0)
+                return error.InvalidVorbisStream;
+
+            // Insert the codeword, from its most significant bit
+            var node: usize = 0;
+            var bit: u6 = @intCast(length);
+            while (bit > 0) {
+                bit -= 1;
+                const side: u1 = @intCast((codeword >> bit) & 1);
+                const child: i32 = tree.items[node][side];
+                if (bit == 0) {
+                    if (child != 0)
+                        return error.InvalidVorbisStream;
+                    tree.items[node][side] = -@as(i32, @intCast(entry)) - 1;
+                } else if (child == 0) {
+                    tree.items[node][side] = @intCast(tree.items.len);
+                    node = tree.items.len;
+                    try tree.append(allocator, .{ 0, 0 });
+                } else if (child < 0) {
+                    return error.InvalidVorbisStream;
+                } else {
+                    node = @intCast(child);
+                }
+            }
+
+            // Update the markers of this length and the longer ones
+            var j: usize = length;
+            while (j > 0) : (j -= 1) {
+                if (markers[j] & 1 != 0) {
+                    markers[j] = if (j == 1) markers[1] + 1 else markers[j - 1] << 1;
+                    break;
+                }
+                markers[j] += 1;
+            }
+            for (length + 1..33) |k| {
+                if (markers[k] >> 1 != codeword)
+                    break;
+                codeword = markers[k];
+                markers[k] = markers[k - 1] << 1;
+            }
+        }
+
+        self.tree = try tree.toOwnedSlice(allocator);
+    }
+
+    fn decodeEntry(self: Codebook, reader: *BitReader) error{EndOfPacket}!u32 {
+        if (self.single_entry) |entry| {
+            _ = try reader.readBits(1);
+            return entry;
+        }
+
+        var node: usize = 0;
+        while (true) {
+            const child: i32 = self.tree[node][try reader.readBits(1)];
+            // A codeword which no entry uses ends the packet, as a corrupted one would
+            if (child == 0)
+                return error.EndOfPacket;
+            if (child < 0)
+                return @intCast(-child - 1);
+            node = @intCast(child);
+        }
+    }
+
+    /// Adds the vector of an entry to `output`, one value every `stride` items.
+    fn addVector(self: Codebook, entry: u32, output: []f32, stride: usize) void {
+        var last: f32 = 0.0;
+        var divisor: u64 = 1;
+        for (0..self.dimensions) |i| {
+            if (i * stride >= output.len)
+                break;
+
+            const offset: u64 = if (self.lookup_type == 1)
+                (entry / divisor) % self.lookup_values
+            else
+                @as(u64, entry) * self.dimensions + i;
+            const value: f32 = self.values[@intCast(offset)] + last;
+
+            output[i * stride] += value;
+            if (self.sequence_p)
+                last = value;
+            divisor *|= self.lookup_values;
+        }
+    }
+};
+
+fn float32Unpack(value: u32) f32 {
+    const mantissa: f32 = @floatFromInt(value & 0x1FFFFF);
+    const exponent: i32 = @intCast((value & 0x7FE00000) >> 21);
+    const result: f32 = std.math.ldexp(mantissa, exponent - 788);
+    return if (value & 0x80000000 != 0) -result else result;
+}
+
+/// The greatest integer whose `dimensions`-th power is at most `entries`.
+fn lookup1Values(entries: u32, dimensions: u16) u32 {
+    if (dimensions == 0)
+        return 0;
+
+    var result: u32 = @intFromFloat(@floor(std.math.pow(f64, @floatFromInt(entries), 1.0 / @as(f64, @floatFromInt(dimensions)))));
+    while (power(result + 1, dimensions) <= entries) result += 1;
+    while (result > 0 and power(result, dimensions) > entries) result -= 1;
+    return result;
+}
+
+fn power(base: u32, exponent: u16) u64 {
+    var result: u64 = 1;
+    for (0..exponent) |_| {
+        result *= base;
+        // Anything above 2^24 entries is out of range anyway
+        if (result > 1 << 32)
+            return result;
+    }
+    return result;
+}
+
+const max_floor_values = 65;
+
+const Floor = struct {
+    partition_classes: []const u8,
+    class_dimensions: [16]u8,
+    class_subclasses: [16]u2,
+    class_masterbooks: [16]u8,
+    /// Book of every subclass, or -1 for none
+    subclass_books: [16][8]i16,
+    multiplier: u8,
+    xs: []const u32,
+    /// Indices of `xs`, sorted by value
+    sorted: []const u8,
+    low_neighbors: []const u8,
+    high_neighbors: []const u8,
+
+    fn parse(allocator: std.mem.Allocator, reader: *BitReader, codebooks: []const Codebook) !Floor {
+        var floor: Floor = undefined;
+
+        const partitions: usize = try reader.readBits(5);
+        const partition_classes: []u8 = try allocator.alloc(u8, partitions);
+        var class_count: usize = 0;
+        for (partition_classes) |*class| {
+            class.* = @intCast(try reader.readBits(4));
+            class_count = @max(class_count, class.* + 1);
+        }
+        floor.partition_classes = partition_classes;
+
+        for (0..class_count) |class| {
+            floor.class_dimensions[class] = @intCast(try reader.readBits(3) + 1);
+            floor.class_subclasses[class] = @intCast(try reader.readBits(2));
+            if (floor.class_subclasses[class] != 0) {
+                floor.class_masterbooks[class] = @intCast(try reader.readBits(8));
+                if (floor.class_masterbooks[class] >= codebooks.len)
+                    return error.InvalidVorbisStream;
+            }
+            for (0..@as(usize, 1) << floor.class_subclasses[class]) |subclass| {
+                const book: i16 = @as(i16, @intCast(try reader.readBits(8))) - 1;
+                if (book >= codebooks.len)
+                    return error.InvalidVorbisStream;
+                floor.subclass_books[class][subclass] = book;
+            }
+        }
+
+        floor.multiplier = @intCast(try reader.readBits(2) + 1);
+        const range_bits: u6 = @intCast(try reader.readBits(4));
+
+        var xs: std.array_list.Aligned(u32, null) = .empty;
+        try xs.appendSlice(allocator, &.{ 0, @as(u32, 1) << @intCast(range_bits) });
+        for (partition_classes) |class| {
+            for (0..floor.class_dimensions[class]) |_| {
+                if (xs.items.len == max_floor_values)
+                    return error.InvalidVorbisStream;
+                try xs.append(allocator, try reader.readBits(range_bits));
+            }
+        }
+        floor.xs = xs.items;
+
+        const sorted: []u8 = try allocator.alloc(u8, xs.items.len);
+        for (sorted, 0..) |*index, i| {
+            index.* = @intCast(i);
+        }
+        std.mem.sort(u8, sorted, floor.xs, struct {
+            fn lessThan(values: []const u32, a: u8, b: u8) bool {
+                return values[a] < values[b];
+            }
+        }.lessThan);
+        for (sorted[1..], sorted[0 .. sorted.len - 1]) |a, b| {
+            if (floor.xs[a] == floor.xs[b])
+                return error.InvalidVorbisStream;
+        }
+        floor.sorted = sorted;
+
+        // The closest lower and higher values among the preceding ones
+        const low_neighbors: []u8 = try allocator.alloc(u8, xs.items.len);
+        const high_neighbors: []u8 = try allocator.alloc(u8, xs.items.len);
+        for (2..xs.items.len) |i| {
+            var low: usize = 0;
+            var high: usize = 1;
+            for (0..i) |j| {
+                if (floor.xs[j] < floor.xs[i] and floor.xs[j] > floor.xs[low]) low = j;
+                if (floor.xs[j] > floor.xs[i] and floor.xs[j] < floor.xs[high]) high = j;
+            }
+            low_neighbors[i] = @intCast(low);
+            high_neighbors[i] = @intCast(high);
+        }
+        floor.low_neighbors = low_neighbors;
+        floor.high_neighbors = high_neighbors;
+
+        return floor;
+    }
+
+    /// Decodes the floor's points of a channel.
+    ///
+    /// ## Returns
+    /// `false` if the channel is unused in this packet
+    fn decode(self: Floor, reader: *BitReader, codebooks: []const Codebook, ys: *[max_floor_values]u32, used: *[max_floor_values]bool) error{EndOfPacket}!bool {
+        if (!try reader.readFlag())
+            return false;
+
+        const range: u32 = ([_]u32{ 256, 128, 86, 64 })[self.multiplier - 1];
+        const bits: u6 = ilog(range - 1);
+        ys[0] = @min(try reader.readBits(bits), range - 1);
+        ys[1] = @min(try reader.readBits(bits), range - 1);
+
+        var offset: usize = 2;
+        for (self.partition_classes) |class| {
+            const subclass_bits: u2 = self.class_subclasses[class];
+            const subclass_mask: u32 = (@as(u32, 1) << subclass_bits) - 1;
+            var subclass: u32 = if (subclass_bits > 0) try codebooks[self.class_masterbooks[class]].decodeEntry(reader) else 0;
+
+            for (0..self.class_dimensions[class]) |_| {
+                const book: i16 = self.subclass_books[class][subclass & subclass_mask];
+                subclass >>= subclass_bits;
+                ys[offset] = if (book >= 0) try codebooks[@intCast(book)].decodeEntry(reader) else 0;
+                offset += 1;
+            }
+        }
+
+        // Every value is coded as an offset from the line between its neighbours
+        used[0] = true;
+        used[1] = true;
+        for (2..self.xs.len) |i| {
+            const low: usize = self.low_neighbors[i];
+            const high: usize = self.high_neighbors[i];
+            const predicted: u32 = renderPoint(self.xs[low], ys[low], self.xs[high], ys[high], self.xs[i]);
+            const value: i64 = ys[i];
+            const high_room: i64 = @as(i64, range) - predicted;
+            const low_room: i64 = predicted;
+            const room: i64 = @min(high_room, low_room) * 2;
+
+            if (value == 0) {
+                used[i] = false;
+                ys[i] = predicted;
+                continue;
+            }
+
+            used[low] = true;
+            used[high] = true;
+            used[i] = true;
+            const final: i64 = if (value >= room)
+                (if (high_room > low_room) value - low_room + predicted else predicted + high_room - value - 1)
+            else if (@mod(value, 2) == 1)
+                predicted - @divTrunc(value + 1, 2)
+            else
+                predicted + @divTrunc(value, 2);
+            ys[i] = @intCast(std.math.clamp(final, 0, range - 1));
+        }
+
+        return true;
+    }
+
+    /// Renders the floor curve of decoded points into `output`, as linear amplitudes.
+    fn render(self: Floor, ys: *const [max_floor_values]u32, used: *const [max_floor_values]bool, output: []f32) void {
+        var lx: u32 = 0;
+        var ly: u32 = ys[self.sorted[0]] * self.multiplier;
+        var hx: u32 = 0;
+        var hy: u32 = ly;
+
+        for (self.sorted[1..]) |i| {
+            if (!used[i])
+                continue;
+
+            hx = self.xs[i];
+            hy = ys[i] * self.multiplier;
+            renderLine(lx, ly, hx, hy, output);
+            lx = hx;
+            ly = hy;
+        }
+
+        if (hx < output.len)
+            renderLine(hx, hy, @intCast(output.len), hy, output);
+    }
+};
+
+fn renderPoint(x0: u32, y0: u32, x1: u32, y1: u32, x: u32) u32 {
+    const dy: i64 = @as(i64, y1) - y0;
+    const adx: i64 = @as(i64, x1) - x0;
+    const offset: i64 = @divTrunc(@as(i64, @intCast(@abs(dy))) * (@as(i64, x) - x0), adx);
+    return @intCast(if (dy < 0) y0 - offset else y0 + offset);
+}
+
+fn renderLine(x0: u32, y0: u32, x1: u32, y1: u32, output: []f32) void {
+    const dy: i64 = @as(i64, y1) - y0;
+    const adx: i64 = @as(i64, x1) - x0;
+    const base: i64 = @divTrunc(dy, adx);
+    const sy: i64 = if (dy < 0) base - 1 else base + 1;
+    const ady: i64 = @as(i64, @intCast(@abs(dy))) - @as(i64, @intCast(@abs(base))) * adx;
+
+    var y: i64 = y0;
+    var err: i64 = 0;
+    var x: usize = x0;
+    while (x < x1 and x < output.len) : (x += 1) {
+        if (x > x0) {
+            err += ady;
+            if (err >= adx) {
+                err -= adx;
+                y += sy;
+            } else {
+                y += base;
+            }
+        }
+        output[x] = floor1_inverse_db_table[@intCast(std.math.clamp(y, 0, 255))];
+    }
+}
+
+const Residue = struct {
+    kind: u2,
+    begin: u32,
+    end: u32,
+    partition_size: u32,
+    classifications: u8,
+    classbook: u8,
+    /// Book of every class and pass, or -1 for none
+    books: [64][8]i16,
+
+    fn parse(reader: *BitReader, codebooks: []const Codebook) !Residue {
+        const kind: u32 = try reader.readBits(16);
+        if (kind > 2)
+            return error.InvalidVorbisStream;
+
+        var residue: Residue = .{
+            .kind = @intCast(kind),
+            .begin = try reader.readBits(24),
+            .end = try reader.readBits(24),
+            .partition_size = try reader.readBits(24) + 1,
+            .classifications = @intCast(try reader.readBits(6) + 1),
+            .classbook = @intCast(try reader.readBits(8)),
+            .books = undefined,
+        };
+        if (residue.classbook >= codebooks.len or codebooks[residue.classbook].dimensions == 0)
+            return error.InvalidVorbisStream;
+
+        var cascades: [64]u8 = undefined;
+        for (cascades[0..residue.classifications]) |*cascade| {
+            const low: u32 = try reader.readBits(3);
+            const high: u32 = if (try reader.readFlag()) try reader.readBits(5) else 0;
+            cascade.* = @intCast(high * 8 + low);
+        }
+
+        for (cascades[0..residue.classifications], 0..) |cascade, class| {
+            for (0..8) |pass| {
+                residue.books[class][pass] = -1;
+                if (cascade & (@as(u8, 1) << @intCast(pass)) != 0) {
+                    const book: u32 = try reader.readBits(8);
+                    if (book >= codebooks.len or codebooks[book].lookup_type == 0 or codebooks[book].dimensions == 0)
+                        return error.InvalidVorbisStream;
+                    residue.books[class][pass] = @intCast(book);
+                }
+            }
+        }
+
+        return residue;
+    }
+
+    /// Decodes the residue vectors of the channels of a submap.
+    /// Vectors must be zeroed, and `scratch` must hold the vectors of every channel interleaved.
+    fn decode(
+        self: Residue,
+        reader: *BitReader,
+        codebooks: []const Codebook,
+        vectors: []const []f32,
+        do_not_decode: []const bool,
+        scratch: []f32,
+        classes: []u8,
+    ) void {
+        if (self.kind != 2) {
+            self.decodePartitions(reader, codebooks, vectors, do_not_decode, self.kind, classes) catch {};
+            return;
+        }
+
+        if (std.mem.allEqual(bool, do_not_decode, true))
+            return;
+
+        // Format 2 interleaves the channels into one vector
+        const channels: usize = vectors.len;
+        const interleaved: []f32 = scratch[0 .. vectors[0].len * channels];
+        @memset(interleaved, 0.0);
+        self.decodePartitions(reader, codebooks, &.{interleaved}, &.{false}, 1, classes) catch {};
+
+        for (vectors, 0..) |vector, ch| {
+            for (vector, 0..) |*value, i| {
+                value.* = interleaved[i * channels + ch];
+            }
+        }
+    }
+
+    fn decodePartitions(
+        self: Residue,
+        reader: *BitReader,
+        codebooks: []const Codebook,
+        vectors: []const []f32,
+        do_not_decode: []const bool,
+        kind: u2,
+        classes: []u8,
+    ) error{EndOfPacket}!void {
+        const size: usize = vectors[0].len;
+        const begin: usize = @min(self.begin, size);
+        const end: usize = @min(self.end, size);
+        if (end <= begin)
+            return;
+
+        const partitions: usize = (end - begin) / self.partition_size;
+        const classbook: Codebook = codebooks[self.classbook];
+        const classwords: usize = classbook.dimensions;
+        const stride: usize = partitions + classwords;
+
+        for (0..8) |pass| {
+            var partition: usize = 0;
+            while (partition < partitions) {
+                if (pass == 0) {
+                    for (vectors, 0..) |_, ch| {
+                        if (do_not_decode[ch])
+                            continue;
+
+                        var temp: u32 = try classbook.decodeEntry(reader);
+                        var i: usize = classwords;
+                        while (i > 0) {
+                            i -= 1;
+                            classes[ch * stride + partition + i] = @intCast(temp % self.classifications);
+                            temp /= self.classifications;
+                        }
+                    }
+                }
+
+                for (0..classwords) |_| {
+                    if (partition >= partitions)
+                        break;
+
+                    for (vectors, 0..) |vector, ch| {
+                        if (do_not_decode[ch])
+                            continue;
+
+                        const book: i16 = self.books[classes[ch * stride + partition]][pass];
+                        if (book < 0)
+                            continue;
+
+                        const codebook: Codebook = codebooks[@intCast(book)];
+                        const output: []f32 = vector[begin + partition * self.partition_size ..][0..self.partition_size];
+                        const dimensions: usize = codebook.dimensions;
+
+                        if (kind == 0) {
+                            // Format 0 spreads every vector across the partition
+                            const step: usize = self.partition_size / dimensions;
+                            for (0..step) |j| {
+                                codebook.addVector(try codebook.decodeEntry(reader), output[j..], step);
+                            }
+                        } else {
+                            var j: usize = 0;
+                            while (j < self.partition_size) : (j += dimensions) {
+                                codebook.addVector(try codebook.decodeEntry(reader), output[j..], 1);
+                            }
+                        }
+                    }
+                    partition += 1;
+                }
+            }
+        }
+    }
+};
+
+const Mapping = struct {
+    magnitudes: []const u8,
+    angles: []const u8,
+    /// Submap of every channel
+    mux: []const u8,
+    submap_floors: []const u8,
+    submap_residues: []const u8,
+
+    fn parse(allocator: std.mem.Allocator, reader: *BitReader, channels: u16, floor_count: usize, residue_count: usize) !Mapping {
+        if (try reader.readBits(16) != 0)
+            return error.InvalidVorbisStream;
+
+        const submaps: usize = if (try reader.readFlag()) try reader.readBits(4) + 1 else 1;
+
+        var steps: usize = 0;
+        if (try reader.readFlag())
+            steps = try reader.readBits(8) + 1;
+
+        const magnitudes: []u8 = try allocator.alloc(u8, steps);
+        const angles: []u8 = try allocator.alloc(u8, steps);
+        const channel_bits: u6 = ilog(channels - 1);
+        for (magnitudes, angles) |*magnitude, *angle| {
+            magnitude.* = @intCast(try reader.readBits(channel_bits));
+            angle.* = @intCast(try reader.readBits(channel_bits));
+            if (magnitude.* == angle.* or magnitude.* >= channels or angle.* >= channels)
+                return error.InvalidVorbisStream;
+        }
+
+        if (try reader.readBits(2) != 0)
+            return error.InvalidVorbisStream;
+
+        const mux: []u8 = try allocator.alloc(u8, channels);
+        for (mux) |*submap| {
+            submap.* = if (submaps > 1) @intCast(try reader.readBits(4)) else 0;
+            if (submap.* >= submaps)
+                return error.InvalidVorbisStream;
+        }
+
+        const submap_floors: []u8 = try allocator.alloc(u8, submaps);
+        const submap_residues: []u8 = try allocator.alloc(u8, submaps);
+        for (submap_floors, submap_residues) |*floor, *residue| {
+            _ = try reader.readBits(8); // Unused time configuration
+            floor.* = @intCast(try reader.readBits(8));
+            residue.* = @intCast(try reader.readBits(8));
+            if (floor.* >= floor_count or residue.* >= residue_count)
+                return error.InvalidVorbisStream;
+        }
+
+        return .{
+            .magnitudes = magnitudes,
+            .angles = angles,
+            .mux = mux,
+            .submap_floors = submap_floors,
+            .submap_residues = submap_residues,
+        };
+    }
+};
+
+const Mode = struct {
+    long_block: bool,
+    mapping: u8,
+};
+
+const Setup = struct {
+    codebooks: []const Codebook,
+    floors: []const Floor,
+    residues: []const Residue,
+    mappings: []const Mapping,
+    modes: []const Mode,
+
+    fn parse(allocator: std.mem.Allocator, packet: []const u8, identification: Identification) !Setup {
+        if (!std.mem.startsWith(u8, packet, "\x05vorbis"))
+            return error.InvalidVorbisStream;
+        var reader: BitReader = .{ .bytes = packet, .position = 7 * 8 };
+
+        const codebooks: []Codebook = try allocator.alloc(Codebook, try reader.readBits(8) + 1);
+        for (codebooks) |*codebook| {
+            codebook.* = try Codebook.parse(allocator, &reader);
+        }
+
+        // Time domain transforms are placeholders in Vorbis I
+        for (0..try reader.readBits(6) + 1) |_| {
+            if (try reader.readBits(16) != 0)
+                return error.InvalidVorbisStream;
+        }
+
+        const floors: []Floor = try allocator.alloc(Floor, try reader.readBits(6) + 1);
+        for (floors) |*floor| {
+            switch (try reader.readBits(16)) {
+                0 => return error.UnsupportedCodec,
+                1 => floor.* = try Floor.parse(allocator, &reader, codebooks),
+                else => return error.InvalidVorbisStream,
+            }
+        }
+
+        const residues: []Residue = try allocator.alloc(Residue, try reader.readBits(6) + 1);
+        for (residues) |*residue| {
+            residue.* = try Residue.parse(&reader, codebooks);
+        }
+
+        const mappings: []Mapping = try allocator.alloc(Mapping, try reader.readBits(6) + 1);
+        for (mappings) |*mapping| {
+            mapping.* = try Mapping.parse(allocator, &reader, identification.channels, floors.len, residues.len);
+        }
+
+        const modes: []Mode = try allocator.alloc(Mode, try reader.readBits(6) + 1);
+        for (modes) |*mode| {
+            mode.long_block = try reader.readFlag();
+            const window_type: u32 = try reader.readBits(16);
+            const transform_type: u32 = try reader.readBits(16);
+            mode.mapping = @intCast(try reader.readBits(8));
+            if (window_type != 0 or transform_type != 0 or mode.mapping >= mappings.len)
+                return error.InvalidVorbisStream;
+        }
+
+        if (!try reader.readFlag())
+            return error.InvalidVorbisStream;
+
+        return .{
+            .codebooks = codebooks,
+            .floors = floors,
+            .residues = residues,
+            .mappings = mappings,
+            .modes = modes,
+        };
+    }
+};
+
+/// Inverse MDCT of one block size, through a complex FFT of a quarter of its size.
+const Imdct = struct {
+    size: usize,
+    pre_twiddles: []const Complex,
+    post_twiddles: []const Complex,
+    roots: []const Complex,
+    bit_reverse: []const u16,
+
+    fn init(allocator: std.mem.Allocator, size: usize) !Imdct {
+        const m: usize = size / 2;
+        const h: usize = size / 4;
+        const pre_twiddles: []Complex = try allocator.alloc(Complex, h);
+        const post_twiddles: []Complex = try allocator.alloc(Complex, h);
+        const roots: []Complex = try allocator.alloc(Complex, h / 2);
+        const bit_reverse: []u16 = try allocator.alloc(u16, h);
+
+        for (0..h) |k| {
+            const fk: f64 = @floatFromInt(k);
+            const fm: f64 = @floatFromInt(m);
+            pre_twiddles[k] = expi(-std.math.pi * fk / fm);
+            post_twiddles[k] = expi(-std.math.pi * (fk + 0.25) / fm);
+            bit_reverse[k] = @bitReverse(@as(u16, @intCast(k))) >> @intCast(16 - std.math.log2_int(usize, h));
+        }
+        for (roots, 0..) |*root, k| {
+            root.* = expi(-2.0 * std.math.pi * @as(f64, @floatFromInt(k)) / @as(f64, @floatFromInt(h)));
+        }
+
+        return .{
+            .size = size,
+            .pre_twiddles = pre_twiddles,
+            .post_twiddles = post_twiddles,
+            .roots = roots,
+            .bit_reverse = bit_reverse,
+        };
+    }
+
+    fn expi(angle: f64) Complex {
+        return .init(@floatCast(@cos(angle)), @floatCast(@sin(angle)));
+    }
+
+    /// Computes `output[n] = sum(spectrum[k] * cos(2π / N * (n + 1/2 + N/4) * (k + 1/2)))`.
+    ///
+    /// ## Parameters
+    /// - `spectrum`: N/2 coefficients
+    /// - `fft`: Scratch buffer of N/4 values
+    /// - `dct`: Scratch buffer of N/2 values
+    /// - `output`: N samples
+    fn inverse(self: Imdct, spectrum: []const f32, fft: []Complex, dct: []f32, output: []f32) void {
+        const m: usize = self.size / 2;
+        const h: usize = self.size / 4;
+
+        // A DCT-IV of the spectrum, from a complex FFT of its even and reversed odd coefficients
+        for (0..h) |j| {
+            fft[self.bit_reverse[j]] = Complex.init(spectrum[2 * j], spectrum[m - 1 - 2 * j]).mul(self.pre_twiddles[j]);
+        }
+
+        var size: usize = 2;
+        while (size <= h) : (size *= 2) {
+            const half: usize = size / 2;
+            const step: usize = h / size;
+            var start: usize = 0;
+            while (start < h) : (start += size) {
+                for (0..half) |k| {
+                    const u: Complex = fft[start + k];
+                    const v: Complex = fft[start + k + half].mul(self.roots[k * step]);
+                    fft[start + k] = u.add(v);
+                    fft[start + k + half] = u.sub(v);
+                }
+            }
+        }
+
+        for (0..h) |k| {
+            const value: Complex = fft[k].mul(self.post_twiddles[k]);
+            dct[2 * k] = value.re;
+            dct[m - 1 - 2 * k] = -value.im;
+        }
+
+        // The IMDCT is the DCT-IV, unfolded with its symmetries
+        for (output, 0..) |*sample, n| {
+            const i: usize = n + m / 2;
+            sample.* = if (i < m) dct[i] else if (i < 2 * m) -dct[2 * m - 1 - i] else -dct[i - 2 * m];
+        }
+    }
+};
+
+const Decoder = struct {
+    identification: Identification,
+    setup: Setup,
+    imdcts: [2]Imdct,
+    /// Rising half of the window for short and long overlaps
+    slopes: [2][]const f32,
+
+    /// Windowed samples of the previous block, by channel
+    previous: []const []f32,
+    previous_size: ?usize,
+    current: []const []f32,
+    spectra: []const []f32,
+    floor_curve: []f32,
+    floor_ys: [][max_floor_values]u32,
+    floor_used: [][max_floor_values]bool,
+    channel_used: []bool,
+    residue_scratch: []f32,
+    classes: []u8,
+    fft: []Complex,
+    dct: []f32,
+
+    /// Samples completed by the last packet, by channel
+    outputs: []const []f32,
+    frames: usize,
+
+    fn init(allocator: std.mem.Allocator, identification: Identification, setup: Setup) !Decoder {
+        const channels: usize = identification.channels;
+        const long_size: usize = identification.block_sizes[1];
+
+        var slopes: [2][]const f32 = undefined;
+        for (&slopes, identification.block_sizes) |*slope, block_size| {
+            const values: []f32 = try allocator.alloc(f32, block_size / 2);
+            for (values, 0..) |*value, i| {
+                const x: f64 = (@as(f64, @floatFromInt(i)) + 0.5) / @as(f64, @floatFromInt(values.len)) * std.math.pi / 2.0;
+                value.* = @floatCast(@sin(std.math.pi / 2.0 * @sin(x) * @sin(x)));
+            }
+            slope.* = values;
+        }
+
+        var max_partitions: usize = 0;
+        for (setup.residues) |residue| {
+            const size: usize = @min(residue.end, long_size / 2 * channels) -| residue.begin;
+            max_partitions = @max(max_partitions, size / residue.partition_size + setup.codebooks[residue.classbook].dimensions);
+        }
+
+        return .{
+            .identification = identification,
+            .setup = setup,
+            .imdcts = .{
+                try .init(allocator, identification.block_sizes[0]),
+                try .init(allocator, identification.block_sizes[1]),
+            },
+            .slopes = slopes,
+            .previous = try allocChannels(allocator, channels, long_size),
+            .previous_size = null,
+            .current = try allocChannels(allocator, channels, long_size),
+            .spectra = try allocChannels(allocator, channels, long_size / 2),
+            .floor_curve = try allocator.alloc(f32, long_size / 2),
+            .floor_ys = try allocator.alloc([max_floor_values]u32, channels),
+            .floor_used = try allocator.alloc([max_floor_values]bool, channels),
+            .channel_used = try allocator.alloc(bool, channels),
+            .residue_scratch = try allocator.alloc(f32, long_size / 2 * channels),
+            .classes = try allocator.alloc(u8, max_partitions * channels),
+            .fft = try allocator.alloc(Complex, long_size / 4),
+            .dct = try allocator.alloc(f32, long_size / 2),
+            .outputs = try allocChannels(allocator, channels, long_size),
+            .frames = 0,
+        };
+    }
+
+    fn allocChannels(allocator: std.mem.Allocator, channels: usize, size: usize) ![]const []f32 {
+        const result: [][]f32 = try allocator.alloc([]f32, channels);
+        for (result) |*channel| {
+            channel.* = try allocator.alloc(f32, size);
+            @memset(channel.*, 0.0);
+        }
+        return result;
+    }
+
+    /// Decodes an audio packet, leaving the samples it completes in `outputs[ch][0..frames]`.
+    fn decodePacket(self: *Decoder, packet: []const u8) !void {
+        self.frames = 0;
+
+        var reader: BitReader = .{ .bytes = packet };
+        const header = self.decodeHeader(&reader) catch return;
+        const n: usize = self.identification.block_sizes[@intFromBool(header.mode.long_block)];
+        const mapping: Mapping = self.setup.mappings[header.mode.mapping];
+        const channels: usize = self.identification.channels;
+
+        for (0..channels) |ch| {
+            const floor: Floor = self.setup.floors[mapping.submap_floors[mapping.mux[ch]]];
+            self.channel_used[ch] = floor.decode(&reader, self.setup.codebooks, &self.floor_ys[ch], &self.floor_used[ch]) catch false;
+        }
+
+        // Coupled channels are decoded together if either one is used
+        var no_residue_buffer: [256]bool = undefined;
+        const no_residue: []bool = no_residue_buffer[0..channels];
+        for (no_residue, self.channel_used) |*no, used| {
+            no.* = !used;
+        }
+        for (mapping.magnitudes, mapping.angles) |magnitude, angle| {
+            if (!no_residue[magnitude] or !no_residue[angle]) {
+                no_residue[magnitude] = false;
+                no_residue[angle] = false;
+            }
+        }
+
+        for (mapping.submap_residues, 0..) |residue_index, submap| {
+            var vectors_buffer: [256][]f32 = undefined;
+            var do_not_decode_buffer: [256]bool = undefined;
+            var count: usize = 0;
+            for (0..channels) |ch| {
+                if (mapping.mux[ch] != submap)
+                    continue;
+                vectors_buffer[count] = self.spectra[ch][0 .. n / 2];
+                @memset(vectors_buffer[count], 0.0);
+                do_not_decode_buffer[count] = no_residue[ch];
+                count += 1;
+            }
+            if (count == 0)
+                continue;
+
+            self.setup.residues[residue_index].decode(
+                &reader,
+                self.setup.codebooks,
+                vectors_buffer[0..count],
+                do_not_decode_buffer[0..count],
+                self.residue_scratch,
+                self.classes,
+            );
+        }
+
+        // Inverse square polar coupling, in reverse order
+        var step: usize = mapping.magnitudes.len;
+        while (step > 0) {
+            step -= 1;
+            const magnitudes: []f32 = self.spectra[mapping.magnitudes[step]][0 .. n / 2];
+            const angles: []f32 = self.spectra[mapping.angles[step]][0 .. n / 2];
+            for (magnitudes, angles) |*magnitude, *angle| {
+                const m: f32 = magnitude.*;
+                const a: f32 = angle.*;
+                if (m > 0) {
+                    if (a > 0) {
+                        angle.* = m - a;
+                    } else {
+                        angle.* = m;
+                        magnitude.* = m + a;
+                    }
+                } else {
+                    if (a > 0) {
+                        angle.* = m + a;
+                    } else {
+                        angle.* = m;
+                        magnitude.* = m - a;
+                    }
+                }
+            }
+        }
+
+        const imdct: Imdct = self.imdcts[@intFromBool(header.mode.long_block)];
+        for (0..channels) |ch| {
+            const spectrum: []f32 = self.spectra[ch][0 .. n / 2];
+            const current: []f32 = self.current[ch][0..n];
+
+            if (!self.channel_used[ch]) {
+                @memset(current, 0.0);
+                continue;
+            }
+
+            const floor: Floor = self.setup.floors[mapping.submap_floors[mapping.mux[ch]]];
+            floor.render(&self.floor_ys[ch], &self.floor_used[ch], self.floor_curve[0 .. n / 2]);
+            for (spectrum, self.floor_curve[0 .. n / 2]) |*value, amplitude| {
+                value.* *= amplitude;
+            }
+
+            imdct.inverse(spectrum, self.fft, self.dct, current);
+            self.applyWindow(current, header.mode.long_block, header.previous_long, header.next_long);
+        }
+
+        // Overlap the right half of the previous block with the left half of this one
+        if (self.previous_size) |previous_size| {
+            self.frames = previous_size / 4 + n / 4;
+            for (0..channels) |ch| {
+                for (0..self.frames) |i| {
+                    const p: usize = previous_size / 2 + i;
+                    const q: isize = @as(isize, @intCast(n / 4 + i)) - @as(isize, @intCast(previous_size / 4));
+                    const from_previous: f32 = if (p < previous_size) self.previous[ch][p] else 0.0;
+                    const from_current: f32 = if (q >= 0) self.current[ch][@intCast(q)] else 0.0;
+                    self.outputs[ch][i] = from_previous + from_current;
+                }
+            }
+        }
+
+        std.mem.swap([]const []f32, &self.previous, &self.current);
+        self.previous_size = n;
+    }
+
+    const PacketHeader = struct {
+        mode: Mode,
+        previous_long: bool,
+        next_long: bool,
+    };
+
+    fn decodeHeader(self: Decoder, reader: *BitReader) !PacketHeader {
+        // Not an audio packet
+        if (try reader.readFlag())
+            return error.InvalidVorbisStream;
+
+        const mode_number: u32 = try reader.readBits(ilog(@intCast(self.setup.modes.len - 1)));
+        if (mode_number >= self.setup.modes.len)
+            return error.InvalidVorbisStream;
+
+        const mode: Mode = self.setup.modes[mode_number];
+        var header: PacketHeader = .{ .mode = mode, .previous_long = false, .next_long = false };
+        if (mode.long_block) {
+            header.previous_long = try reader.readFlag();
+            header.next_long = try reader.readFlag();
+        }
+        return header;
+    }
+
+    /// Multiplies a block by its window, whose slopes are short next to short blocks.
+    fn applyWindow(self: Decoder, block: []f32, long_block: bool, previous_long: bool, next_long: bool) void {
+        const n: usize = block.len;
+        const short_size: usize = self.identification.block_sizes[0];
+
+        const left_short: bool = long_block and !previous_long;
+        const right_short: bool = long_block and !next_long;
+        const left_start: usize = if (left_short) n / 4 - short_size / 4 else 0;
+        const left_end: usize = if (left_short) n / 4 + short_size / 4 else n / 2;
+        const right_start: usize = if (right_short) n * 3 / 4 - short_size / 4 else n / 2;
+        const right_end: usize = if (right_short) n * 3 / 4 + short_size / 4 else n;
+
+        const left_slope: []const f32 = self.slopes[@intFromBool(long_block and !left_short)];
+        const right_slope: []const f32 = self.slopes[@intFromBool(long_block and !right_short)];
+
+        @memset(block[0..left_start], 0.0);
+        for (block[left_start..left_end], left_slope) |*sample, w| {
+            sample.* *= w;
+        }
+        for (block[right_start..right_end], 0..) |*sample, i| {
+            sample.* *= right_slope[right_slope.len - 1 - i];
+        }
+        @memset(block[right_end..], 0.0);
+    }
+};
+
+const floor1_inverse_db_table = [256]f32{
+    1.0649863e-07, 1.1341951e-07, 1.2079015e-07, 1.2863978e-07,
+    1.3699951e-07, 1.4590251e-07, 1.5538408e-07, 1.6548181e-07,
+    1.7623575e-07, 1.8768855e-07, 1.9988561e-07, 2.1287530e-07,
+    2.2670913e-07, 2.4144197e-07, 2.5713223e-07, 2.7384213e-07,
+    2.9163793e-07, 3.1059021e-07, 3.3077411e-07, 3.5226968e-07,
+    3.7516214e-07, 3.9954229e-07, 4.2550680e-07, 4.5315863e-07,
+    4.8260743e-07, 5.1396998e-07, 5.4737065e-07, 5.8294187e-07,
+    6.2082472e-07, 6.6116941e-07, 7.0413592e-07, 7.4989464e-07,
+    7.9862701e-07, 8.5052630e-07, 9.0579828e-07, 9.6466216e-07,
+    1.0273513e-06, 1.0941144e-06, 1.1652161e-06, 1.2409384e-06,
+    1.3215816e-06, 1.4074654e-06, 1.4989305e-06, 1.5963394e-06,
+    1.7000785e-06, 1.8105592e-06, 1.9282195e-06, 2.0535261e-06,
+    2.1869758e-06, 2.3290978e-06, 2.4804557e-06, 2.6416497e-06,
+    2.8133190e-06, 2.9961443e-06, 3.1908506e-06, 3.3982101e-06,
+    3.6190449e-06, 3.8542308e-06, 4.1047004e-06, 4.3714470e-06,
+    4.6555282e-06, 4.9580707e-06, 5.2802740e-06, 5.6234160e-06,
+    5.9888572e-06, 6.3780469e-06, 6.7925283e-06, 7.2339451e-06,
+    7.7040476e-06, 8.2047000e-06, 8.7378876e-06, 9.3057248e-06,
+    9.9104632e-06, 1.0554501e-05, 1.1240392e-05, 1.1970856e-05,
+    1.2748789e-05, 1.3577278e-05, 1.4459606e-05, 1.5399272e-05,
+    1.6400004e-05, 1.7465768e-05, 1.8600792e-05, 1.9809576e-05,
+    2.1096914e-05, 2.2467911e-05, 2.3928002e-05, 2.5482978e-05,
+    2.7139006e-05, 2.8902651e-05, 3.0780908e-05, 3.2781225e-05,
+    3.4911534e-05, 3.7180282e-05, 3.9596466e-05, 4.2169667e-05,
+    4.4910090e-05, 4.7828601e-05, 5.0936773e-05, 5.4246931e-05,
+    5.7772202e-05, 6.1526565e-05, 6.5524908e-05, 6.9783085e-05,
+    7.4317983e-05, 7.9147585e-05, 8.4291040e-05, 8.9768747e-05,
+    9.5602426e-05, 0.00010181521, 0.00010843174, 0.00011547824,
+    0.00012298267, 0.00013097477, 0.00013948625, 0.00014855085,
+    0.00015820453, 0.00016848555, 0.00017943469, 0.00019109536,
+    0.00020351382, 0.00021673929, 0.00023082423, 0.00024582449,
+    0.00026179955, 0.00027881276, 0.00029693158, 0.00031622787,
+    0.00033677814, 0.00035866388, 0.00038197188, 0.00040679456,
+    0.00043323036, 0.00046138411, 0.00049136745, 0.00052329927,
+    0.00055730621, 0.00059352311, 0.00063209358, 0.00067317058,
+    0.00071691700, 0.00076350630, 0.00081312324, 0.00086596457,
+    0.00092223983, 0.00098217216, 0.0010459992,  0.0011139742,
+    0.0011863665,  0.0012634633,  0.0013455702,  0.0014330129,
+    0.0015261382,  0.0016253153,  0.0017309374,  0.0018434235,
+    0.0019632195,  0.0020908006,  0.0022266726,  0.0023713743,
+    0.0025254795,  0.0026895994,  0.0028643847,  0.0030505286,
+    0.0032487691,  0.0034598925,  0.0036847358,  0.0039241906,
+    0.0041792066,  0.0044507950,  0.0047400328,  0.0050480668,
+    0.0053761186,  0.0057254891,  0.0060975636,  0.0064938176,
+    0.0069158225,  0.0073652516,  0.0078438871,  0.0083536271,
+    0.0088964928,  0.009474637,   0.010090352,   0.010746080,
+    0.011444421,   0.012188144,   0.012980198,   0.013823725,
+    0.014722068,   0.015678791,   0.016697687,   0.017782797,
+    0.018938423,   0.020169149,   0.021479854,   0.022875735,
+    0.024362330,   0.025945531,   0.027631618,   0.029427276,
+    0.031339626,   0.033376252,   0.035545228,   0.037855157,
+    0.040315199,   0.042935108,   0.045725273,   0.048696758,
+    0.051861348,   0.055231591,   0.058820850,   0.062643361,
+    0.066714279,   0.071049749,   0.075666962,   0.080584227,
+    0.085821044,   0.091398179,   0.097337747,   0.10366330,
+    0.11039993,    0.11757434,    0.12521498,    0.13335215,
+    0.14201813,    0.15124727,    0.16107617,    0.17154380,
+    0.18269168,    0.19456402,    0.20720788,    0.22067342,
+    0.23501402,    0.25028656,    0.26655159,    0.28387361,
+    0.30232132,    0.32196786,    0.34289114,    0.36517414,
+    0.38890521,    0.41417847,    0.44109412,    0.46975890,
+    0.50028648,    0.53279791,    0.56742212,    0.60429640,
+    0.64356699,    0.68538959,    0.72993007,    0.77736504,
+    0.82788260,    0.88168307,    0.9389798,     1.0,
+};
+
+/// Builds an Ogg stream with one single-segment packet per page.
+fn testStream(allocator: std.mem.Allocator, pages: []const []const u8) ![]u8 {
+    var bytes: std.array_list.Aligned(u8, null) = .empty;
+    errdefer bytes.deinit(allocator);
+
+    for (pages, 0..) |body, sequence| {
+        var header: [28]u8 = undefined;
+        @memcpy(header[0..4], "OggS");
+        header[4] = 0;
+        header[5] = if (sequence == 0) 0x02 else if (sequence == pages.len - 1) 0x04 else 0x00;
+        std.mem.writeInt(u64, header[6..14], 0, .little);
+        std.mem.writeInt(u32, header[14..18], 1, .little);
+        std.mem.writeInt(u32, header[18..22], @intCast(sequence), .little);
+        std.mem.writeInt(u32, header[22..26], 0, .little);
+        header[26] = 1;
+        header[27] = @intCast(body.len);
+
+        const crc: u32 = oggCrc(oggCrc(0, &header), body);
+        std.mem.writeInt(u32, header[22..26], crc, .little);
+        try bytes.appendSlice(allocator, &header);
+        try bytes.appendSlice(allocator, body);
+    }
+
+    return bytes.toOwnedSlice(allocator);
+}
+
+test "Ogg CRC" {
+    // The CRC of a page is computed with its CRC field set to zero
+    try testing.expectEqual(oggCrc(0, ""), 0);
+    try testing.expectEqual(oggCrc(0, "OggS"), 0x5FB0A94F);
+    try testing.expectEqual(oggCrc(oggCrc(0, "Og"), "gS"), oggCrc(0, "OggS"));
+}
+
+test "Huffman codewords" {
+    const allocator = testing.allocator;
+    var arena_state = std.heap.ArenaAllocator.init(allocator);
+    defer arena_state.deinit();
+
+    // The example of the Vorbis I specification
+    var codebook: Codebook = .{
+        .dimensions = 1,
+        .lengths = &.{ 2, 4, 4, 4, 4, 2, 3, 3 },
+        .tree = &.{},
+        .single_entry = null,
+        .lookup_type = 0,
+        .values = &.{},
+        .lookup_values = 0,
+        .sequence_p = false,
+    };
+    try codebook.buildTree(arena_state.allocator());
+
+    // Entries 0, 1, 5 and 7: codewords 00, 0100, 10 and 111, packed from the least significant bit
+    const bits = [_]u8{ 0b0100_1000, 0b0000_0111 };
+    var reader: BitReader = .{ .bytes = &bits };
+    try testing.expectEqual(try codebook.decodeEntry(&reader), 0);
+    try testing.expectEqual(try codebook.decodeEntry(&reader), 1);
+    try testing.expectEqual(try codebook.decodeEntry(&reader), 5);
+    try testing.expectEqual(try codebook.decodeEntry(&reader), 7);
+
+    var overspecified: Codebook = codebook;
+    overspecified.lengths = &.{ 1, 1, 1 };
+    try testing.expectError(error.InvalidVorbisStream, overspecified.buildTree(arena_state.allocator()));
+}
+
+test "reject codebooks larger than their packet" {
+    const allocator = testing.allocator;
+
+    // 100000 unordered entries, whose lengths would take 500000 bits, in a 9-byte packet
+    var unordered: BitReader = .{ .bytes = &[_]u8{ 0x42, 0x43, 0x56, 0x01, 0x00, 0xA0, 0x86, 0x01, 0x00 } };
+    try testing.expectError(error.InvalidVorbisStream, Codebook.parse(allocator, &unordered));
+
+    // 2^24 - 1 ordered entries, which a few bits could describe
+    var ordered: BitReader = .{ .bytes = &[_]u8{ 0x42, 0x43, 0x56, 0x01, 0x00, 0xFF, 0xFF, 0xFF, 0x01 } };
+    try testing.expectError(error.InvalidVorbisStream, Codebook.parse(allocator, &ordered));
+}
+
+test "codebook values" {
+    try testing.expectEqual(float32Unpack(0x80000000 | (772 << 21) | 983040), -15.0);
+    try testing.expectEqual(float32Unpack((768 << 21) | 1048576), 1.0);
+
+    try testing.expectEqual(lookup1Values(961, 2), 31);
+    try testing.expectEqual(lookup1Values(960, 2), 30);
+    try testing.expectEqual(lookup1Values(81, 4), 3);
+    try testing.expectEqual(lookup1Values(5, 1), 5);
+}
+
+test "inverse MDCT" {
+    const allocator = testing.allocator;
+    var arena_state = std.heap.ArenaAllocator.init(allocator);
+    defer arena_state.deinit();
+    const arena = arena_state.allocator();
+
+    const size = 64;
+    const imdct: Imdct = try .init(arena, size);
+
+    var spectrum: [size / 2]f32 = undefined;
+    var prng = std.Random.DefaultPrng.init(0);
+    for (&spectrum) |*value| {
+        value.* = prng.random().float(f32) * 2.0 - 1.0;
+    }
+
+    var fft: [size / 4]Complex = undefined;
+    var dct: [size / 2]f32 = undefined;
+    var output: [size]f32 = undefined;
+    imdct.inverse(&spectrum, &fft, &dct, &output);
+
+    for (output, 0..) |actual, n| {
+        var expected: f64 = 0.0;
+        for (spectrum, 0..) |value, k| {
+            const phase: f64 = 2.0 * std.math.pi / size * (@as(f64, @floatFromInt(n)) + 0.5 + size / 4.0) * (@as(f64, @floatFromInt(k)) + 0.5);
+            expected += value * @cos(phase);
+        }
+        try testing.expectApproxEqAbs(expected, actual, 0.0001);
+    }
+}
+
+test "floor lines" {
+    var output: [8]f32 = undefined;
+    renderLine(0, 0, 8, 255, &output);
+    try testing.expectEqual(output[0], floor1_inverse_db_table[0]);
+    try testing.expectEqual(output[7], floor1_inverse_db_table[223]);
+
+    try testing.expectEqual(renderPoint(0, 10, 10, 20, 5), 15);
+    try testing.expectEqual(renderPoint(0, 20, 10, 10, 3), 17);
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
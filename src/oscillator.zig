@@ -0,0 +1,456 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+const Error = @import("./error.zig").Error;
+
+/// Oscillator type function: Creates an Oscillator type for the specified sample type.
+///
+/// Oscillator generates periodic waveforms (sine, square, sawtooth, triangle and pulse)
+/// as `Wave(T)` instances, so callers don't have to write their own sample loops.
+/// Square, sawtooth and pulse waves can be band-limited with PolyBLEP to reduce aliasing.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Oscillator = lightmix.Oscillator;
+/// const wave = try Oscillator(f64).sine(allocator, .{
+///     .frequency = 440.0,
+///     .amplitude = 0.5,
+///     .duration = 1.0,
+///     .sample_rate = 44100,
+///     .channels = 1,
+/// });
+/// defer wave.deinit();
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// Waveform shapes supported by the oscillator.
+        pub const Shape = enum {
+            sine,
+            square,
+            sawtooth,
+            triangle,
+            pulse,
+        };
+
+        /// Options for generating a periodic wave.
+        pub const Options = struct {
+            /// Frequency of the wave in Hz
+            frequency: T,
+            /// Peak amplitude of the wave
+            amplitude: T = 1.0,
+            /// Initial phase, normalized to one cycle (0.0 to 1.0)
+            phase: T = 0.0,
+            /// Length of the wave in seconds
+            duration: T,
+            /// Samples per second
+            sample_rate: u32,
+            /// Number of channels. Every channel receives the same signal.
+            channels: u16 = 1,
+            /// Ratio of the high part of one cycle (0.0 to 1.0). Only used by `.pulse`.
+            pulse_width: T = 0.5,
+            /// Whether to smooth the discontinuities of square, sawtooth and pulse waves
+            /// with PolyBLEP, which removes most of the aliasing of high-pitched waves.
+            /// Sine and triangle waves are not affected.
+            band_limited: bool = false,
+        };
+
+        /// Generates a wave with the given shape.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for the sample data
+        /// - `shape`: The waveform shape to generate
+        /// - `options`: Frequency, amplitude, phase, duration and format of the wave
+        ///
+        /// ## Returns
+        /// A new Wave containing `round(duration * sample_rate)` frames
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The duration is negative, NaN or too long to allocate
+        /// - Allocator error (errors.OutOfMemory)
+        pub fn generate(
+            allocator: std.mem.Allocator,
+            shape: Shape,
+            options: Options,
+        ) (Error || std.mem.Allocator.Error)!Wave(T) {
+            const frames: usize = try frameCount(options.duration, options.sample_rate);
+            const channels: usize = options.channels;
+            const phase_increment: T = options.frequency / @as(T, @floatFromInt(options.sample_rate));
+
+            const samples: []T = try allocator.alloc(T, std.math.mul(usize, frames, channels) catch return error.InvalidRange);
+
+            for (0..frames) |i| {
+                const phase: T = phaseAt(options, i);
+                const shaped: T = if (options.band_limited)
+                    bandLimitedValueAt(shape, phase, phase_increment, options.pulse_width)
+                else
+                    valueAt(shape, phase, options.pulse_width);
+                const value: T = options.amplitude * shaped;
+
+                for (0..channels) |ch| {
+                    samples[i * channels + ch] = value;
+                }
+            }
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = allocator,
+
+                .sample_rate = options.sample_rate,
+                .channels = options.channels,
+            };
+        }
+
+        /// Generates a sine wave. See `generate`.
+        pub fn sine(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .sine, options);
+        }
+
+        /// Generates a square wave with a 50% duty cycle. See `generate`.
+        pub fn square(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .square, options);
+        }
+
+        /// Generates a sawtooth wave rising from -1 to +1. See `generate`.
+        pub fn sawtooth(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .sawtooth, options);
+        }
+
+        /// Generates a triangle wave. See `generate`.
+        pub fn triangle(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .triangle, options);
+        }
+
+        /// Generates a pulse wave using `options.pulse_width` as its duty cycle. See `generate`.
+        pub fn pulse(allocator: std.mem.Allocator, options: Options) (Error || std.mem.Allocator.Error)!Wave(T) {
+            return generate(allocator, .pulse, options);
+        }
+
+        /// Evaluates a waveform shape at a normalized phase.
+        ///
+        /// ## Parameters
+        /// - `shape`: The waveform shape
+        /// - `phase`: Position within one cycle (0.0 to 1.0)
+        /// - `pulse_width`: Duty cycle, only used by `.pulse`
+        ///
+        /// ## Returns
+        /// The sample value in the range -1.0 to +1.0
+        pub fn valueAt(shape: Shape, phase: T, pulse_width: T) T {
+            return switch (shape) {
+                .sine => @sin(phase * 2.0 * std.math.pi),
+                .square => if (phase < 0.5) 1.0 else -1.0,
+                .sawtooth => phase * 2.0 - 1.0,
+                .triangle => if (phase < 0.5)
+                    4.0 * phase - 1.0 // Rising: -1 to +1
+                else
+                    3.0 - 4.0 * phase, // Falling: +1 to -1
+                .pulse => if (phase < pulse_width) 1.0 else -1.0,
+            };
+        }
+
+        /// Evaluates a waveform shape at a normalized phase, with PolyBLEP correction
+        /// applied around the discontinuities of square, sawtooth and pulse waves.
+        ///
+        /// ## Parameters
+        /// - `shape`: The waveform shape
+        /// - `phase`: Position within one cycle (0.0 to 1.0)
+        /// - `phase_increment`: Phase advance per frame (`frequency / sample_rate`)
+        /// - `pulse_width`: Duty cycle, only used by `.pulse`
+        ///
+        /// ## Returns
+        /// The sample value, roughly in the range -1.0 to +1.0
+        pub fn bandLimitedValueAt(shape: Shape, phase: T, phase_increment: T, pulse_width: T) T {
+            return switch (shape) {
+                .sine, .triangle => valueAt(shape, phase, pulse_width),
+                .sawtooth => valueAt(.sawtooth, phase, pulse_width) - polyBlep(phase, phase_increment),
+                .square => bandLimitedPulse(phase, phase_increment, 0.5),
+                .pulse => bandLimitedPulse(phase, phase_increment, pulse_width),
+            };
+        }
+
+        fn bandLimitedPulse(phase: T, phase_increment: T, width: T) T {
+            const naive: T = if (phase < width) 1.0 else -1.0;
+
+            // Rising edge at phase 0.0, falling edge at phase `width`
+            return naive + polyBlep(phase, phase_increment) - polyBlep(@mod(phase + 1.0 - width, 1.0), phase_increment);
+        }
+
+        /// Two-sample polynomial approximation of a band-limited step (PolyBLEP).
+        ///
+        /// Returns the correction for a step from -1 to +1 located at phase 0.0.
+        fn polyBlep(phase: T, phase_increment: T) T {
+            if (phase < phase_increment) {
+                const x: T = phase / phase_increment;
+                return x + x - x * x - 1.0;
+            }
+
+            if (phase > 1.0 - phase_increment) {
+                const x: T = (phase - 1.0) / phase_increment;
+                return x * x + x + x + 1.0;
+            }
+
+            return 0.0;
+        }
+
+        /// Returns the normalized phase (0.0 to 1.0) of the frame at `index`.
+        ///
+        /// The phase is computed from the frame index instead of being accumulated,
+        /// so rounding errors don't build up over long waves.
+        pub fn phaseAt(options: Options, index: usize) T {
+            const t: T = @as(T, @floatFromInt(index)) / @as(T, @floatFromInt(options.sample_rate));
+            return @mod(options.phase + options.frequency * t, 1.0);
+        }
+
+        /// Returns the number of frames needed for `duration` seconds at `sample_rate`.
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The duration is negative or NaN, or the frame count doesn't fit a `usize`
+        pub fn frameCount(duration: T, sample_rate: u32) error{InvalidRange}!usize {
+            const frames: T = @round(duration * @as(T, @floatFromInt(sample_rate)));
+            // Fails on NaN too, as every comparison with it is false
+            if (!(frames >= 0.0 and frames < @as(T, @floatFromInt(std.math.maxInt(usize)))))
+                return error.InvalidRange;
+
+            return @intFromFloat(frames);
+        }
+
+        test "sine" {
+            const allocator = testing.allocator;
+            const wave = try Self.sine(allocator, .{
+                .frequency = 440.0,
+                .amplitude = 0.5,
+                .duration = 1.0,
+                .sample_rate = 44100,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.sample_rate, 44100);
+            try testing.expectEqual(wave.channels, 1);
+            try testing.expectEqual(wave.samples.len, 44100);
+
+            // Same values as the generator used by Wave's tests
+            try testing.expectApproxEqAbs(wave.samples[0], 0.0, 0.00001);
+            try testing.expectApproxEqAbs(wave.samples[1], 0.031324162089371846, 0.00001);
+            try testing.expectApproxEqAbs(wave.samples[2], 0.06252526184726405, 0.00001);
+        }
+
+        test "phase offset" {
+            const allocator = testing.allocator;
+            const wave = try Self.sine(allocator, .{
+                .frequency = 1.0,
+                .phase = 0.25,
+                .duration = 1.0,
+                .sample_rate = 4,
+            });
+            defer wave.deinit();
+
+            try testing.expectApproxEqAbs(wave.samples[0], 1.0, 0.00001);
+            try testing.expectApproxEqAbs(wave.samples[1], 0.0, 0.00001);
+            try testing.expectApproxEqAbs(wave.samples[2], -1.0, 0.00001);
+            try testing.expectApproxEqAbs(wave.samples[3], 0.0, 0.00001);
+        }
+
+        test "square" {
+            const allocator = testing.allocator;
+            const wave = try Self.square(allocator, .{
+                .frequency = 1.0,
+                .amplitude = 0.5,
+                .duration = 1.0,
+                .sample_rate = 4,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ 0.5, 0.5, -0.5, -0.5 });
+        }
+
+        test "sawtooth" {
+            const allocator = testing.allocator;
+            const wave = try Self.sawtooth(allocator, .{
+                .frequency = 1.0,
+                .duration = 1.0,
+                .sample_rate = 4,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ -1.0, -0.5, 0.0, 0.5 });
+        }
+
+        test "triangle" {
+            const allocator = testing.allocator;
+            const wave = try Self.triangle(allocator, .{
+                .frequency = 1.0,
+                .duration = 1.0,
+                .sample_rate = 4,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ -1.0, 0.0, 1.0, 0.0 });
+        }
+
+        test "pulse" {
+            const allocator = testing.allocator;
+            const wave = try Self.pulse(allocator, .{
+                .frequency = 1.0,
+                .duration = 1.0,
+                .sample_rate = 4,
+                .pulse_width = 0.25,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ 1.0, -1.0, -1.0, -1.0 });
+        }
+
+        test "channels receive the same signal" {
+            const allocator = testing.allocator;
+            const wave = try Self.sawtooth(allocator, .{
+                .frequency = 1.0,
+                .duration = 1.0,
+                .sample_rate = 4,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.channels, 2);
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ -1.0, -1.0, -0.5, -0.5, 0.0, 0.0, 0.5, 0.5 });
+        }
+
+        test "band_limited doesn't change sine and triangle" {
+            const allocator = testing.allocator;
+            const naive = try Self.triangle(allocator, .{
+                .frequency = 1000.0,
+                .duration = 0.01,
+                .sample_rate = 44100,
+            });
+            defer naive.deinit();
+
+            const band_limited = try Self.triangle(allocator, .{
+                .frequency = 1000.0,
+                .duration = 0.01,
+                .sample_rate = 44100,
+                .band_limited = true,
+            });
+            defer band_limited.deinit();
+
+            try testing.expectEqualSlices(T, naive.samples, band_limited.samples);
+        }
+
+        test "band_limited reduces aliasing of sawtooth" {
+            const allocator = testing.allocator;
+            const options: Options = .{
+                .frequency = 2999.0,
+                .duration = 1.0,
+                .sample_rate = 44100,
+            };
+
+            const naive = try Self.sawtooth(allocator, options);
+            defer naive.deinit();
+
+            var band_limited_options = options;
+            band_limited_options.band_limited = true;
+            const band_limited = try Self.sawtooth(allocator, band_limited_options);
+            defer band_limited.deinit();
+
+            try testing.expect(aliasingEnergyRatio(naive.samples, 2999, 44100) > 0.05);
+            try testing.expect(aliasingEnergyRatio(band_limited.samples, 2999, 44100) < 0.005);
+        }
+
+        test "band_limited reduces aliasing of square and pulse" {
+            const allocator = testing.allocator;
+
+            for ([_]T{ 0.5, 0.25 }) |pulse_width| {
+                const options: Options = .{
+                    .frequency = 2999.0,
+                    .duration = 1.0,
+                    .sample_rate = 44100,
+                    .pulse_width = pulse_width,
+                };
+
+                const naive = try Self.pulse(allocator, options);
+                defer naive.deinit();
+
+                var band_limited_options = options;
+                band_limited_options.band_limited = true;
+                const band_limited = try Self.pulse(allocator, band_limited_options);
+                defer band_limited.deinit();
+
+                try testing.expect(aliasingEnergyRatio(naive.samples, 2999, 44100) > 0.03);
+                try testing.expect(aliasingEnergyRatio(band_limited.samples, 2999, 44100) < 0.002);
+            }
+        }
+
+        /// Returns the ratio of signal energy which doesn't belong to DC or
+        /// to a harmonic of `frequency` below the Nyquist frequency.
+        ///
+        /// `samples` must be exactly one second long, so every DFT bin is 1 Hz wide
+        /// and each harmonic of an integer `frequency` falls on a single bin.
+        fn aliasingEnergyRatio(samples: []const T, frequency: usize, sample_rate: usize) T {
+            const n: T = @floatFromInt(samples.len);
+
+            var total: T = 0.0;
+            var sum: T = 0.0;
+            for (samples) |sample| {
+                total += sample * sample;
+                sum += sample;
+            }
+
+            // DC component
+            var harmonic: T = sum * sum / n;
+
+            // Goertzel algorithm for every harmonic below the Nyquist frequency
+            var bin: usize = frequency;
+            while (bin * 2 < sample_rate) : (bin += frequency) {
+                const coefficient: T = 2.0 * @cos(2.0 * std.math.pi * @as(T, @floatFromInt(bin)) / n);
+                var s1: T = 0.0;
+                var s2: T = 0.0;
+                for (samples) |sample| {
+                    const s0: T = sample + coefficient * s1 - s2;
+                    s2 = s1;
+                    s1 = s0;
+                }
+                const power: T = s1 * s1 + s2 * s2 - coefficient * s1 * s2;
+
+                // Both the positive and the negative frequency bin
+                harmonic += 2.0 * power / n;
+            }
+
+            return (total - harmonic) / total;
+        }
+
+        test "zero duration" {
+            const allocator = testing.allocator;
+            const wave = try Self.sine(allocator, .{
+                .frequency = 440.0,
+                .duration = 0.0,
+                .sample_rate = 44100,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.samples.len, 0);
+        }
+
+        test "invalid durations" {
+            const allocator = testing.allocator;
+            for ([_]T{ -1.0, std.math.nan(T), std.math.inf(T), 1e30 }) |duration| {
+                try testing.expectError(error.InvalidRange, Self.sine(allocator, .{
+                    .frequency = 440.0,
+                    .duration = duration,
+                    .sample_rate = 44100,
+                }));
+            }
+
+            try testing.expectEqual(try Self.frameCount(0.5, 44100), 22050);
+            try testing.expectError(error.InvalidRange, Self.frameCount(-0.5, 44100));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
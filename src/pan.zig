@@ -0,0 +1,217 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+const Parameter = @import("./root.zig").Parameter;
+
+/// Pan type function: Creates a Pan filter type for the specified sample type.
+///
+/// Pan positions a mono wave in a stereo field, turning it into a two-channel wave.
+/// The position goes from -1.0 (left) through 0.0 (center) to 1.0 (right), and may follow a control wave.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Pan = lightmix.Pan;
+///
+/// // Slightly to the left
+/// try wave.filter_with(Pan(f64).Options, Pan(f64).filter, .{
+///     .position = .{ .constant = -0.3 },
+/// });
+///
+/// // Auto-pan driven by a control wave
+/// try wave.filter_with(Pan(f64).Options, Pan(f64).filter, .{
+///     .position = .{ .control = lfo_wave },
+///     .law = .linear,
+/// });
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// How the level of each channel follows the position.
+        pub const Law = enum {
+            /// Levels change linearly; the center is 6 dB quieter than the sides
+            linear,
+            /// Sine/cosine levels keeping the total power; the center is 3 dB quieter than the sides
+            constant_power,
+            /// A compromise between the two above; the center is 4.5 dB quieter than the sides
+            compromise,
+        };
+
+        /// Options for panning a wave.
+        pub const Options = struct {
+            /// Position for every frame, from -1.0 (left) to 1.0 (right). Values outside are clamped.
+            /// Control waves must have as many frames as the filtered wave.
+            position: Parameter(T),
+            /// Pan law
+            law: Law = .constant_power,
+        };
+
+        /// Errors that can occur when panning a wave.
+        pub const PanErrors = error{
+            /// Only mono waves can be panned
+            PanningMultiChannelWave,
+        };
+
+        /// Returns the levels of the left and right channels at a position.
+        ///
+        /// ## Parameters
+        /// - `position`: From -1.0 (left) to 1.0 (right). Values outside are clamped.
+        /// - `law`: The pan law
+        ///
+        /// ## Returns
+        /// The left and right levels, in this order
+        pub fn levels(position: T, law: Law) [2]T {
+            // 0.0 is left, 1.0 is right
+            const x: T = (std.math.clamp(position, -1.0, 1.0) + 1.0) / 2.0;
+
+            const left: T = @cos(x * std.math.pi / 2.0);
+            const right: T = @sin(x * std.math.pi / 2.0);
+
+            return switch (law) {
+                .linear => .{ 1.0 - x, x },
+                .constant_power => .{ left, right },
+                .compromise => .{ @sqrt((1.0 - x) * left), @sqrt(x * right) },
+            };
+        }
+
+        /// A filter function which turns a mono wave into a panned stereo wave.
+        ///
+        /// Use it through `Wave(T).filter_with`.
+        ///
+        /// ## Errors
+        /// - `PanningMultiChannelWave`: The wave is not mono
+        /// - `ControlLengthMismatch`: The control wave doesn't have as many frames as the filtered wave
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn filter(
+            comptime _: type,
+            original_wave: Wave(T),
+            options: Options,
+        ) (PanErrors || Parameter(T).ParameterErrors || std.mem.Allocator.Error)!Wave(T) {
+            if (original_wave.channels != 1)
+                return error.PanningMultiChannelWave;
+
+            try options.position.validate(original_wave.samples.len);
+
+            const samples: []T = try original_wave.allocator.alloc(T, original_wave.samples.len * 2);
+
+            for (original_wave.samples, 0..) |sample, frame| {
+                const gains: [2]T = levels(options.position.at(frame), options.law);
+
+                samples[frame * 2] = sample * gains[0];
+                samples[frame * 2 + 1] = sample * gains[1];
+            }
+
+            return Wave(T){
+                .samples = samples,
+                .allocator = original_wave.allocator,
+
+                .sample_rate = original_wave.sample_rate,
+                .channels = 2,
+            };
+        }
+
+        test "levels at the sides" {
+            for (std.enums.values(Law)) |law| {
+                const left = levels(-1.0, law);
+                try testing.expectApproxEqAbs(left[0], 1.0, 0.000001);
+                try testing.expectApproxEqAbs(left[1], 0.0, 0.000001);
+
+                const right = levels(1.0, law);
+                try testing.expectApproxEqAbs(right[0], 0.0, 0.000001);
+                try testing.expectApproxEqAbs(right[1], 1.0, 0.000001);
+            }
+        }
+
+        test "levels at the center follow the pan law" {
+            const expected = [_]struct { law: Law, decibel: T }{
+                .{ .law = .linear, .decibel = -6.0206 },
+                .{ .law = .constant_power, .decibel = -3.0103 },
+                .{ .law = .compromise, .decibel = -4.5154 },
+            };
+
+            for (expected) |e| {
+                const center = levels(0.0, e.law);
+                try testing.expectEqual(center[0], center[1]);
+
+                const decibel: T = 20.0 * @log(center[0]) / std.math.ln10;
+                try testing.expectApproxEqAbs(decibel, e.decibel, 0.001);
+            }
+        }
+
+        test "constant power keeps the power" {
+            var position: T = -1.0;
+            while (position <= 1.0) : (position += 0.125) {
+                const l = levels(position, .constant_power);
+                try testing.expectApproxEqAbs(l[0] * l[0] + l[1] * l[1], 1.0, 0.000001);
+            }
+        }
+
+        test "pan a mono wave" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 0.5 };
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            try wave.filter_with(Options, Self.filter, .{
+                .position = .{ .constant = 0.5 },
+                .law = .linear,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqual(wave.channels, 2);
+            try testing.expectEqual(wave.sample_rate, 44100);
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ 0.25, 0.75, 0.125, 0.375 });
+        }
+
+        test "pan following a control wave" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 1.0, 1.0 };
+            const positions: []const T = &[_]T{ -1.0, 0.0, 1.0 };
+
+            const control = try Wave(T).init(positions, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer control.deinit();
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            try wave.filter_with(Options, Self.filter, .{
+                .position = .{ .control = control },
+                .law = .linear,
+            });
+            defer wave.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, &[_]T{ 1.0, 0.0, 0.5, 0.5, 0.0, 1.0 });
+        }
+
+        test "pan rejects a stereo wave" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 1.0 };
+
+            var wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            try testing.expectError(error.PanningMultiChannelWave, wave.filter_with(Options, Self.filter, .{
+                .position = .{ .constant = 0.0 },
+            }));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -0,0 +1,116 @@
+const std = @import("std");
+const testing = std.testing;
+const Wave = @import("./root.zig").Wave;
+
+/// Parameter type function: Creates a Parameter type for the specified sample type.
+///
+/// Parameter is a value which is either constant, or changes over time following a control wave.
+/// Filters accept it wherever a setting (cutoff, resonance, gain...) can be modulated.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Parameter = lightmix.Parameter;
+///
+/// // A constant value
+/// const constant: Parameter(f64) = .{ .constant = 1000.0 };
+///
+/// // A value following a control wave, which must have as many frames as the filtered wave
+/// const modulated: Parameter(f64) = .{ .control = cutoff_wave };
+/// ```
+pub fn inner(comptime T: type) type {
+    return union(enum) {
+        /// The same value for every frame
+        constant: T,
+        /// One value per frame, read from the first channel of the wave.
+        /// The wave is not owned by the parameter.
+        control: Wave(T),
+
+        const Self = @This();
+
+        /// Errors that can occur when using a parameter with a wave.
+        pub const ParameterErrors = error{
+            /// The control wave doesn't have as many frames as the filtered wave
+            ControlLengthMismatch,
+        };
+
+        /// Returns the value of the parameter at a frame.
+        ///
+        /// ## Parameters
+        /// - `self`: The parameter
+        /// - `frame`: Frame index; must be lower than the control wave's frame count
+        ///
+        /// ## Returns
+        /// The constant value, or the first channel of the control wave at `frame`
+        pub fn at(self: Self, frame: usize) T {
+            return switch (self) {
+                .constant => |value| value,
+                .control => |wave| wave.samples[frame * wave.channels],
+            };
+        }
+
+        /// Checks that the parameter can be used with a wave of `frames` frames.
+        ///
+        /// ## Errors
+        /// - `ControlLengthMismatch`: The control wave doesn't have exactly `frames` frames
+        pub fn validate(self: Self, frames: usize) ParameterErrors!void {
+            switch (self) {
+                .constant => {},
+                .control => |wave| {
+                    if (wave.channels == 0 or wave.samples.len / wave.channels != frames)
+                        return error.ControlLengthMismatch;
+                },
+            }
+        }
+
+        test "constant" {
+            const parameter: Self = .{ .constant = 0.5 };
+
+            try parameter.validate(100);
+            try testing.expectEqual(parameter.at(0), 0.5);
+            try testing.expectEqual(parameter.at(99), 0.5);
+        }
+
+        test "control" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 10.0, 2.0, 20.0, 3.0, 30.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            const parameter: Self = .{ .control = wave };
+
+            // The first channel is used
+            try parameter.validate(3);
+            try testing.expectEqual(parameter.at(0), 1.0);
+            try testing.expectEqual(parameter.at(1), 2.0);
+            try testing.expectEqual(parameter.at(2), 3.0);
+        }
+
+        test "control with a different length" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 2.0, 3.0 };
+            const wave = try Wave(T).init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            const parameter: Self = .{ .control = wave };
+
+            try testing.expectError(error.ControlLengthMismatch, parameter.validate(4));
+            try testing.expectError(error.ControlLengthMismatch, parameter.validate(2));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
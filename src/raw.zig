@@ -0,0 +1,232 @@
+const std = @import("std");
+const testing = std.testing;
+
+/// Sample encodings of headerless PCM data.
+pub const Encoding = enum {
+    /// Unsigned 8-bit integers, centered on 128
+    @"u8",
+    /// Two's complement 16-bit integers
+    @"i16",
+    /// Two's complement 24-bit integers, packed in 3 bytes
+    @"i24",
+    /// Two's complement 32-bit integers
+    @"i32",
+    /// IEEE 754 single precision floats
+    @"f32",
+    /// IEEE 754 double precision floats
+    @"f64",
+
+    /// Returns the number of bytes of a sample.
+    pub fn size(self: Encoding) usize {
+        return switch (self) {
+            .u8 => 1,
+            .i16 => 2,
+            .i24 => 3,
+            .i32, .f32 => 4,
+            .f64 => 8,
+        };
+    }
+};
+
+/// Raw type function: Creates a headerless PCM codec for the specified sample type.
+///
+/// Raw data holds nothing but interleaved samples, so its encoding, byte order, sample rate
+/// and channel count must be given to read it. Integer samples are scaled to the range
+/// from -1.0 to 1.0, as in WAV files.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// Use it through `Wave(T).LowLevelInterfaces.raw`:
+/// ```zig
+/// const wave = try Wave(f64).readRaw(allocator, &reader, .{
+///     .encoding = .i16,
+///     .sample_rate = 44100,
+///     .channels = 2,
+/// });
+/// try wave.write(.raw, &writer.interface, .{ .encoding = .f32, .endian = .big });
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        const Self = @This();
+
+        /// Options for reading raw data.
+        pub const ReadOptions = struct {
+            /// Sample encoding
+            encoding: Encoding,
+            /// Byte order of the samples
+            endian: std.builtin.Endian = .little,
+            /// Samples per second
+            sample_rate: u32,
+            /// Number of interleaved channels
+            channels: u16,
+        };
+
+        /// Options for writing raw data.
+        pub const WriteOptions = struct {
+            /// Sample encoding
+            encoding: Encoding,
+            /// Byte order of the samples
+            endian: std.builtin.Endian = .little,
+        };
+
+        /// Samples and format of raw data.
+        pub const Decoded = struct {
+            samples: []const T,
+            sample_rate: u32,
+            channels: u16,
+        };
+
+        /// Reads every remaining byte of the reader as raw samples.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for sample data
+        /// - `reader`: A reader interface providing the raw bytes
+        /// - `options`: Sample encoding, byte order, sample rate and channel count
+        ///
+        /// ## Returns
+        /// The decoded samples, owned by the caller, with the sample rate and channel count
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The sample rate or the channel count is 0
+        /// - `LengthMismatch`: The data doesn't hold a whole number of frames
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn read(allocator: std.mem.Allocator, reader: anytype, options: ReadOptions) anyerror!Decoded {
+            if (options.sample_rate == 0 or options.channels == 0)
+                return error.InvalidRange;
+
+            const bytes: []u8 = try reader.allocRemaining(allocator, .unlimited);
+            defer allocator.free(bytes);
+
+            const sample_size: usize = options.encoding.size();
+            if (bytes.len % (sample_size * options.channels) != 0)
+                return error.LengthMismatch;
+
+            const samples: []T = try allocator.alloc(T, bytes.len / sample_size);
+            for (samples, 0..) |*sample, i| {
+                sample.* = decodeSample(bytes[i * sample_size ..][0..sample_size], options.encoding, options.endian);
+            }
+
+            return .{
+                .samples = samples,
+                .sample_rate = options.sample_rate,
+                .channels = options.channels,
+            };
+        }
+
+        fn decodeSample(bytes: []const u8, encoding: Encoding, endian: std.builtin.Endian) T {
+            return switch (encoding) {
+                .u8 => scale(i8, @intCast(@as(i16, bytes[0]) - 128)),
+                .i16 => scale(i16, std.mem.readInt(i16, bytes[0..2], endian)),
+                .i24 => scale(i24, std.mem.readInt(i24, bytes[0..3], endian)),
+                .i32 => scale(i32, std.mem.readInt(i32, bytes[0..4], endian)),
+                .f32 => @floatCast(@as(f32, @bitCast(std.mem.readInt(u32, bytes[0..4], endian)))),
+                .f64 => @floatCast(@as(f64, @bitCast(std.mem.readInt(u64, bytes[0..8], endian)))),
+            };
+        }
+
+        fn scale(comptime I: type, value: I) T {
+            return @as(T, @floatFromInt(value)) / @as(T, @floatFromInt(std.math.maxInt(I)));
+        }
+
+        /// Writes samples as raw data, without any header.
+        ///
+        /// ## Parameters
+        /// - `samples`: Interleaved samples
+        /// - `writer`: A writer interface for the output bytes
+        /// - `options`: Sample encoding and byte order
+        ///
+        /// ## Errors
+        /// - Any error returned by the writer
+        pub fn write(samples: []const T, writer: anytype, options: WriteOptions) anyerror!void {
+            for (samples) |sample| {
+                switch (options.encoding) {
+                    .u8 => try writer.writeByte(@intCast(@as(i16, quantize(i8, sample)) + 128)),
+                    .i16 => try writer.writeInt(i16, quantize(i16, sample), options.endian),
+                    .i24 => try writer.writeInt(i24, quantize(i24, sample), options.endian),
+                    .i32 => try writer.writeInt(i32, quantize(i32, sample), options.endian),
+                    .f32 => try writer.writeInt(u32, @bitCast(@as(f32, @floatCast(sample))), options.endian),
+                    .f64 => try writer.writeInt(u64, @bitCast(@as(f64, @floatCast(sample))), options.endian),
+                }
+            }
+        }
+
+        fn quantize(comptime I: type, sample: T) I {
+            const max: T = @floatFromInt(std.math.maxInt(I));
+            return @intFromFloat(@round(std.math.clamp(sample, -1.0, 1.0) * max));
+        }
+
+        test "write & read every encoding" {
+            const allocator = testing.allocator;
+            const samples = [_]T{ 0.0, 0.5, -0.5, 1.0, -1.0, 0.25 };
+
+            for ([_]Encoding{ .u8, .i16, .i24, .i32, .f32, .f64 }) |encoding| {
+                for ([_]std.builtin.Endian{ .little, .big }) |endian| {
+                    var writer = std.Io.Writer.Allocating.init(allocator);
+                    defer writer.deinit();
+                    try Self.write(&samples, &writer.writer, .{ .encoding = encoding, .endian = endian });
+                    try testing.expectEqual(writer.written().len, samples.len * encoding.size());
+
+                    var reader = std.Io.Reader.fixed(writer.written());
+                    const decoded = try Self.read(allocator, &reader, .{
+                        .encoding = encoding,
+                        .endian = endian,
+                        .sample_rate = 8000,
+                        .channels = 2,
+                    });
+                    defer allocator.free(decoded.samples);
+
+                    try testing.expectEqual(decoded.sample_rate, 8000);
+                    try testing.expectEqual(decoded.channels, 2);
+                    for (samples, decoded.samples) |expected, actual| {
+                        try testing.expectApproxEqAbs(expected, actual, 0.01);
+                    }
+                }
+            }
+        }
+
+        test "byte layout" {
+            const allocator = testing.allocator;
+            const samples = [_]T{ 1.0, -1.0, 2.0 };
+
+            var writer = std.Io.Writer.Allocating.init(allocator);
+            defer writer.deinit();
+
+            // Out of range samples are clipped
+            try Self.write(&samples, &writer.writer, .{ .encoding = .u8 });
+            try Self.write(&samples, &writer.writer, .{ .encoding = .i16 });
+            try Self.write(&samples, &writer.writer, .{ .encoding = .i24, .endian = .big });
+            try testing.expectEqualSlices(u8, &.{
+                0xFF, 0x01, 0xFF,
+                0xFF, 0x7F, 0x01,
+                0x80, 0xFF, 0x7F,
+                0x7F, 0xFF, 0xFF,
+                0x80, 0x00, 0x01,
+                0x7F, 0xFF, 0xFF,
+            }, writer.written());
+        }
+
+        test "invalid options and data" {
+            const allocator = testing.allocator;
+
+            var reader = std.Io.Reader.fixed(&.{ 0x00, 0x00, 0x00 });
+            try testing.expectError(error.LengthMismatch, Self.read(allocator, &reader, .{ .encoding = .i16, .sample_rate = 44100, .channels = 1 }));
+
+            var stereo_reader = std.Io.Reader.fixed(&.{ 0x00, 0x00, 0x00, 0x00, 0x00, 0x00 });
+            try testing.expectError(error.LengthMismatch, Self.read(allocator, &stereo_reader, .{ .encoding = .i16, .sample_rate = 44100, .channels = 2 }));
+
+            var empty_reader = std.Io.Reader.fixed("");
+            try testing.expectError(error.InvalidRange, Self.read(allocator, &empty_reader, .{ .encoding = .f32, .sample_rate = 0, .channels = 1 }));
+            try testing.expectError(error.InvalidRange, Self.read(allocator, &empty_reader, .{ .encoding = .f32, .sample_rate = 44100, .channels = 0 }));
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
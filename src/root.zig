@@ -7,7 +7,61 @@
 //!
 //! ### Wave
 //! The `Wave` type function creates audio waveform types for different sample formats.
-//! It supports operations like mixing, filtering, and reading/writing WAV files.
+//! It supports operations like mixing, filtering, reading/writing WAV, AIFF, FLAC and raw PCM
+//! files, and reading Ogg Vorbis files.
+//!
+//! ### Oscillator
+//! The `Oscillator` type function creates generators for periodic waveforms
+//! (sine, square, sawtooth, triangle and pulse) that return Wave instances.
+//!
+//! ### Noise
+//! The `Noise` type function creates seedable generators for white, pink, brown,
+//! blue, violet and velvet noise that return Wave instances.
+//!
+//! ### Envelope
+//! The `Envelope` type function creates ADSR, AHDSR and breakpoint envelopes which
+//! shape the amplitude of a Wave, or are rendered as control waves.
+//!
+//! ### Biquad
+//! The `Biquad` type function creates second-order filters (low-pass, high-pass,
+//! band-pass, notch, all-pass, peaking EQ and shelves) usable with `Wave.filter_with`.
+//!
+//! ### Parameter
+//! The `Parameter` type function creates values which are either constant or follow
+//! a control Wave. Filters such as `Biquad` and `Gain` use them for modulation.
+//!
+//! ### Gain
+//! The `Gain` type function creates a filter which scales a Wave by a linear or
+//! decibel gain, usable with `Wave.filter_with`.
+//!
+//! ### Pan
+//! The `Pan` type function creates a filter which positions a mono Wave in a stereo
+//! field with a linear, constant-power or compromise pan law.
+//!
+//! ### Timeline
+//! The `Timeline` type function creates tempo maps which convert seconds, milliseconds
+//! and bars, beats and ticks into frame indices for `Composer`.
+//!
+//! ### Tuning
+//! The `Tuning` type function creates tuning systems (equal divisions of the octave,
+//! just intonation, Pythagorean, quarter-comma meantone and Scala files) which convert
+//! pitches, MIDI note numbers and note names such as "C#4" into frequencies.
+//!
+//! ### Scale
+//! The `Scale` type function creates scales (major, minor, modes, pentatonic or
+//! user-defined intervals) whose degrees are note numbers for a `Tuning`.
+//!
+//! ### Chord
+//! The `Chord` type function creates chords from symbols such as "Cmaj7" or "Am/E",
+//! with inversions and voicings, and arpeggiates them into `Midi` sequences for `Composer`.
+//!
+//! ### Midi
+//! The `Midi` type function creates note sequences read from Standard MIDI Files,
+//! which `Composer.appendMidi` renders with an instrument function.
+//!
+//! ### Error
+//! The `Error` error set is returned by Wave and Composer operations whose input is
+//! invalid, such as mixing waves with different sample rates.
 //!
 //! ### Composer
 //! The `Composer` type function creates types for sequencing and overlaying multiple
@@ -20,19 +74,15 @@
 //! const lightmix = @import("lightmix");
 //! const Wave = lightmix.Wave;
 //! const Composer = lightmix.Composer;
+//! const Oscillator = lightmix.Oscillator;
 //!
 //! pub fn main() !void {
 //!     const allocator = std.heap.page_allocator;
 //!
 //!     // Create a simple sine wave
-//!     var samples: [44100]f64 = undefined;
-//!     for (0..samples.len) |i| {
-//!         const t = @as(f64, @floatFromInt(i)) / 44100.0;
-//!         samples[i] = @sin(t * 440.0 * 2.0 * std.math.pi);
-//!     }
-//!
-//!     // Wave(T).init() creates a deep copy of samples
-//!     const wave: Wave(f64) = Wave(f64).init(&samples, allocator, .{
+//!     const wave: Wave(f64) = try Oscillator(f64).sine(allocator, .{
+//!         .frequency = 440.0,
+//!         .duration = 1.0,
 //!         .sample_rate = 44100,
 //!         .channels = 1,
 //!     });
@@ -55,8 +105,38 @@
 
 pub const Wave = @import("./wave.zig").inner;
 pub const Composer = @import("./composer.zig").inner;
+pub const Oscillator = @import("./oscillator.zig").inner;
+pub const Noise = @import("./noise.zig").inner;
+pub const Envelope = @import("./envelope.zig").inner;
+pub const Biquad = @import("./biquad.zig").inner;
+pub const Parameter = @import("./parameter.zig").inner;
+pub const Gain = @import("./gain.zig").inner;
+pub const Pan = @import("./pan.zig").inner;
+pub const Timeline = @import("./timeline.zig").inner;
+pub const Tuning = @import("./tuning.zig").inner;
+pub const Scale = @import("./scale.zig").inner;
+pub const Chord = @import("./chord.zig").inner;
+pub const Midi = @import("./midi.zig").inner;
+pub const Error = @import("./error.zig").Error;
 
 test "Import tests" {
     _ = @import("./wave.zig");
     _ = @import("./composer.zig");
+    _ = @import("./oscillator.zig");
+    _ = @import("./noise.zig");
+    _ = @import("./envelope.zig");
+    _ = @import("./biquad.zig");
+    _ = @import("./parameter.zig");
+    _ = @import("./gain.zig");
+    _ = @import("./pan.zig");
+    _ = @import("./timeline.zig");
+    _ = @import("./tuning.zig");
+    _ = @import("./scala.zig");
+    _ = @import("./scale.zig");
+    _ = @import("./chord.zig");
+    _ = @import("./midi.zig");
+    _ = @import("./aiff.zig");
+    _ = @import("./flac.zig");
+    _ = @import("./ogg.zig");
+    _ = @import("./raw.zig");
 }
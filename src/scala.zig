@@ -0,0 +1,380 @@
+const std = @import("std");
+const testing = std.testing;
+const Tuning = @import("./root.zig").Tuning;
+
+/// Scala type function: Creates readers of Scala tuning files for the specified sample type.
+///
+/// A `.scl` file lists the degrees of a scale as ratios or cents, and a `.kbm` file maps
+/// the keys of a keyboard to the degrees, with the reference pitch. Both are described in
+/// https://www.huygens-fokker.org/scala/scl_format.html and
+/// https://www.huygens-fokker.org/scala/help.htm#mappings.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// Use it through `Tuning(T).Scala`:
+/// ```zig
+/// var scl_reader = std.Io.Reader.fixed(@embedFile("./werckmeister3.scl"));
+/// const scale = try Tuning(f64).Scala.readScale(allocator, &scl_reader);
+/// defer scale.deinit();
+///
+/// var kbm_reader = std.Io.Reader.fixed(@embedFile("./a415.kbm"));
+/// const mapping = try Tuning(f64).Scala.readKeyboardMapping(allocator, &kbm_reader);
+/// defer mapping.deinit();
+///
+/// const tuning: Tuning(f64) = scale.tuning(mapping); // Or scale.tuning(null)
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        /// Errors that can occur when reading Scala files.
+        pub const ScalaErrors = error{
+            /// The data is not a `.scl` file, or one of its pitches is malformed
+            InvalidScaleFile,
+            /// The data is not a `.kbm` file, or one of its values is malformed
+            InvalidKeyboardMapping,
+        };
+
+        /// A scale read from a `.scl` file.
+        pub const Scale = struct {
+            /// Description of the scale
+            description: []const u8,
+            /// Ratios of each degree after 1/1. The last ratio is the period.
+            ratios: []const T,
+            allocator: std.mem.Allocator,
+
+            /// Frees the description and the ratios.
+            pub fn deinit(self: Scale) void {
+                self.allocator.free(self.description);
+                self.allocator.free(self.ratios);
+            }
+
+            /// Returns a tuning of the scale, which borrows its ratios and the mapping's keyboard.
+            ///
+            /// ## Parameters
+            /// - `self`: The scale
+            /// - `mapping`: A keyboard mapping, or `null` for Scala's default: each key plays the
+            ///   next degree, from 1/1 on C4, with A4 at 440 Hz
+            pub fn tuning(self: Scale, mapping: ?KeyboardMapping) Tuning(T) {
+                const system: Tuning(T).System = .{ .scale = self.ratios };
+                const keyboard_mapping: KeyboardMapping = mapping orelse return .{ .system = system };
+
+                return .{
+                    .reference_frequency = keyboard_mapping.reference_frequency,
+                    .reference_pitch = .{ .note = keyboard_mapping.reference_note },
+                    .system = system,
+                    .tonic = keyboard_mapping.middle_note,
+                    .keyboard = keyboard_mapping.keyboard,
+                };
+            }
+        };
+
+        /// A keyboard mapping read from a `.kbm` file.
+        pub const KeyboardMapping = struct {
+            /// Degrees of the keys from the middle note, with the range of mapped keys
+            keyboard: Tuning(T).Keyboard,
+            /// The key which plays 1/1
+            middle_note: i32,
+            /// The key which sounds at `reference_frequency`
+            reference_note: i32,
+            /// Frequency of the reference note, in Hz
+            reference_frequency: T,
+            allocator: std.mem.Allocator,
+
+            /// Frees the map.
+            pub fn deinit(self: KeyboardMapping) void {
+                self.allocator.free(self.keyboard.map);
+            }
+        };
+
+        /// Reads a `.scl` file.
+        ///
+        /// Lines starting with `!` are comments. The first other line is the description, the second
+        /// is the number of pitches, and each of the next ones starts with a pitch: cents when it holds
+        /// a period (`386.3137`), or a ratio otherwise (`5/4` or `2`).
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for the description and the ratios
+        /// - `reader`: A reader interface providing the raw file bytes
+        ///
+        /// ## Returns
+        /// The scale, owned by the caller
+        ///
+        /// ## Errors
+        /// - `InvalidScaleFile`: The data is not a `.scl` file, it has no pitch or fewer pitches than its count, or a pitch is malformed
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn readScale(allocator: std.mem.Allocator, reader: anytype) anyerror!Scale {
+            const bytes: []u8 = try reader.allocRemaining(allocator, .unlimited);
+            defer allocator.free(bytes);
+
+            var lines: Lines = .{ .iterator = std.mem.splitScalar(u8, bytes, '\n') };
+            const description: []const u8 = lines.next() orelse return error.InvalidScaleFile;
+            const count_token: []const u8 = firstToken(lines.next()) orelse return error.InvalidScaleFile;
+            const count: usize = std.fmt.parseInt(usize, count_token, 10) catch return error.InvalidScaleFile;
+            // Each pitch takes a line, so the count can't exceed the lines left, nor allocate more
+            if (count == 0 or count > lines.remaining())
+                return error.InvalidScaleFile;
+
+            const ratios: []T = try allocator.alloc(T, count);
+            errdefer allocator.free(ratios);
+            for (ratios) |*ratio| {
+                ratio.* = try parsePitch(firstToken(lines.next()) orelse return error.InvalidScaleFile);
+            }
+
+            return Scale{
+                .description = try allocator.dupe(u8, description),
+                .ratios = ratios,
+                .allocator = allocator,
+            };
+        }
+
+        fn parsePitch(token: []const u8) ScalaErrors!T {
+            if (std.mem.indexOfScalar(u8, token, '.') != null) {
+                const cents: T = std.fmt.parseFloat(T, token) catch return error.InvalidScaleFile;
+                return @exp2(cents / 1200.0);
+            }
+
+            var parts = std.mem.splitScalar(u8, token, '/');
+            const numerator: u64 = std.fmt.parseInt(u64, parts.first(), 10) catch return error.InvalidScaleFile;
+            const denominator: u64 = if (parts.next()) |text|
+                std.fmt.parseInt(u64, text, 10) catch return error.InvalidScaleFile
+            else
+                1;
+            if (parts.next() != null or numerator == 0 or denominator == 0)
+                return error.InvalidScaleFile;
+
+            return @as(T, @floatFromInt(numerator)) / @as(T, @floatFromInt(denominator));
+        }
+
+        /// Reads a `.kbm` file.
+        ///
+        /// Lines starting with `!` are comments. The other lines are the size of the map (0 maps each
+        /// key to the next degree), the first and last mapped keys, the middle note which plays 1/1,
+        /// the reference note and its frequency, the degree between repetitions of the map, and then
+        /// the degree of each key of the map, or `x` for unmapped keys. Missing keys are unmapped.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for the map
+        /// - `reader`: A reader interface providing the raw file bytes
+        ///
+        /// ## Returns
+        /// The keyboard mapping, owned by the caller
+        ///
+        /// ## Errors
+        /// - `InvalidKeyboardMapping`: The data is not a `.kbm` file, or a value is malformed
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        /// - Any error returned by the reader
+        pub fn readKeyboardMapping(allocator: std.mem.Allocator, reader: anytype) anyerror!KeyboardMapping {
+            const bytes: []u8 = try reader.allocRemaining(allocator, .unlimited);
+            defer allocator.free(bytes);
+
+            var lines: Lines = .{ .iterator = std.mem.splitScalar(u8, bytes, '\n') };
+            const size: u32 = try parseField(u32, lines.next());
+            const first_note: i32 = try parseField(i32, lines.next());
+            const last_note: i32 = try parseField(i32, lines.next());
+            const middle_note: i32 = try parseField(i32, lines.next());
+            const reference_note: i32 = try parseField(i32, lines.next());
+            const frequency_token: []const u8 = firstToken(lines.next()) orelse return error.InvalidKeyboardMapping;
+            const reference_frequency: T = std.fmt.parseFloat(T, frequency_token) catch return error.InvalidKeyboardMapping;
+            const octave_degree: u32 = try parseField(u32, lines.next());
+            if (!(reference_frequency > 0.0))
+                return error.InvalidKeyboardMapping;
+
+            // The map grows with the lines of the file, and the keys missing from it are unmapped
+            var map: std.array_list.Aligned(?u32, null) = .empty;
+            errdefer map.deinit(allocator);
+            for (0..size) |_| {
+                const line: []const u8 = lines.next() orelse break;
+                const token: []const u8 = firstToken(line) orelse {
+                    try map.append(allocator, null);
+                    continue;
+                };
+                try map.append(allocator, if (std.mem.eql(u8, token, "x"))
+                    null
+                else
+                    std.fmt.parseInt(u32, token, 10) catch return error.InvalidKeyboardMapping);
+            }
+
+            return KeyboardMapping{
+                .keyboard = .{
+                    .map = try map.toOwnedSlice(allocator),
+                    .map_size = size,
+                    .octave_degree = octave_degree,
+                    .first_note = first_note,
+                    .last_note = last_note,
+                },
+                .middle_note = middle_note,
+                .reference_note = reference_note,
+                .reference_frequency = reference_frequency,
+                .allocator = allocator,
+            };
+        }
+
+        fn parseField(comptime I: type, line: ?[]const u8) ScalaErrors!I {
+            const token: []const u8 = firstToken(line) orelse return error.InvalidKeyboardMapping;
+            return std.fmt.parseInt(I, token, 10) catch error.InvalidKeyboardMapping;
+        }
+
+        test "read a scale of ratios and cents" {
+            const allocator = testing.allocator;
+            var reader = std.Io.Reader.fixed(
+                \\! meantone.scl
+                \\!
+                \\Just major with a meantone fifth
+                \\ 7
+                \\!
+                \\ 9/8
+                \\ 5/4 major third
+                \\ 4/3
+                \\ 696.578428
+                \\ 5/3
+                \\ 15/8
+                \\ 2
+                \\
+            );
+            const scale = try readScale(allocator, &reader);
+            defer scale.deinit();
+
+            try testing.expectEqualStrings(scale.description, "Just major with a meantone fifth");
+            try testing.expectEqual(scale.ratios.len, 7);
+            try testing.expectApproxEqAbs(scale.ratios[1], 1.25, 0.000001);
+            try testing.expectApproxEqAbs(scale.ratios[3], @sqrt(@sqrt(@as(T, 5.0))), 0.000001);
+            try testing.expectApproxEqAbs(scale.ratios[6], 2.0, 0.000001);
+
+            // 1/1 on C4, and each key plays the next degree, so A4 (440 Hz) plays 5/4 an octave up
+            const tuning = scale.tuning(null);
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 60 }), 176.0, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 62 }), 220.0, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 67 }), 352.0, 0.000001);
+        }
+
+        test "read a keyboard mapping" {
+            const allocator = testing.allocator;
+            var scl_reader = std.Io.Reader.fixed("Just major\r\n7\r\n9/8\r\n5/4\r\n4/3\r\n3/2\r\n5/3\r\n15/8\r\n2/1\r\n");
+            const scale = try readScale(allocator, &scl_reader);
+            defer scale.deinit();
+
+            var kbm_reader = std.Io.Reader.fixed(
+                \\! White keys of a keyboard, with A4 at 415 Hz
+                \\12
+                \\21
+                \\108
+                \\60
+                \\69
+                \\415.0
+                \\7
+                \\! Mapping
+                \\0
+                \\x
+                \\1
+                \\x
+                \\2
+                \\3
+                \\x
+                \\4
+                \\x
+                \\5
+                \\x
+            );
+            const mapping = try readKeyboardMapping(allocator, &kbm_reader);
+            defer mapping.deinit();
+
+            try testing.expectEqual(mapping.keyboard.map_size, 12);
+            try testing.expectEqual(mapping.keyboard.map.len, 11);
+            try testing.expectEqual(mapping.keyboard.map[2], 1);
+
+            const tuning = scale.tuning(mapping);
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 69 }), 415.0, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 72 }), 498.0, 0.000001);
+            try testing.expect(!tuning.isMapped(.{ .note = 61 }));
+            // The last key is missing, so it is unmapped
+            try testing.expect(!tuning.isMapped(.{ .note = 71 }));
+            try testing.expect(!tuning.isMapped(.{ .note = 109 }));
+        }
+
+        test "invalid files" {
+            const allocator = testing.allocator;
+
+            const scales = [_][]const u8{
+                "",
+                "No count\n",
+                "Too few pitches\n3\n9/8\n5/4\n",
+                "No pitch\n0\n",
+                "Negative ratio\n1\n-3/2\n",
+                "Zero denominator\n1\n3/0\n",
+                "Not a pitch\n1\nfifth\n",
+                "Huge count\n4000000000\n9/8\n",
+            };
+            for (scales) |bytes| {
+                var reader = std.Io.Reader.fixed(bytes);
+                try testing.expectError(error.InvalidScaleFile, readScale(allocator, &reader));
+            }
+
+            const mappings = [_][]const u8{
+                "",
+                "0\n0\n127\n60\n69\n",
+                "0\n0\n127\n60\n69\n0.0\n12\n",
+                "1\n0\n127\n60\n69\n440.0\n12\ny\n",
+            };
+            for (mappings) |bytes| {
+                var reader = std.Io.Reader.fixed(bytes);
+                try testing.expectError(error.InvalidKeyboardMapping, readKeyboardMapping(allocator, &reader));
+            }
+
+            // A huge map only allocates the keys written in the file
+            var huge_reader = std.Io.Reader.fixed("4000000000\n0\n127\n60\n69\n440.0\n12\n0\n");
+            const huge = try readKeyboardMapping(allocator, &huge_reader);
+            defer huge.deinit();
+            try testing.expectEqual(huge.keyboard.map.len, 1);
+        }
+    };
+}
+
+/// Iterates over the lines of a Scala file which aren't comments, without their line breaks.
+const Lines = struct {
+    iterator: std.mem.SplitIterator(u8, .scalar),
+
+    fn next(self: *Lines) ?[]const u8 {
+        while (self.iterator.next()) |line| {
+            if (std.mem.startsWith(u8, line, "!"))
+                continue;
+            return std.mem.trim(u8, line, " \t\r");
+        }
+
+        return null;
+    }
+
+    /// Returns the number of lines left which aren't comments.
+    fn remaining(self: Lines) usize {
+        var copy: Lines = self;
+        var count: usize = 0;
+        while (copy.next()) |_| {
+            count += 1;
+        }
+
+        return count;
+    }
+};
+
+/// Returns the first word of a line, or `null` when there is no line or no word.
+fn firstToken(line: ?[]const u8) ?[]const u8 {
+    var tokens = std.mem.tokenizeAny(u8, line orelse return null, " \t");
+    return tokens.next();
+}
+
+test "lines without comments" {
+    var lines: Lines = .{ .iterator = std.mem.splitScalar(u8, "! comment\r\n  Description \r\n\n!\n 12 notes", '\n') };
+    try testing.expectEqualStrings(lines.next().?, "Description");
+    try testing.expectEqualStrings(lines.next().?, "");
+    try testing.expectEqualStrings(firstToken(lines.next()).?, "12");
+    try testing.expectEqual(lines.next(), null);
+    try testing.expectEqual(firstToken(null), null);
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -0,0 +1,155 @@
+const std = @import("std");
+const testing = std.testing;
+const Tuning = @import("./root.zig").Tuning;
+
+/// Scale type function: Creates musical scales for the specified sample type.
+///
+/// A scale is a tonic and the intervals from the tonic to each of its degrees within an octave.
+/// Its notes are note numbers as in MIDI (60 is C4), which a `Tuning` turns into frequencies.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const d_dorian: Scale(f64) = .{ .tonic = 62, .intervals = Scale(f64).dorian };
+///
+/// const fifth = d_dorian.note(4); // A4 (69)
+/// const melody = try d_dorian.notes(allocator, 8); // D4 to D5
+/// defer allocator.free(melody);
+///
+/// const hz = d_dorian.frequency(2, .{ .reference_frequency = 442.0 }); // F4
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        /// Note number of the tonic, where 60 is C4
+        tonic: i32,
+        /// Semitones from the tonic to each degree, ascending from 0 and below 12. It must not be empty.
+        intervals: []const i32 = major,
+
+        const Self = @This();
+
+        /// Major scale, the Ionian mode
+        pub const major: []const i32 = &.{ 0, 2, 4, 5, 7, 9, 11 };
+        /// Natural minor scale, the Aeolian mode
+        pub const natural_minor: []const i32 = aeolian;
+        /// Harmonic minor scale, with a raised seventh
+        pub const harmonic_minor: []const i32 = &.{ 0, 2, 3, 5, 7, 8, 11 };
+        /// Melodic minor scale (ascending), with a raised sixth and seventh
+        pub const melodic_minor: []const i32 = &.{ 0, 2, 3, 5, 7, 9, 11 };
+        /// Major pentatonic scale
+        pub const major_pentatonic: []const i32 = &.{ 0, 2, 4, 7, 9 };
+        /// Minor pentatonic scale
+        pub const minor_pentatonic: []const i32 = &.{ 0, 3, 5, 7, 10 };
+        /// Whole tone scale
+        pub const whole_tone: []const i32 = &.{ 0, 2, 4, 6, 8, 10 };
+        /// Chromatic scale, every semitone
+        pub const chromatic: []const i32 = &.{ 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11 };
+
+        /// Ionian mode, the major scale
+        pub const ionian: []const i32 = major;
+        /// Dorian mode
+        pub const dorian: []const i32 = &.{ 0, 2, 3, 5, 7, 9, 10 };
+        /// Phrygian mode
+        pub const phrygian: []const i32 = &.{ 0, 1, 3, 5, 7, 8, 10 };
+        /// Lydian mode
+        pub const lydian: []const i32 = &.{ 0, 2, 4, 6, 7, 9, 11 };
+        /// Mixolydian mode
+        pub const mixolydian: []const i32 = &.{ 0, 2, 4, 5, 7, 9, 10 };
+        /// Aeolian mode, the natural minor scale
+        pub const aeolian: []const i32 = &.{ 0, 2, 3, 5, 7, 8, 10 };
+        /// Locrian mode
+        pub const locrian: []const i32 = &.{ 0, 1, 3, 5, 6, 8, 10 };
+
+        /// Returns the note number of a degree, where 0 is the tonic. Degrees beyond the scale
+        /// continue into the next octaves, and negative degrees go down below the tonic.
+        pub fn note(self: Self, degree: i32) i32 {
+            const size: i32 = @intCast(self.intervals.len);
+            return self.tonic + 12 * @divFloor(degree, size) + self.intervals[@intCast(@mod(degree, size))];
+        }
+
+        /// Returns the note numbers of the first degrees of the scale, from the tonic upward.
+        ///
+        /// ## Parameters
+        /// - `self`: The scale
+        /// - `allocator`: Memory allocator for the note numbers
+        /// - `count`: Number of degrees
+        ///
+        /// ## Returns
+        /// The note numbers, owned by the caller
+        ///
+        /// ## Errors
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn notes(self: Self, allocator: std.mem.Allocator, count: usize) std.mem.Allocator.Error![]i32 {
+            const result: []i32 = try allocator.alloc(i32, count);
+            for (result, 0..) |*number, degree| {
+                number.* = self.note(@intCast(degree));
+            }
+
+            return result;
+        }
+
+        /// Returns whether a note number belongs to the scale, in any octave.
+        pub fn contains(self: Self, note_number: i32) bool {
+            return std.mem.indexOfScalar(i32, self.intervals, @mod(note_number - self.tonic, 12)) != null;
+        }
+
+        /// Returns the frequency of a degree in a tuning, in Hz.
+        pub fn frequency(self: Self, degree: i32, tuning: Tuning(T)) T {
+            return tuning.frequency(.{ .note = self.note(degree) });
+        }
+
+        test "notes of scales" {
+            const allocator = testing.allocator;
+
+            const c_major: Self = .{ .tonic = 60 };
+            const c_major_notes = try c_major.notes(allocator, 8);
+            defer allocator.free(c_major_notes);
+            try testing.expectEqualSlices(i32, &.{ 60, 62, 64, 65, 67, 69, 71, 72 }, c_major_notes);
+
+            const a_harmonic: Self = .{ .tonic = 69, .intervals = harmonic_minor };
+            try testing.expectEqual(a_harmonic.note(6), 80); // G#5
+            try testing.expectEqual(a_harmonic.note(-1), 68); // G#4
+
+            const d_dorian: Self = .{ .tonic = 62, .intervals = dorian };
+            try testing.expectEqual(d_dorian.note(-1), 60);
+            try testing.expectEqual(d_dorian.note(-7), 50);
+            try testing.expectEqual(d_dorian.note(9), 77);
+
+            const pentatonic: Self = .{ .tonic = 57, .intervals = minor_pentatonic };
+            try testing.expectEqual(pentatonic.note(5), 69);
+            try testing.expectEqual(pentatonic.note(12), 86);
+        }
+
+        test "user-defined intervals" {
+            const diminished: Self = .{ .tonic = 60, .intervals = &.{ 0, 2, 3, 5, 6, 8, 9, 11 } };
+            try testing.expectEqual(diminished.note(8), 72);
+            try testing.expectEqual(diminished.note(12), 78);
+            try testing.expect(diminished.contains(45));
+            try testing.expect(!diminished.contains(61));
+        }
+
+        test "scale membership" {
+            const g_mixolydian: Self = .{ .tonic = 67, .intervals = mixolydian };
+            try testing.expect(g_mixolydian.contains(65)); // F
+            try testing.expect(!g_mixolydian.contains(66)); // F#
+            try testing.expect(g_mixolydian.contains(-1)); // B-2
+            try testing.expect(g_mixolydian.contains(127)); // G9
+        }
+
+        test "frequencies of degrees" {
+            const a_minor: Self = .{ .tonic = 69, .intervals = natural_minor };
+            try testing.expectApproxEqAbs(a_minor.frequency(0, .{}), 440.0, 0.000001);
+            try testing.expectApproxEqAbs(a_minor.frequency(7, .{}), 880.0, 0.000001);
+            try testing.expectApproxEqAbs(a_minor.frequency(0, .{ .reference_frequency = 432.0 }), 432.0, 0.000001);
+            try testing.expectApproxEqAbs(a_minor.frequency(-5, .{}), 261.6255653005986, 0.000001);
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -0,0 +1,202 @@
+const std = @import("std");
+const testing = std.testing;
+const Oscillator = @import("./root.zig").Oscillator;
+
+/// Timeline type function: Creates a Timeline type for the specified sample type.
+///
+/// Timeline converts positions given in seconds, milliseconds or bars, beats and ticks
+/// into frame indices, following a tempo (with optional tempo changes) and a time signature.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const Timeline = lightmix.Timeline;
+/// const timeline: Timeline(f64) = .{
+///     .tempo = 120.0,
+///     .time_signature = .{ .beats_per_bar = 3 },
+///     .tempo_changes = &.{.{ .at = .{ .bar = 8 }, .tempo = 90.0 }},
+/// };
+///
+/// // The third beat of the second bar
+/// const frame = try timeline.frameAt(.{ .musical = .{ .bar = 1, .beat = 2 } }, 44100);
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        /// Tempo at the start of the timeline, in beats per minute
+        tempo: T = 120.0,
+        /// Time signature of the whole timeline
+        time_signature: TimeSignature = .{},
+        /// Resolution of `Musical.tick`
+        ticks_per_beat: u32 = 480,
+        /// Tempo changes, sorted by position
+        tempo_changes: []const TempoChange = &.{},
+
+        const Self = @This();
+
+        /// Number of beats in a bar, and the note value of a beat.
+        /// The tempo counts beats of this note value: in 6/8, 120 BPM means 120 eighth notes per minute.
+        pub const TimeSignature = struct {
+            beats_per_bar: u32 = 4,
+            beat_unit: u32 = 4,
+        };
+
+        /// A musical position. Every field counts from 0, so the first beat of the first bar is `.{ .bar = 0 }`.
+        /// Beats and ticks may exceed a bar or a beat; they carry over.
+        pub const Musical = struct {
+            bar: usize,
+            beat: usize = 0,
+            tick: usize = 0,
+        };
+
+        /// A new tempo which applies from a musical position onward.
+        pub const TempoChange = struct {
+            at: Musical,
+            /// Beats per minute
+            tempo: T,
+        };
+
+        /// A position on the timeline.
+        pub const Position = union(enum) {
+            /// A frame index, used as it is
+            frame: usize,
+            /// Seconds from the start
+            seconds: T,
+            /// Milliseconds from the start
+            milliseconds: T,
+            /// Bars, beats and ticks from the start, following the tempo
+            musical: Musical,
+        };
+
+        /// Returns the number of beats from the start to a musical position.
+        pub fn beatsAt(self: Self, musical: Musical) T {
+            // Counted in T, so distant bars don't overflow
+            const bars: T = @as(T, @floatFromInt(musical.bar)) * @as(T, @floatFromInt(self.time_signature.beats_per_bar));
+            const ticks: T = @as(T, @floatFromInt(musical.tick)) / @as(T, @floatFromInt(self.ticks_per_beat));
+
+            return bars + @as(T, @floatFromInt(musical.beat)) + ticks;
+        }
+
+        /// Returns the number of seconds from the start to a position counted in beats,
+        /// following the tempo changes.
+        pub fn secondsAtBeat(self: Self, beats: T) T {
+            var seconds: T = 0.0;
+            var tempo: T = self.tempo;
+            var segment_start: T = 0.0;
+
+            for (self.tempo_changes) |change| {
+                const change_beat: T = self.beatsAt(change.at);
+                if (change_beat >= beats)
+                    break;
+
+                seconds += (change_beat - segment_start) * 60.0 / tempo;
+                segment_start = change_beat;
+                tempo = change.tempo;
+            }
+
+            return seconds + (beats - segment_start) * 60.0 / tempo;
+        }
+
+        /// Returns the number of seconds from the start to a position.
+        ///
+        /// ## Parameters
+        /// - `self`: The timeline
+        /// - `position`: The position to convert
+        /// - `sample_rate`: Samples per second, used for `.frame` positions
+        pub fn secondsAt(self: Self, position: Position, sample_rate: u32) T {
+            return switch (position) {
+                .frame => |frame| @as(T, @floatFromInt(frame)) / @as(T, @floatFromInt(sample_rate)),
+                .seconds => |seconds| seconds,
+                .milliseconds => |milliseconds| milliseconds / 1000.0,
+                .musical => |musical| self.secondsAtBeat(self.beatsAt(musical)),
+            };
+        }
+
+        /// Returns the frame index of a position, rounded to the nearest frame.
+        ///
+        /// ## Parameters
+        /// - `self`: The timeline
+        /// - `position`: The position to convert. Negative times are clamped to 0.
+        /// - `sample_rate`: Samples per second
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The time is NaN, or its frame index doesn't fit a `usize`,
+        ///   as with a tempo of 0 or NaN, or a very distant position
+        pub fn frameAt(self: Self, position: Position, sample_rate: u32) error{InvalidRange}!usize {
+            switch (position) {
+                .frame => |frame| return frame,
+                else => {
+                    const seconds: T = self.secondsAt(position, sample_rate);
+                    if (std.math.isNan(seconds))
+                        return error.InvalidRange;
+
+                    return Oscillator(T).frameCount(@max(0.0, seconds), sample_rate);
+                },
+            }
+        }
+
+        test "seconds and milliseconds" {
+            const timeline: Self = .{};
+
+            try testing.expectEqual(try timeline.frameAt(.{ .seconds = 1.5 }, 44100), 66150);
+            try testing.expectEqual(try timeline.frameAt(.{ .milliseconds = 500.0 }, 44100), 22050);
+            try testing.expectEqual(try timeline.frameAt(.{ .frame = 123 }, 44100), 123);
+            try testing.expectEqual(try timeline.frameAt(.{ .seconds = -1.0 }, 44100), 0);
+        }
+
+        test "bars, beats and ticks" {
+            const timeline: Self = .{ .tempo = 120.0 };
+
+            // A beat is 0.5 seconds, a 4/4 bar is 2 seconds
+            try testing.expectEqual(try timeline.frameAt(.{ .musical = .{ .bar = 1 } }, 44100), 88200);
+            try testing.expectEqual(try timeline.frameAt(.{ .musical = .{ .bar = 0, .beat = 1, .tick = 240 } }, 44100), 33075);
+            try testing.expectEqual(try timeline.frameAt(.{ .musical = .{ .bar = 0, .beat = 4 } }, 44100), 88200);
+        }
+
+        test "invalid tempos and positions" {
+            const zero: Self = .{ .tempo = 0.0 };
+            try testing.expectError(error.InvalidRange, zero.frameAt(.{ .musical = .{ .bar = 1 } }, 44100));
+
+            const nan: Self = .{ .tempo = std.math.nan(T) };
+            try testing.expectError(error.InvalidRange, nan.frameAt(.{ .musical = .{ .bar = 1 } }, 44100));
+
+            const timeline: Self = .{};
+            try testing.expectError(error.InvalidRange, timeline.frameAt(.{ .musical = .{ .bar = std.math.maxInt(usize) } }, 44100));
+            try testing.expectError(error.InvalidRange, timeline.frameAt(.{ .seconds = std.math.nan(T) }, 44100));
+            try testing.expectError(error.InvalidRange, timeline.frameAt(.{ .seconds = std.math.inf(T) }, 44100));
+        }
+
+        test "time signature" {
+            const timeline: Self = .{
+                .tempo = 180.0,
+                .time_signature = .{ .beats_per_bar = 6, .beat_unit = 8 },
+            };
+
+            // Six eighth notes at 180 BPM
+            try testing.expectApproxEqAbs(timeline.secondsAt(.{ .musical = .{ .bar = 1 } }, 44100), 2.0, 0.000001);
+        }
+
+        test "tempo changes" {
+            const timeline: Self = .{
+                .tempo = 120.0,
+                .tempo_changes = &.{
+                    .{ .at = .{ .bar = 1 }, .tempo = 60.0 },
+                    .{ .at = .{ .bar = 2 }, .tempo = 240.0 },
+                },
+            };
+
+            try testing.expectApproxEqAbs(timeline.secondsAt(.{ .musical = .{ .bar = 1 } }, 44100), 2.0, 0.000001);
+            try testing.expectApproxEqAbs(timeline.secondsAt(.{ .musical = .{ .bar = 1, .beat = 2 } }, 44100), 4.0, 0.000001);
+            try testing.expectApproxEqAbs(timeline.secondsAt(.{ .musical = .{ .bar = 2 } }, 44100), 6.0, 0.000001);
+            try testing.expectApproxEqAbs(timeline.secondsAt(.{ .musical = .{ .bar = 3 } }, 44100), 7.0, 0.000001);
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
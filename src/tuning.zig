@@ -0,0 +1,550 @@
+const std = @import("std");
+const testing = std.testing;
+
+/// Tuning type function: Creates tuning systems for the specified sample type.
+///
+/// A tuning maps pitches to frequencies, from a reference pitch which sounds at a reference
+/// frequency (A4 at 440 Hz by default). Pitches are note numbers as in MIDI, where 60 is C4
+/// and 69 is A4, and can be offset by cents (hundredths of a semitone).
+///
+/// The tuning system gives the intervals between the notes: equal divisions of the octave
+/// (twelve-tone equal temperament by default), or scales of ratios from a tonic such as just
+/// intonation, Pythagorean tuning, quarter-comma meantone and Scala `.scl` files. A keyboard
+/// mapping, as in Scala `.kbm` files, can also map the notes to the degrees of a scale.
+///
+/// ## Type Parameter
+/// - `T`: The sample data type (typically f64, f80, or f128 for floating-point audio)
+///
+/// ## Usage
+/// ```zig
+/// const tuning: Tuning(f64) = .{ .reference_frequency = 442.0 };
+///
+/// const c4 = try Tuning(f64).Pitch.parse("C4");
+/// const e4 = tuning.frequency(c4.add(4)); // 4 semitones up from C4
+/// const detuned = tuning.frequency(c4.addCents(-15.0));
+/// const a3 = tuning.frequency(.fromMidi(57));
+///
+/// // Just intonation from C4, with A4 at 440 Hz
+/// const just: Tuning(f64) = .{ .system = .just_intonation, .tonic = 60 };
+/// const just_e4 = just.frequency(try .parse("E4")); // 5/4 of C4
+///
+/// // Quarter tones
+/// const quarter_tones: Tuning(f64) = .{ .system = .{ .equal = 24 } };
+/// ```
+pub fn inner(comptime T: type) type {
+    return struct {
+        /// Frequency of the reference pitch, in Hz
+        reference_frequency: T = 440.0,
+        /// The pitch which sounds at `reference_frequency`. A keyboard must map it.
+        reference_pitch: Pitch = .{ .note = 69 },
+        /// Intervals between the notes
+        system: System = .{ .equal = 12 },
+        /// Note number of the first degree of the scale, whose ratio is 1/1.
+        /// Equal divisions of the octave don't depend on it.
+        tonic: i32 = 60,
+        /// Maps the notes to degrees of the scale. `null` maps each note to the next degree.
+        keyboard: ?Keyboard = null,
+
+        const Self = @This();
+
+        /// Errors that can occur when parsing note names.
+        pub const TuningErrors = error{
+            /// The text is not a note name such as "C4", "C#4" or "Bb3"
+            InvalidNoteName,
+        };
+
+        /// Note code
+        ///
+        /// Codes with `~s` represent sharp (#).
+        /// Example: `cs` is C# (C sharp)
+        ///
+        /// ## Note names and MIDI number mapping
+        /// - c (0): C
+        /// - cs (1): C♯
+        /// - d (2): D
+        /// - ds (3): D♯
+        /// - e (4): E
+        /// - f (5): F
+        /// - fs (6): F♯
+        /// - g (7): G
+        /// - gs (8): G♯
+        /// - a (9): A
+        /// - as (10): A♯
+        /// - b (11): B
+        pub const Code = enum(u8) {
+            c = 0,
+            cs = 1,
+            d = 2,
+            ds = 3,
+            e = 4,
+            f = 5,
+            fs = 6,
+            g = 7,
+            gs = 8,
+            a = 9,
+            as = 10,
+            b = 11,
+        };
+
+        /// Intervals between the notes of a tuning.
+        pub const System = union(enum) {
+            /// Equal divisions of the octave: 12 for twelve-tone equal temperament, 24 for quarter tones, 19, 31...
+            equal: u32,
+            /// Ratios of each degree after the tonic (1/1), as in Scala files. The last ratio is
+            /// the period (usually 2/1, the octave), after which the scale repeats.
+            scale: []const T,
+
+            /// 5-limit just intonation, with pure thirds and fifths from the tonic
+            pub const just_intonation: System = .{ .scale = &[_]T{
+                16.0 / 15.0, // Minor second
+                9.0 / 8.0, // Major second
+                6.0 / 5.0, // Minor third
+                5.0 / 4.0, // Major third
+                4.0 / 3.0, // Perfect fourth
+                45.0 / 32.0, // Augmented fourth
+                3.0 / 2.0, // Perfect fifth
+                8.0 / 5.0, // Minor sixth
+                5.0 / 3.0, // Major sixth
+                9.0 / 5.0, // Minor seventh
+                15.0 / 8.0, // Major seventh
+                2.0, // Octave
+            } };
+            /// Pythagorean tuning, a chain of pure fifths (3/2) from the minor third to the augmented fifth
+            pub const pythagorean: System = .{ .scale = &chainOfFifths(3.0 / 2.0) };
+            /// Quarter-comma meantone, a chain of fifths narrowed to give pure major thirds (5/4),
+            /// from the minor third to the augmented fifth
+            pub const quarter_comma_meantone: System = .{ .scale = &chainOfFifths(@sqrt(@sqrt(@as(T, 5.0)))) };
+
+            /// Returns the 12 ratios of a chain of fifths from the tonic's minor third to its
+            /// augmented fifth, brought into one octave and sorted.
+            fn chainOfFifths(fifth: T) [12]T {
+                var ratios: [12]T = undefined;
+                for (0..12) |i| {
+                    const fifths: i32 = @as(i32, @intCast(i)) - 3;
+                    var ratio: T = 1.0;
+                    for (0..@abs(fifths)) |_| {
+                        ratio = if (fifths > 0) ratio * fifth else ratio / fifth;
+                    }
+                    while (ratio >= 2.0) ratio /= 2.0;
+                    while (ratio < 1.0) ratio *= 2.0;
+
+                    ratios[@intCast(@mod(7 * fifths, 12))] = ratio;
+                }
+
+                // The tonic is left out, and the octave closes the scale
+                var scale: [12]T = undefined;
+                @memcpy(scale[0..11], ratios[1..12]);
+                scale[11] = 2.0;
+                return scale;
+            }
+        };
+
+        /// Maps the notes to degrees of a scale, as Scala `.kbm` files do.
+        pub const Keyboard = struct {
+            /// Degree of each note from the tonic, repeating every `map_size` notes.
+            /// `null` leaves a note unmapped. An empty map maps each note to the next degree.
+            map: []const ?u32 = &.{},
+            /// Number of notes in a repetition of the map, after which the notes missing from `map`
+            /// are unmapped. `null` uses `map.len`, and 0 maps each note to the next degree.
+            map_size: ?u32 = null,
+            /// Degrees between two repetitions of the map
+            octave_degree: u32 = 0,
+            /// Lowest mapped note
+            first_note: i32 = 0,
+            /// Highest mapped note
+            last_note: i32 = 127,
+        };
+
+        /// Scala `.scl` scale and `.kbm` keyboard mapping files.
+        pub const Scala = @import("./scala.zig").inner(T);
+
+        /// A note number, offset by cents.
+        ///
+        /// Note names follow the twelve keys of an octave, so in other tuning systems,
+        /// they stand for note numbers.
+        pub const Pitch = struct {
+            /// Note number, where 60 is C4 and 69 is A4, as in MIDI. It may exceed the MIDI range.
+            note: i32,
+            /// Offset from the note, in cents (hundredths of a semitone)
+            cents: T = 0.0,
+
+            /// Returns the pitch of a note code in an octave, where C4 is the middle C.
+            pub fn init(note_code: Code, note_octave: i32) Pitch {
+                return .{ .note = 12 * (note_octave + 1) + @intFromEnum(note_code) };
+            }
+
+            /// Returns the pitch of a MIDI note number.
+            pub fn fromMidi(number: u7) Pitch {
+                return .{ .note = number };
+            }
+
+            /// Returns the MIDI note number nearest to the pitch.
+            ///
+            /// ## Errors
+            /// - `InvalidRange`: The nearest note is below 0 or above 127
+            pub fn toMidi(self: Pitch) error{InvalidRange}!u7 {
+                const number: T = @as(T, @floatFromInt(self.note)) + @round(self.cents / 100.0);
+                if (!(number >= 0.0 and number <= 127.0))
+                    return error.InvalidRange;
+
+                return @intFromFloat(number);
+            }
+
+            /// Parses a note name: a letter from A to G in either case, any number of sharps (`#`)
+            /// and flats (`b`), then an octave number, where C4 is the middle C.
+            ///
+            /// ## Errors
+            /// - `InvalidNoteName`: The text is not a note name, such as "H4" or "C#", or its octave is so far
+            ///   from C4 that the note number overflows
+            ///
+            /// ## Example
+            /// ```zig
+            /// const c_sharp = try Tuning(f64).Pitch.parse("C#4"); // Note 61
+            /// const b_flat = try Tuning(f64).Pitch.parse("Bb3"); // Note 58
+            /// const lowest = try Tuning(f64).Pitch.parse("C-1"); // Note 0
+            /// ```
+            pub fn parse(name: []const u8) TuningErrors!Pitch {
+                var length: usize = 0;
+                const offset: i32 = parseClass(name, &length) orelse return error.InvalidNoteName;
+
+                // Octaves which don't fit a note number are rejected rather than overflowing
+                const note_octave: i32 = std.fmt.parseInt(i32, name[length..], 10) catch return error.InvalidNoteName;
+                const octave_start: i32 = std.math.mul(i32, 12, std.math.add(i32, note_octave, 1) catch return error.InvalidNoteName) catch return error.InvalidNoteName;
+                return .{ .note = std.math.add(i32, octave_start, offset) catch return error.InvalidNoteName };
+            }
+
+            /// Parses the note name at the start of a text, without its octave: a letter from A to G
+            /// in either case, then any number of sharps (`#`) and flats (`b`).
+            ///
+            /// ## Parameters
+            /// - `text`: The text, such as "F#m7" or "Bb3"
+            /// - `length`: Set to the length of the note name
+            ///
+            /// ## Returns
+            /// The semitones from C to the note, which may be below 0 or above 11 ("Cb" is -1),
+            /// or `null` when the text doesn't start with a note name or has too many accidentals
+            pub fn parseClass(text: []const u8, length: *usize) ?i32 {
+                if (text.len == 0)
+                    return null;
+
+                const note_code: Code = switch (std.ascii.toLower(text[0])) {
+                    'c' => .c,
+                    'd' => .d,
+                    'e' => .e,
+                    'f' => .f,
+                    'g' => .g,
+                    'a' => .a,
+                    'b' => .b,
+                    else => return null,
+                };
+
+                var semitones: i32 = @intFromEnum(note_code);
+                var i: usize = 1;
+                while (i < text.len) : (i += 1) {
+                    switch (text[i]) {
+                        '#' => semitones = std.math.add(i32, semitones, 1) catch return null,
+                        'b' => semitones = std.math.sub(i32, semitones, 1) catch return null,
+                        else => break,
+                    }
+                }
+
+                length.* = i;
+                return semitones;
+            }
+
+            /// Returns the note code of the pitch, ignoring its cents.
+            pub fn code(self: Pitch) Code {
+                return @enumFromInt(@as(u8, @intCast(@mod(self.note, 12))));
+            }
+
+            /// Returns the octave of the pitch, ignoring its cents. C4 is in octave 4.
+            pub fn octave(self: Pitch) i32 {
+                return @divFloor(self.note, 12) - 1;
+            }
+
+            /// Moves the pitch by semitones (positive for up, negative for down).
+            pub fn add(self: Pitch, semitones: i32) Pitch {
+                return .{ .note = self.note + semitones, .cents = self.cents };
+            }
+
+            /// Moves the pitch by cents (positive for up, negative for down).
+            pub fn addCents(self: Pitch, cents: T) Pitch {
+                return .{ .note = self.note, .cents = self.cents + cents };
+            }
+
+            /// Returns the interval from another pitch to this one, in cents.
+            pub fn centsFrom(self: Pitch, other: Pitch) T {
+                return @as(T, @floatFromInt(self.note - other.note)) * 100.0 + self.cents - other.cents;
+            }
+        };
+
+        /// Returns the frequency of a pitch, in Hz. Its cents are applied on top of the tuning system.
+        ///
+        /// Notes which the keyboard leaves unmapped aren't meant to be played, and return 0 Hz.
+        pub fn frequency(self: Self, pitch: Pitch) T {
+            const ratio: T = self.ratioAt(pitch.note) orelse return 0.0;
+            const reference_ratio: T = self.ratioAt(self.reference_pitch.note) orelse return 0.0;
+
+            return self.reference_frequency * ratio / reference_ratio * @exp2((pitch.cents - self.reference_pitch.cents) / 1200.0);
+        }
+
+        /// Returns whether the keyboard maps a pitch to a degree of the scale.
+        pub fn isMapped(self: Self, pitch: Pitch) bool {
+            return self.ratioAt(pitch.note) != null;
+        }
+
+        /// Returns the frequency ratio from the tonic to a note, or `null` when it is unmapped.
+        fn ratioAt(self: Self, note: i32) ?T {
+            var degree: i64 = @as(i64, note) - self.tonic;
+            if (self.keyboard) |keyboard| {
+                if (note < keyboard.first_note or note > keyboard.last_note)
+                    return null;
+
+                const size: i64 = keyboard.map_size orelse @intCast(keyboard.map.len);
+                if (size > 0) {
+                    const index: usize = @intCast(@mod(degree, size));
+                    const mapped: u32 = (if (index < keyboard.map.len) keyboard.map[index] else null) orelse return null;
+                    degree = @divFloor(degree, size) * keyboard.octave_degree + mapped;
+                }
+            }
+
+            switch (self.system) {
+                .equal => |steps| {
+                    if (steps == 0)
+                        return null;
+                    return @exp2(@as(T, @floatFromInt(degree)) / @as(T, @floatFromInt(steps)));
+                },
+                .scale => |ratios| {
+                    if (ratios.len == 0)
+                        return null;
+
+                    const size: i64 = @intCast(ratios.len);
+                    const periods: T = @floatFromInt(@divFloor(degree, size));
+                    const step: usize = @intCast(@mod(degree, size));
+                    const ratio: T = if (step == 0) 1.0 else ratios[step - 1];
+
+                    return ratio * @exp2(periods * @log2(ratios[ratios.len - 1]));
+                },
+            }
+        }
+
+        /// Returns the mapped note nearest to a frequency, with the cents from that note to the frequency.
+        ///
+        /// ## Parameters
+        /// - `self`: The tuning, whose frequencies must rise with the note numbers
+        /// - `hz`: A frequency above 0 Hz
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: `hz` is not a finite frequency above 0 Hz
+        pub fn pitchAt(self: Self, hz: T) error{InvalidRange}!Pitch {
+            if (!(hz > 0.0) or std.math.isInf(hz))
+                return error.InvalidRange;
+
+            var note: i32 = self.reference_pitch.note;
+            var cents: T = centsBetween(self.frequency(.{ .note = note }), hz);
+
+            // Walk from the reference pitch while the notes get nearer
+            const direction: i32 = if (cents > 0.0) 1 else -1;
+            var next: i32 = note + direction;
+            var unmapped: usize = 0;
+            while (unmapped < 128) : (next += direction) {
+                const next_frequency: T = self.frequency(.{ .note = next });
+                if (next_frequency == 0.0) {
+                    unmapped += 1;
+                    continue;
+                }
+
+                const next_cents: T = centsBetween(next_frequency, hz);
+                if (@abs(next_cents) >= @abs(cents))
+                    break;
+
+                note = next;
+                cents = next_cents;
+                unmapped = 0;
+            }
+
+            return .{ .note = note, .cents = cents };
+        }
+
+        /// Returns the interval between two frequencies, in cents. It is negative when `to` is lower.
+        pub fn centsBetween(from: T, to: T) T {
+            return 1200.0 * @log2(to / from);
+        }
+
+        test "frequencies of notes" {
+            const tuning: Self = .{};
+
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 69 }), 440.0, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(.{ .note = 57 }), 220.0, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(Pitch.init(.c, 4)), 261.6255653005986, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(Pitch.fromMidi(69).addCents(1200.0)), 880.0, 0.000001);
+            try testing.expectApproxEqAbs(tuning.frequency(Pitch.fromMidi(69).addCents(-50.0)), 427.4740541075866, 0.000001);
+        }
+
+        test "configurable reference pitch" {
+            const concert: Self = .{ .reference_frequency = 442.0 };
+            try testing.expectApproxEqAbs(concert.frequency(.{ .note = 81 }), 884.0, 0.000001);
+
+            // Scientific pitch: C4 at 256 Hz
+            const scientific: Self = .{ .reference_frequency = 256.0, .reference_pitch = Pitch.init(.c, 4) };
+            try testing.expectApproxEqAbs(scientific.frequency(Pitch.init(.c, 5)), 512.0, 0.000001);
+            try testing.expectApproxEqAbs(scientific.frequency(Pitch.init(.g, 3)), 256.0 * @exp2(@as(T, -5.0) / 12.0), 0.000001);
+        }
+
+        test "pitch arithmetic" {
+            const c4 = Pitch.init(.c, 4);
+            try testing.expectEqual(c4.note, 60);
+
+            const a3 = c4.add(-3);
+            try testing.expectEqual(a3.code(), .a);
+            try testing.expectEqual(a3.octave(), 3);
+            try testing.expectEqual(Pitch.fromMidi(0).octave(), -1);
+            try testing.expectEqual(c4.add(-61).code(), .b);
+            try testing.expectEqual(c4.add(-61).octave(), -2);
+
+            try testing.expectEqual(try c4.toMidi(), 60);
+            try testing.expectEqual(try c4.addCents(60.0).toMidi(), 61);
+            try testing.expectEqual(try c4.addCents(-40.0).toMidi(), 60);
+            try testing.expectError(error.InvalidRange, Pitch.fromMidi(127).add(1).toMidi());
+            try testing.expectError(error.InvalidRange, Pitch.fromMidi(0).addCents(-60.0).toMidi());
+
+            try testing.expectApproxEqAbs(c4.addCents(25.0).centsFrom(a3), 325.0, 0.000001);
+        }
+
+        test "parse note names" {
+            try testing.expectEqual((try Pitch.parse("C4")).note, 60);
+            try testing.expectEqual((try Pitch.parse("C#4")).note, 61);
+            try testing.expectEqual((try Pitch.parse("Bb3")).note, 58);
+            try testing.expectEqual((try Pitch.parse("bb3")).note, 58);
+            try testing.expectEqual((try Pitch.parse("a4")).note, 69);
+            try testing.expectEqual((try Pitch.parse("Cb4")).note, 59);
+            try testing.expectEqual((try Pitch.parse("B#3")).note, 60);
+            try testing.expectEqual((try Pitch.parse("F##2")).note, 43);
+            try testing.expectEqual((try Pitch.parse("C-1")).note, 0);
+            try testing.expectEqual((try Pitch.parse("G9")).note, 127);
+
+            for ([_][]const u8{ "", "H4", "C", "C#", "4", "C 4", "C4#", "Cs4", "C999999999", "C-999999999", "B178956969" }) |name| {
+                try testing.expectError(error.InvalidNoteName, Pitch.parse(name));
+            }
+        }
+
+        test "parse note names without octave" {
+            var length: usize = 0;
+            try testing.expectEqual(Pitch.parseClass("C", &length), 0);
+            try testing.expectEqual(length, 1);
+            try testing.expectEqual(Pitch.parseClass("F#m7", &length), 6);
+            try testing.expectEqual(length, 2);
+            try testing.expectEqual(Pitch.parseClass("Bbb", &length), 9);
+            try testing.expectEqual(length, 3);
+            try testing.expectEqual(Pitch.parseClass("cb4", &length), -1);
+            try testing.expectEqual(length, 2);
+            try testing.expectEqual(Pitch.parseClass("H", &length), null);
+            try testing.expectEqual(Pitch.parseClass("", &length), null);
+        }
+
+        test "nearest pitch of frequencies" {
+            const tuning: Self = .{};
+
+            const a4 = try tuning.pitchAt(440.0);
+            try testing.expectEqual(a4.note, 69);
+            try testing.expectApproxEqAbs(a4.cents, 0.0, 0.000001);
+
+            const sharp = try tuning.pitchAt(445.0);
+            try testing.expectEqual(sharp.note, 69);
+            try testing.expectApproxEqAbs(sharp.cents, 19.56, 0.01);
+
+            const flat = try tuning.pitchAt(255.0);
+            try testing.expectEqual(flat.note, 60);
+            try testing.expectApproxEqAbs(tuning.frequency(flat), 255.0, 0.000001);
+
+            try testing.expectError(error.InvalidRange, tuning.pitchAt(0.0));
+            try testing.expectError(error.InvalidRange, tuning.pitchAt(-440.0));
+            try testing.expectError(error.InvalidRange, tuning.pitchAt(std.math.nan(T)));
+            try testing.expectError(error.InvalidRange, tuning.pitchAt(std.math.inf(T)));
+
+            try testing.expectApproxEqAbs(centsBetween(440.0, 880.0), 1200.0, 0.000001);
+            try testing.expectApproxEqAbs(centsBetween(440.0, 220.0), -1200.0, 0.000001);
+        }
+
+        test "equal divisions of the octave" {
+            const quarter_tones: Self = .{ .system = .{ .equal = 24 } };
+            try testing.expectApproxEqAbs(quarter_tones.frequency(.{ .note = 69 }), 440.0, 0.000001);
+            try testing.expectApproxEqAbs(quarter_tones.frequency(.{ .note = 70 }), 440.0 * @exp2(@as(T, 1.0) / 24.0), 0.000001);
+            try testing.expectApproxEqAbs(quarter_tones.frequency(.{ .note = 93 }), 880.0, 0.000001);
+
+            const edo19: Self = .{ .system = .{ .equal = 19 }, .reference_frequency = 261.6255653005986, .reference_pitch = .{ .note = 60 } };
+            try testing.expectApproxEqAbs(edo19.frequency(.{ .note = 79 }), 523.2511306011972, 0.000001);
+
+            const pitch = try quarter_tones.pitchAt(453.0);
+            try testing.expectEqual(pitch.note, 70);
+            try testing.expectApproxEqAbs(quarter_tones.frequency(pitch), 453.0, 0.000001);
+        }
+
+        test "just intonation" {
+            // Scala's default mapping: 1/1 on C4, and A4 at 440 Hz
+            const just: Self = .{ .system = .just_intonation };
+            try testing.expectApproxEqAbs(just.frequency(Pitch.init(.c, 4)), 264.0, 0.000001);
+            try testing.expectApproxEqAbs(just.frequency(Pitch.init(.e, 4)), 330.0, 0.000001);
+            try testing.expectApproxEqAbs(just.frequency(Pitch.init(.g, 4)), 396.0, 0.000001);
+            try testing.expectApproxEqAbs(just.frequency(Pitch.init(.a, 4)), 440.0, 0.000001);
+            try testing.expectApproxEqAbs(just.frequency(Pitch.init(.e, 2)), 82.5, 0.000001);
+            try testing.expectApproxEqAbs(just.frequency(Pitch.init(.c, 6)), 1056.0, 0.000001);
+
+            // In D: the tonic sounds at A4 * 3/4
+            const in_d: Self = .{ .system = .just_intonation, .tonic = 62 };
+            try testing.expectApproxEqAbs(in_d.frequency(Pitch.init(.d, 4)), 293.3333333333333, 0.000001);
+            try testing.expectApproxEqAbs(in_d.frequency(Pitch.init(.fs, 4)), 366.6666666666667, 0.000001);
+
+            const pitch = try just.pitchAt(330.0);
+            try testing.expectEqual(pitch.note, 64);
+            try testing.expectApproxEqAbs(pitch.cents, 0.0, 0.000001);
+        }
+
+        test "Pythagorean tuning and quarter-comma meantone" {
+            const pythagorean: Self = .{ .system = .pythagorean, .reference_frequency = 260.0, .reference_pitch = .{ .note = 60 } };
+            try testing.expectApproxEqAbs(pythagorean.frequency(Pitch.init(.g, 4)), 390.0, 0.000001);
+            try testing.expectApproxEqAbs(pythagorean.frequency(Pitch.init(.e, 4)), 260.0 * 81.0 / 64.0, 0.000001);
+            try testing.expectApproxEqAbs(pythagorean.frequency(Pitch.init(.ds, 4)), 260.0 * 32.0 / 27.0, 0.000001);
+            try testing.expectApproxEqAbs(pythagorean.frequency(Pitch.init(.fs, 4)), 260.0 * 729.0 / 512.0, 0.000001);
+
+            const meantone: Self = .{ .system = .quarter_comma_meantone, .reference_frequency = 260.0, .reference_pitch = .{ .note = 60 } };
+            try testing.expectApproxEqAbs(meantone.frequency(Pitch.init(.e, 4)), 325.0, 0.000001);
+            try testing.expectApproxEqAbs(meantone.frequency(Pitch.init(.g, 4)), 260.0 * @sqrt(@sqrt(@as(T, 5.0))), 0.000001);
+            try testing.expectApproxEqAbs(meantone.frequency(Pitch.init(.gs, 4)), 260.0 * 25.0 / 16.0, 0.000001);
+            try testing.expectApproxEqAbs(meantone.frequency(Pitch.init(.c, 5)), 520.0, 0.000001);
+        }
+
+        test "keyboard mappings" {
+            // The white keys play a 7-note scale, and the black keys are unmapped
+            const major: Self = .{
+                .system = .{ .scale = &.{ 9.0 / 8.0, 5.0 / 4.0, 4.0 / 3.0, 3.0 / 2.0, 5.0 / 3.0, 15.0 / 8.0, 2.0 } },
+                .keyboard = .{
+                    .map = &.{ 0, null, 1, null, 2, 3, null, 4, null, 5, null, 6 },
+                    .octave_degree = 7,
+                    .first_note = 21,
+                    .last_note = 108,
+                },
+            };
+
+            try testing.expectApproxEqAbs(major.frequency(Pitch.init(.c, 4)), 264.0, 0.000001);
+            try testing.expectApproxEqAbs(major.frequency(Pitch.init(.d, 4)), 297.0, 0.000001);
+            try testing.expectApproxEqAbs(major.frequency(Pitch.init(.b, 3)), 247.5, 0.000001);
+            try testing.expectApproxEqAbs(major.frequency(Pitch.init(.c, 5)), 528.0, 0.000001);
+
+            try testing.expect(!major.isMapped(Pitch.init(.cs, 4)));
+            try testing.expectEqual(major.frequency(Pitch.init(.cs, 4)), 0.0);
+            try testing.expect(!major.isMapped(.{ .note = 20 }));
+            try testing.expect(major.isMapped(.{ .note = 21 }));
+
+            // Unmapped notes are skipped
+            try testing.expectEqual((try major.pitchAt(285.0)).note, 62);
+        }
+    };
+}
+
+test "Run tests for each samples' type" {
+    _ = inner(f128);
+    _ = inner(f80);
+    _ = inner(f64);
+    // _ = inner(f32); zigggwavvv 0.2.1 cannot use f32 as samples' type
+}
@@ -2,6 +2,11 @@ const std = @import("std");
 const zigggwavvv = @import("zigggwavvv");
 const zaudio = @import("zaudio");
 const testing = std.testing;
+const Error = @import("./error.zig").Error;
+const Aiff = @import("./aiff.zig").inner;
+const Flac = @import("./flac.zig").inner;
+const Ogg = @import("./ogg.zig").inner;
+const Raw = @import("./raw.zig").inner;
 
 /// Wave type function: Creates a Wave type for the specified sample type.
 ///
@@ -28,9 +33,16 @@ pub fn inner(comptime T: type) type {
         sample_rate: u32,
         channels: u16,
 
-        /// Supported audio file formats for reading wave data.
+        /// Supported audio file formats for reading and writing wave data.
+        ///
+        /// `.ogg` reads Ogg Vorbis files, and cannot be written.
+        /// `.raw` writes headerless samples, and `readRaw` reads them with their format.
         pub const LowLevelInterfaces = enum {
             wav,
+            aiff,
+            flac,
+            ogg,
+            raw,
 
             /// Reads wave data using the specified file format.
             ///
@@ -43,7 +55,8 @@ pub fn inner(comptime T: type) type {
             /// A `LowLevelWave` containing the decoded samples, sample rate, and channel count
             ///
             /// ## Errors
-            /// Returns errors from the underlying format decoder or allocation failures
+            /// - `MissingReadOptions`: `.raw` data has no header describing it, so it must be read with `readRaw`
+            /// - Any error from the underlying format decoder or allocation failures
             pub fn read(self: LowLevelInterfaces, allocator: std.mem.Allocator, reader: anytype) anyerror!LowLevelWave {
                 return switch (self) {
                     .wav => {
@@ -55,6 +68,56 @@ pub fn inner(comptime T: type) type {
                             .channels = v.channels,
                         };
                     },
+                    .aiff => {
+                        const v = try Aiff(T).read(allocator, reader);
+
+                        return .{
+                            .samples = v.samples,
+                            .sample_rate = v.sample_rate,
+                            .channels = v.channels,
+                        };
+                    },
+                    .flac => {
+                        const v = try Flac(T).read(allocator, reader);
+
+                        return .{
+                            .samples = v.samples,
+                            .sample_rate = v.sample_rate,
+                            .channels = v.channels,
+                        };
+                    },
+                    .ogg => {
+                        const v = try Ogg(T).read(allocator, reader);
+
+                        return .{
+                            .samples = v.samples,
+                            .sample_rate = v.sample_rate,
+                            .channels = v.channels,
+                        };
+                    },
+                    .raw => error.MissingReadOptions,
+                };
+            }
+
+            /// Reads headerless samples, whose format is given in the options.
+            ///
+            /// ## Parameters
+            /// - `allocator`: Memory allocator for sample data
+            /// - `reader`: A reader interface providing the raw bytes
+            /// - `options`: Encoding, byte order, sample rate and channel count of the samples
+            ///
+            /// ## Returns
+            /// A `LowLevelWave` containing the decoded samples, sample rate, and channel count
+            ///
+            /// ## Errors
+            /// Returns errors from the raw decoder or allocation failures
+            pub fn readRaw(allocator: std.mem.Allocator, reader: anytype, options: readRawOptions) anyerror!LowLevelWave {
+                const v = try Raw(T).read(allocator, reader, options);
+
+                return .{
+                    .samples = v.samples,
+                    .sample_rate = v.sample_rate,
+                    .channels = v.channels,
                 };
             }
 
@@ -87,6 +150,10 @@ pub fn inner(comptime T: type) type {
                             .peak_timestamp = options.peak_timestamp,
                         });
                     },
+                    .aiff => try Aiff(T).write(wave.samples, wave.sample_rate, wave.channels, writer, options),
+                    .flac => try Flac(T).write(wave.allocator, wave.samples, wave.sample_rate, wave.channels, writer, options),
+                    .ogg => unreachable,
+                    .raw => try Raw(T).write(wave.samples, writer, options),
                 }
             }
 
@@ -100,6 +167,10 @@ pub fn inner(comptime T: type) type {
             pub fn writeOptions(interface: LowLevelInterfaces) type {
                 return switch (interface) {
                     .wav => writeWavOptions,
+                    .aiff => writeAiffOptions,
+                    .flac => writeFlacOptions,
+                    .ogg => @compileError("Ogg Vorbis files can only be read"),
+                    .raw => writeRawOptions,
                 };
             }
 
@@ -118,6 +189,24 @@ pub fn inner(comptime T: type) type {
                 format_code: zigggwavvv.FormatCode,
             };
 
+            /// Options for writing wave data to an AIFF file.
+            ///
+            /// `.pcm` writes 8, 16, 24 or 32-bit integers into an AIFF file,
+            /// and `.ieee_float` writes 32 or 64-bit floats into an AIFF-C file.
+            pub const writeAiffOptions = Aiff(T).WriteOptions;
+
+            /// Options for writing wave data to a FLAC file.
+            ///
+            /// FLAC is lossless: reading the file gives back the samples quantized to `bits`.
+            /// Higher compression levels make smaller files and take longer to encode.
+            pub const writeFlacOptions = Flac(T).WriteOptions;
+
+            /// Options for reading headerless samples: their encoding, byte order, sample rate and channel count.
+            pub const readRawOptions = Raw(T).ReadOptions;
+
+            /// Options for writing headerless samples: their encoding and byte order.
+            pub const writeRawOptions = Raw(T).WriteOptions;
+
             /// Raw wave data returned by low-level format decoders.
             pub const LowLevelWave = struct {
                 samples: []const T,
@@ -164,9 +253,49 @@ pub fn inner(comptime T: type) type {
             };
         }
 
-        /// Options for mixing two waves together.
+        /// Options for mixing waves together.
         pub const mixOptions = struct {
-            mixer: fn (T, T) T = default_mixing_expression,
+            /// Function folding two samples into one, used by the `.mixer` mode
+            mixer: *const fn (T, T) T = default_mixing_expression,
+            /// How the samples of every wave are combined
+            mode: MixMode = .mixer,
+        };
+
+        /// Strategies for combining the samples of N waves.
+        pub const MixMode = union(enum) {
+            /// Folds the samples pairwise with `mixOptions.mixer` (plain addition by default)
+            mixer,
+            /// Adds the samples
+            sum,
+            /// Adds the samples, then divides by the number of waves
+            average,
+            /// Adds the samples, then divides by the square root of the number of waves.
+            /// Uncorrelated waves keep their loudness, while leaving headroom against clipping.
+            headroom,
+            /// Adds the samples, then saturates the sum smoothly into (-1.0, 1.0) with tanh
+            soft_clip,
+            /// Multiplies each wave by its own linear gain, then adds the samples.
+            /// It must contain one gain per wave.
+            weighted: []const T,
+
+            /// Returns the gain applied to the wave at `index` before it is added.
+            pub fn weight(self: MixMode, index: usize) T {
+                return switch (self) {
+                    .weighted => |weights| weights[index],
+                    else => 1.0,
+                };
+            }
+
+            /// Turns the sum of `count` weighted samples into the mixed sample.
+            pub fn finish(self: MixMode, sum: T, count: usize) T {
+                return switch (self) {
+                    .mixer, .sum, .weighted => sum,
+                    .average => if (count == 0) sum else sum / @as(T, @floatFromInt(count)),
+                    .headroom => if (count == 0) sum else sum / @sqrt(@as(T, @floatFromInt(count))),
+                    // tanh(x) = 1 - 2 / (exp(2x) + 1), which stays finite for large |x|
+                    .soft_clip => 1.0 - 2.0 / (@exp(2.0 * sum) + 1.0),
+                };
+            }
         };
 
         /// Default mixing function that adds two samples together.
@@ -185,46 +314,168 @@ pub fn inner(comptime T: type) type {
         /// Mixes this wave with another wave, combining their samples.
         ///
         /// Both waves must have the same length, sample rate, and channel count.
-        /// The mixing is performed sample-by-sample using the provided mixer function.
+        /// The mixing is performed sample-by-sample following `options.mode`.
         ///
         /// ## Parameters
         /// - `self`: The first wave to mix
         /// - `other`: The second wave to mix
-        /// - `options`: Mixing options (includes the mixer function)
+        /// - `options`: Mixing options (mix mode and mixer function)
         ///
         /// ## Returns
         /// A new Wave containing the mixed result
         ///
         /// ## Errors
-        /// - Allocator error (errors.OutOfMemory)
-        pub fn mix(self: Self, other: Self, options: mixOptions) std.mem.Allocator.Error!Self {
-            std.debug.assert(self.samples.len == other.samples.len);
-            std.debug.assert(self.sample_rate == other.sample_rate);
-            std.debug.assert(self.channels == other.channels);
+        /// - `SampleRateMismatch`: The waves have different sample rates
+        /// - `ChannelMismatch`: The waves have different channel counts
+        /// - `LengthMismatch`: The waves have different lengths, or `.weighted` doesn't contain two gains
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn mix(self: Self, other: Self, options: mixOptions) (Error || std.mem.Allocator.Error)!Self {
+            return mixAll(&[_]Self{ self, other }, options);
+        }
 
-            if (self.samples.len == 0)
+        /// Mixes any number of waves at once, combining their samples.
+        ///
+        /// Every wave must have the same length, sample rate, and channel count.
+        /// The result uses the allocator of the first wave.
+        ///
+        /// ## Parameters
+        /// - `waves`: The waves to mix; at least one
+        /// - `options`: Mixing options (mix mode and mixer function)
+        ///
+        /// ## Returns
+        /// A new Wave containing the mixed result
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: `waves` is empty
+        /// - `SampleRateMismatch`: The waves have different sample rates
+        /// - `ChannelMismatch`: The waves have different channel counts
+        /// - `LengthMismatch`: The waves have different lengths, or `.weighted` doesn't contain one gain per wave
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        ///
+        /// ## Example
+        /// ```zig
+        /// const chord = try Wave(f64).mixAll(&.{ c4, e4, g4 }, .{ .mode = .headroom });
+        /// defer chord.deinit();
+        ///
+        /// const balanced = try Wave(f64).mixAll(&.{ c4, e4, g4 }, .{ .mode = .{ .weighted = &.{ 0.5, 0.3, 0.2 } } });
+        /// defer balanced.deinit();
+        /// ```
+        pub fn mixAll(waves: []const Self, options: mixOptions) (Error || std.mem.Allocator.Error)!Self {
+            if (waves.len == 0)
+                return error.InvalidRange;
+
+            const first: Self = waves[0];
+            for (waves) |wave| {
+                if (wave.sample_rate != first.sample_rate)
+                    return error.SampleRateMismatch;
+                if (wave.channels != first.channels)
+                    return error.ChannelMismatch;
+                if (wave.samples.len != first.samples.len)
+                    return error.LengthMismatch;
+            }
+
+            switch (options.mode) {
+                .weighted => |weights| if (weights.len != waves.len) return error.LengthMismatch,
+                else => {},
+            }
+
+            if (first.samples.len == 0)
                 return Self{
                     .samples = &[_]T{},
-                    .allocator = self.allocator,
+                    .allocator = first.allocator,
 
-                    .sample_rate = self.sample_rate,
-                    .channels = self.channels,
+                    .sample_rate = first.sample_rate,
+                    .channels = first.channels,
                 };
 
-            var samples: std.array_list.Aligned(T, null) = .empty;
+            const samples: []T = try first.allocator.alloc(T, first.samples.len);
 
-            for (0..self.samples.len) |i| {
-                const left: T = self.samples[i];
-                const right: T = other.samples[i];
-                const result: T = options.mixer(left, right);
+            for (0..samples.len) |i| {
+                if (options.mode == .mixer) {
+                    var result: T = first.samples[i];
+                    for (waves[1..]) |wave| {
+                        result = options.mixer(result, wave.samples[i]);
+                    }
+                    samples[i] = result;
+                    continue;
+                }
 
-                try samples.append(self.allocator, result);
+                var sum: T = 0.0;
+                for (waves, 0..) |wave, index| {
+                    sum += wave.samples[i] * options.mode.weight(index);
+                }
+                samples[i] = options.mode.finish(sum, waves.len);
             }
 
-            const result: []const T = try samples.toOwnedSlice(self.allocator);
+            return Self{
+                .samples = samples,
+                .allocator = first.allocator,
+
+                .sample_rate = first.sample_rate,
+                .channels = first.channels,
+            };
+        }
+
+        /// Mixes another wave into this one, starting at a frame offset.
+        ///
+        /// Unlike `mix`, the waves may have different lengths: the shorter one is treated as
+        /// silence where it ends, so the result lasts until the end of the longer one.
+        /// `.average` and `.headroom` scale each sample by the number of waves sounding there,
+        /// as `Composer.finalize` does.
+        ///
+        /// ## Parameters
+        /// - `self`: The first wave to mix, starting at frame 0
+        /// - `other`: The second wave to mix, starting at frame `offset`
+        /// - `offset`: Frame index of `self` where `other` starts; it may be after the end of `self`
+        /// - `options`: Mixing options (mix mode and mixer function). `.weighted` must contain two gains.
+        ///
+        /// ## Returns
+        /// A new Wave of `max(frames of self, offset + frames of other)` frames
+        ///
+        /// ## Errors
+        /// - `SampleRateMismatch`: The waves have different sample rates
+        /// - `ChannelMismatch`: The waves have different channel counts
+        /// - `LengthMismatch`: `.weighted` doesn't contain two gains
+        /// - `InvalidRange`: The offset is so large that the sample count of the result overflows
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        ///
+        /// ## Example
+        /// ```zig
+        /// // Overlay a hit one second into a loop
+        /// const overlay = try loop.mixAt(hit, 44100, .{});
+        /// defer overlay.deinit();
+        /// ```
+        pub fn mixAt(self: Self, other: Self, offset: usize, options: mixOptions) (Error || std.mem.Allocator.Error)!Self {
+            if (self.sample_rate != other.sample_rate)
+                return error.SampleRateMismatch;
+            if (self.channels != other.channels)
+                return error.ChannelMismatch;
+
+            switch (options.mode) {
+                .weighted => |weights| if (weights.len != 2) return error.LengthMismatch,
+                else => {},
+            }
+
+            const start: usize = std.math.mul(usize, offset, self.channels) catch return error.InvalidRange;
+            const end: usize = std.math.add(usize, start, other.samples.len) catch return error.InvalidRange;
+            const length: usize = @max(self.samples.len, end);
+            const samples: []T = try self.allocator.alloc(T, length);
+
+            for (0..length) |i| {
+                const has_left: bool = i < self.samples.len;
+                const has_right: bool = i >= start and i - start < other.samples.len;
+                const left: T = if (has_left) self.samples[i] else 0.0;
+                const right: T = if (has_right) other.samples[i - start] else 0.0;
+                const count: usize = @as(usize, @intFromBool(has_left)) + @intFromBool(has_right);
+
+                samples[i] = if (options.mode == .mixer)
+                    options.mixer(left, right)
+                else
+                    options.mode.finish(left * options.mode.weight(0) + right * options.mode.weight(1), count);
+            }
 
             return Self{
-                .samples = result,
+                .samples = samples,
                 .allocator = self.allocator,
 
                 .sample_rate = self.sample_rate,
@@ -234,9 +485,10 @@ pub fn inner(comptime T: type) type {
 
         /// Separates the wave into two parts at a specified point.
         ///
-        /// This function splits the wave's samples into two new Wave instances:
-        /// one containing samples from the start to the separation point,
-        /// and another containing samples from the separation point to the end.
+        /// This function splits the wave's frames into two new Wave instances:
+        /// one containing frames from the start to the separation point,
+        /// and another containing frames from the separation point to the end.
+        /// Every channel of a frame stays in the same part.
         ///
         /// ## Parameters
         /// - `self`: The wave to separate
@@ -247,7 +499,7 @@ pub fn inner(comptime T: type) type {
         ///
         /// ## Errors
         /// - `SeparatingZeroLengthWave`: If the wave has no samples
-        /// - `TooBigSeparatePoint`: If the separation point exceeds the wave length
+        /// - `TooBigSeparatePoint`: If the separation point exceeds the wave's frame count
         /// - `OutOfMemory`: Allocator error when memory allocation fails
         pub fn separate(
             self: Self,
@@ -256,11 +508,11 @@ pub fn inner(comptime T: type) type {
             if (self.samples.len == 0)
                 return error.SeparatingZeroLengthWave;
 
-            if (self.samples.len < options.separate_point)
+            if (self.frameCount() < options.separate_point)
                 return error.TooBigSeparatePoint;
 
-            const initial_len = options.separate_point;
-            const terminal_len = self.samples.len - options.separate_point;
+            const initial_len = options.separate_point * self.channels;
+            const terminal_len = self.samples.len - initial_len;
             var initial: []T = try options.allocator.alloc(T, initial_len);
             var terminal: []T = try options.allocator.alloc(T, terminal_len);
 
@@ -293,7 +545,7 @@ pub fn inner(comptime T: type) type {
         pub const SeparateOptions = struct {
             /// Memory allocator for the new wave instances
             allocator: std.mem.Allocator,
-            /// Sample index at which to split the wave (exclusive for initial, inclusive for terminal)
+            /// Frame index at which to split the wave (exclusive for initial, inclusive for terminal)
             separate_point: usize,
         };
 
@@ -309,7 +561,7 @@ pub fn inner(comptime T: type) type {
         pub const SeparateErrors = error{
             /// Attempted to separate a wave with zero samples
             SeparatingZeroLengthWave,
-            /// The separation point exceeds the wave's sample length
+            /// The separation point exceeds the wave's frame count
             TooBigSeparatePoint,
         };
 
@@ -326,26 +578,20 @@ pub fn inner(comptime T: type) type {
         /// A new Wave with samples from 0 to `start`, then zeros from `start` to `end`
         ///
         /// ## Errors
-        /// - Allocator error (errors.OutOfMemory)
-        pub fn fill_zero_to_end(self: Self, start: usize, end: usize) std.mem.Allocator.Error!Self {
+        /// - `InvalidRange`: `start` exceeds the wave's length, or `end` is lower than `start`
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn fill_zero_to_end(self: Self, start: usize, end: usize) (Error || std.mem.Allocator.Error)!Self {
+            if (start > self.samples.len or end < start)
+                return error.InvalidRange;
+
             // Initialization
             var result: std.array_list.Aligned(T, null) = .empty;
-            try result.appendSlice(self.allocator, self.samples);
-
-            const delete_count: usize = result.items.len - start;
-
-            for (0..delete_count) |_| {
-                _ = result.pop();
-            }
-
-            std.debug.assert(start == result.items.len);
+            try result.appendSlice(self.allocator, self.samples[0..start]);
 
-            for (delete_count..end) |_| {
+            for (start..end) |_| {
                 try result.append(self.allocator, 0.0);
             }
 
-            std.debug.assert(result.items.len == end);
-
             return Self{
                 .samples = try result.toOwnedSlice(self.allocator),
                 .allocator = self.allocator,
@@ -407,8 +653,413 @@ pub fn inner(comptime T: type) type {
             };
         }
 
+        /// Returns the number of frames of the wave. A frame holds one sample per channel.
+        pub fn frameCount(self: Self) usize {
+            if (self.channels == 0)
+                return 0;
+
+            return self.samples.len / self.channels;
+        }
+
+        /// How `toMono` combines the channels of a frame.
+        pub const DownmixMode = enum {
+            /// The mean of every channel; keeps the level of identical channels
+            average,
+            /// The sum of every channel; may exceed the original peak level
+            sum,
+            /// The sum of every channel divided by sqrt(channels); keeps the power of uncorrelated channels
+            equal_power,
+        };
+
+        /// Downmixes every channel of the wave into one channel.
+        ///
+        /// ## Parameters
+        /// - `self`: The wave to downmix
+        /// - `mode`: How the channels of a frame are combined
+        ///
+        /// ## Returns
+        /// A new mono Wave with the same frame count
+        ///
+        /// ## Errors
+        /// - Allocator error (errors.OutOfMemory)
+        pub fn toMono(self: Self, mode: DownmixMode) std.mem.Allocator.Error!Self {
+            const channels: usize = self.channels;
+            const samples: []T = try self.allocator.alloc(T, self.frameCount());
+
+            const scale: T = switch (mode) {
+                .average => 1.0 / @as(T, @floatFromInt(channels)),
+                .sum => 1.0,
+                .equal_power => 1.0 / @sqrt(@as(T, @floatFromInt(channels))),
+            };
+
+            for (0..samples.len) |frame| {
+                var total: T = 0.0;
+                for (self.samples[frame * channels .. (frame + 1) * channels]) |sample| {
+                    total += sample;
+                }
+                samples[frame] = total * scale;
+            }
+
+            return Self{
+                .samples = samples,
+                .allocator = self.allocator,
+
+                .sample_rate = self.sample_rate,
+                .channels = 1,
+            };
+        }
+
+        /// Converts the wave to two channels.
+        ///
+        /// A mono wave is copied to both channels, and a stereo wave is cloned.
+        /// A wave with more channels is downmixed following the WAVE channel order: the front
+        /// left and right channels are kept, the front center goes to both sides at -3 dB,
+        /// the low-frequency effects channel is dropped, and the next channels go to the left
+        /// (even indices) or the right (odd indices) at -3 dB.
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: The wave has no channel
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn toStereo(self: Self) (Error || std.mem.Allocator.Error)!Self {
+            switch (self.channels) {
+                0 => return error.InvalidRange,
+                1 => return self.upmix(2),
+                2 => return self.clone(null),
+                else => {},
+            }
+
+            const channels: usize = self.channels;
+            const frame_count: usize = self.frameCount();
+            const samples: []T = try self.allocator.alloc(T, frame_count * 2);
+
+            for (0..frame_count) |frame| {
+                const input: []const T = self.samples[frame * channels .. (frame + 1) * channels];
+                var left: T = input[0];
+                var right: T = input[1];
+
+                for (input[2..], 2..) |sample, ch| {
+                    switch (ch) {
+                        2 => {
+                            left += sample * std.math.sqrt1_2;
+                            right += sample * std.math.sqrt1_2;
+                        },
+                        3 => {},
+                        else => if (ch % 2 == 0) {
+                            left += sample * std.math.sqrt1_2;
+                        } else {
+                            right += sample * std.math.sqrt1_2;
+                        },
+                    }
+                }
+
+                samples[frame * 2] = left;
+                samples[frame * 2 + 1] = right;
+            }
+
+            return Self{
+                .samples = samples,
+                .allocator = self.allocator,
+
+                .sample_rate = self.sample_rate,
+                .channels = 2,
+            };
+        }
+
+        /// Converts the wave to a larger number of channels.
+        ///
+        /// A mono wave is copied to every channel. Otherwise, the existing channels are kept
+        /// and the new channels are silent.
+        ///
+        /// ## Parameters
+        /// - `self`: The wave to upmix
+        /// - `channels`: The channel count of the new wave
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: `channels` is lower than the wave's channel count
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn upmix(self: Self, channels: u16) (Error || std.mem.Allocator.Error)!Self {
+            if (channels < self.channels)
+                return error.InvalidRange;
+
+            const frame_count: usize = self.frameCount();
+            const samples: []T = try self.allocator.alloc(T, frame_count * channels);
+
+            for (0..frame_count) |frame| {
+                for (0..channels) |ch| {
+                    samples[frame * channels + ch] = if (self.channels == 1)
+                        self.samples[frame]
+                    else if (ch < self.channels)
+                        self.samples[frame * self.channels + ch]
+                    else
+                        0.0;
+                }
+            }
+
+            return Self{
+                .samples = samples,
+                .allocator = self.allocator,
+
+                .sample_rate = self.sample_rate,
+                .channels = channels,
+            };
+        }
+
+        /// Extracts one channel of the wave as a mono wave.
+        ///
+        /// ## Parameters
+        /// - `self`: The wave to read
+        /// - `channel`: Index of the channel, starting from 0
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: `channel` is not lower than the wave's channel count
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn selectChannel(self: Self, channel: u16) (Error || std.mem.Allocator.Error)!Self {
+            return self.selectChannels(&[_]u16{channel});
+        }
+
+        fn selectChannels(self: Self, selected: []const u16) (Error || std.mem.Allocator.Error)!Self {
+            for (selected) |channel| {
+                if (channel >= self.channels)
+                    return error.InvalidRange;
+            }
+
+            const frame_count: usize = self.frameCount();
+            const samples: []T = try self.allocator.alloc(T, frame_count * selected.len);
+
+            for (0..frame_count) |frame| {
+                for (selected, 0..) |channel, i| {
+                    samples[frame * selected.len + i] = self.samples[frame * self.channels + channel];
+                }
+            }
+
+            return Self{
+                .samples = samples,
+                .allocator = self.allocator,
+
+                .sample_rate = self.sample_rate,
+                .channels = @intCast(selected.len),
+            };
+        }
+
+        /// Splits the wave into one mono wave per channel.
+        ///
+        /// ## Returns
+        /// A slice of mono waves in channel order, allocated with `self.allocator`.
+        /// The caller must deinit every wave and free the slice.
+        ///
+        /// ## Errors
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        ///
+        /// ## Example
+        /// ```zig
+        /// const channels = try stereo.splitChannels();
+        /// defer {
+        ///     for (channels) |channel| channel.deinit();
+        ///     allocator.free(channels);
+        /// }
+        /// ```
+        pub fn splitChannels(self: Self) (Error || std.mem.Allocator.Error)![]Self {
+            const result: []Self = try self.allocator.alloc(Self, self.channels);
+            errdefer self.allocator.free(result);
+
+            for (0..result.len) |ch| {
+                errdefer for (result[0..ch]) |wave| wave.deinit();
+
+                result[ch] = try self.selectChannel(@intCast(ch));
+            }
+
+            return result;
+        }
+
+        /// Builds a multi-channel wave from mono waves, one per channel.
+        ///
+        /// ## Parameters
+        /// - `waves`: Mono waves in channel order, sharing the same sample rate and frame count
+        /// - `allocator`: Memory allocator for the new wave
+        ///
+        /// ## Errors
+        /// - `InvalidRange`: `waves` is empty, or has more waves than a wave can have channels
+        /// - `ChannelMismatch`: A wave is not mono
+        /// - `SampleRateMismatch`: The waves have different sample rates
+        /// - `LengthMismatch`: The waves have different frame counts
+        /// - `OutOfMemory`: Allocator error when memory allocation fails
+        pub fn interleave(waves: []const Self, allocator: std.mem.Allocator) (Error || std.mem.Allocator.Error)!Self {
+            if (waves.len == 0 or waves.len > std.math.maxInt(u16))
+                return error.InvalidRange;
+
+            for (waves) |wave| {
+                if (wave.channels != 1)
+                    return error.ChannelMismatch;
+                if (wave.sample_rate != waves[0].sample_rate)
+                    return error.SampleRateMismatch;
+                if (wave.samples.len != waves[0].samples.len)
+                    return error.LengthMismatch;
+            }
+
+            const channels: usize = waves.len;
+            const frame_count: usize = waves[0].samples.len;
+            const samples: []T = try allocator.alloc(T, frame_count * channels);
+
+            for (waves, 0..) |wave, ch| {
+                for (wave.samples, 0..) |sample, frame| {
+                    samples[frame * channels + ch] = sample;
+                }
+            }
+
+            return Self{
+                .samples = samples,
+                .allocator = allocator,
+
+                .sample_rate = waves[0].sample_rate,
+                .channels = @intCast(channels),
+            };
+        }
+
+        /// Interpolation methods for `resample`.
+        pub const ResampleQuality = enum {
+            /// Linear interpolation between two neighbouring frames (fastest)
+            linear,
+            /// Catmull-Rom cubic interpolation over four neighbouring frames
+            cubic,
+            /// Blackman-windowed sinc interpolation, which also removes frequencies above
+            /// the new Nyquist frequency when downsampling (slowest, most accurate)
+            sinc,
+        };
+
+        /// Number of zero crossings on each side of the windowed sinc kernel.
+        const sinc_zero_crossings: T = 16.0;
+
+        /// Converts the wave to another sample rate, keeping its pitch and duration.
+        ///
+        /// ## Parameters
+        /// - `self`: The wave to convert
+        /// - `target_rate`: The sample rate of the new wave
+        /// - `quality`: The interpolation method
+        ///
+        /// ## Returns
+        /// A new Wave with `round(frames * target_rate / sample_rate)` frames.
+        /// If the sample rates are already equal, the result is a clone.
+        ///
+        /// ## Errors
+        /// - Allocator error (errors.OutOfMemory)
+        ///
+        /// ## Example
+        /// ```zig
+        /// // Mix a 48 kHz sample with 44.1 kHz synth output
+        /// const converted = try sample.resample(44100, .sinc);
+        /// defer converted.deinit();
+        /// ```
+        pub fn resample(self: Self, target_rate: u32, quality: ResampleQuality) std.mem.Allocator.Error!Self {
+            if (self.sample_rate == target_rate)
+                return self.clone(null);
+
+            const channels: usize = self.channels;
+            const new_frames: usize = self.resampledFrameCount(target_rate);
+
+            const samples: []T = try self.allocator.alloc(T, new_frames * channels);
+
+            // Position of each new frame, measured in frames of the original wave
+            const step: T = @as(T, @floatFromInt(self.sample_rate)) / @as(T, @floatFromInt(target_rate));
+            // Cutoff of the sinc kernel, relative to the original Nyquist frequency
+            const cutoff: T = @min(1.0, @as(T, @floatFromInt(target_rate)) / @as(T, @floatFromInt(self.sample_rate)));
+
+            for (0..new_frames) |j| {
+                const position: T = @as(T, @floatFromInt(j)) * step;
+
+                for (0..channels) |ch| {
+                    samples[j * channels + ch] = switch (quality) {
+                        .linear => self.interpolateLinear(position, ch),
+                        .cubic => self.interpolateCubic(position, ch),
+                        .sinc => self.interpolateSinc(position, ch, cutoff),
+                    };
+                }
+            }
+
+            return Self{
+                .samples = samples,
+                .allocator = self.allocator,
+
+                .sample_rate = target_rate,
+                .channels = self.channels,
+            };
+        }
+
+        /// Returns the number of frames `resample` produces for a target sample rate.
+        pub fn resampledFrameCount(self: Self, target_rate: u32) usize {
+            const source_rate: u64 = self.sample_rate;
+            if (source_rate == 0)
+                return 0;
+
+            return @intCast((@as(u64, self.frameCount()) * target_rate + source_rate / 2) / source_rate);
+        }
+
+        /// Returns a sample of a channel at a frame index, which may be out of range.
+        /// Out of range frames are clamped to the first or last frame when `clamp` is true,
+        /// and are silent otherwise.
+        fn frameSample(self: Self, frame: isize, ch: usize, clamp: bool) T {
+            const frames: isize = @intCast(self.samples.len / self.channels);
+
+            if (frame < 0 or frame >= frames) {
+                if (!clamp)
+                    return 0.0;
+
+                const clamped: usize = @intCast(std.math.clamp(frame, 0, frames - 1));
+                return self.samples[clamped * self.channels + ch];
+            }
+
+            return self.samples[@as(usize, @intCast(frame)) * self.channels + ch];
+        }
+
+        fn interpolateLinear(self: Self, position: T, ch: usize) T {
+            const i: isize = @intFromFloat(@floor(position));
+            const f: T = position - @floor(position);
+
+            return self.frameSample(i, ch, true) * (1.0 - f) + self.frameSample(i + 1, ch, true) * f;
+        }
+
+        fn interpolateCubic(self: Self, position: T, ch: usize) T {
+            const i: isize = @intFromFloat(@floor(position));
+            const f: T = position - @floor(position);
+
+            const p0: T = self.frameSample(i - 1, ch, true);
+            const p1: T = self.frameSample(i, ch, true);
+            const p2: T = self.frameSample(i + 1, ch, true);
+            const p3: T = self.frameSample(i + 2, ch, true);
+
+            // Catmull-Rom spline
+            return p1 + 0.5 * f * (p2 - p0 + f * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3 + f * (3.0 * (p1 - p2) + p3 - p0)));
+        }
+
+        fn interpolateSinc(self: Self, position: T, ch: usize, cutoff: T) T {
+            // Widening the kernel while lowering its cutoff band-limits the signal when downsampling
+            const half_width: T = sinc_zero_crossings / cutoff;
+            const first: isize = @intFromFloat(@floor(position - half_width) + 1.0);
+            const last: isize = @intFromFloat(@floor(position + half_width));
+
+            var result: T = 0.0;
+            var k: isize = first;
+            while (k <= last) : (k += 1) {
+                const t: T = position - @as(T, @floatFromInt(k));
+                if (@abs(t) >= half_width)
+                    continue;
+
+                const x: T = std.math.pi * cutoff * t;
+                const kernel: T = if (x == 0.0) 1.0 else @sin(x) / x;
+
+                // Blackman window
+                const u: T = t / half_width;
+                const blackman: T = 0.42 + 0.5 * @cos(std.math.pi * u) + 0.08 * @cos(2.0 * std.math.pi * u);
+
+                result += self.frameSample(k, ch, false) * cutoff * kernel * blackman;
+            }
+
+            return result;
+        }
+
         /// Reads wave data from a file using the specified format.
         ///
+        /// Headerless `.raw` data must be read with `readRaw` instead, which takes its format.
+        ///
         /// ## Parameters
         /// - `file_extension`: The file format to use for decoding (e.g. `.wav`)
         /// - `allocator`: Memory allocator for sample data
@@ -424,7 +1075,44 @@ pub fn inner(comptime T: type) type {
             allocator: std.mem.Allocator,
             reader: anytype,
         ) anyerror!Self {
-            const lowlevel_wave = try file_extension.read(allocator, reader);
+            const lowlevel_wave = try file_extension.read(allocator, reader);
+
+            return Self{
+                .samples = lowlevel_wave.samples,
+                .allocator = allocator,
+                .sample_rate = lowlevel_wave.sample_rate,
+                .channels = lowlevel_wave.channels,
+            };
+        }
+
+        /// Reads headerless samples, whose encoding, byte order, sample rate and channel count
+        /// are given in the options, as nothing in the data describes them.
+        ///
+        /// ## Parameters
+        /// - `allocator`: Memory allocator for sample data
+        /// - `reader`: A reader interface for reading the raw bytes
+        /// - `options`: Format of the samples
+        ///
+        /// ## Returns
+        /// A new Wave instance containing the samples
+        ///
+        /// ## Errors
+        /// Returns errors from the raw decoder or allocation failures
+        ///
+        /// ## Example
+        /// ```zig
+        /// const wave = try Wave(f64).readRaw(allocator, &reader, .{
+        ///     .encoding = .i16,
+        ///     .sample_rate = 44100,
+        ///     .channels = 2,
+        /// });
+        /// ```
+        pub fn readRaw(
+            allocator: std.mem.Allocator,
+            reader: anytype,
+            options: LowLevelInterfaces.readRawOptions,
+        ) anyerror!Self {
+            const lowlevel_wave = try LowLevelInterfaces.readRaw(allocator, reader, options);
 
             return Self{
                 .samples = lowlevel_wave.samples,
@@ -797,6 +1485,199 @@ pub fn inner(comptime T: type) type {
             try testing.expectApproxEqAbs(result.samples[2], 0.1250505236945281, 0.00001);
         }
 
+        test "mixAll with mix modes" {
+            const allocator = testing.allocator;
+
+            const wave1 = try Self.init(&[_]T{ 1.0, 0.5 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave1.deinit();
+            const wave2 = try Self.init(&[_]T{ 1.0, -0.5 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave2.deinit();
+            const wave3 = try Self.init(&[_]T{ 1.0, 0.25 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave3.deinit();
+
+            const waves = [_]Self{ wave1, wave2, wave3 };
+
+            const sum = try Self.mixAll(&waves, .{ .mode = .sum });
+            defer sum.deinit();
+            try testing.expectEqualSlices(T, sum.samples, &.{ 3.0, 0.25 });
+
+            const average = try Self.mixAll(&waves, .{ .mode = .average });
+            defer average.deinit();
+            try testing.expectApproxEqAbs(average.samples[0], 1.0, 0.000001);
+            try testing.expectApproxEqAbs(average.samples[1], 0.25 / 3.0, 0.000001);
+
+            const headroom = try Self.mixAll(&waves, .{ .mode = .headroom });
+            defer headroom.deinit();
+            try testing.expectApproxEqAbs(headroom.samples[0], 3.0 / @sqrt(@as(T, 3.0)), 0.000001);
+
+            const soft_clip = try Self.mixAll(&waves, .{ .mode = .soft_clip });
+            defer soft_clip.deinit();
+            try testing.expect(soft_clip.samples[0] < 1.0);
+            try testing.expectApproxEqAbs(soft_clip.samples[0], 0.9950547536867305, 0.000001);
+            try testing.expectApproxEqAbs(soft_clip.samples[1], 0.24491866240370913, 0.000001);
+
+            const weighted = try Self.mixAll(&waves, .{ .mode = .{ .weighted = &.{ 0.5, 0.0, 2.0 } } });
+            defer weighted.deinit();
+            try testing.expectEqualSlices(T, weighted.samples, &.{ 2.5, 0.75 });
+        }
+
+        test "mixAt overlays a shorter wave" {
+            const allocator = testing.allocator;
+
+            const long = try Self.init(&[_]T{ 1.0, 1.0, 1.0, 1.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer long.deinit();
+            const short = try Self.init(&[_]T{ 0.5, 0.5 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer short.deinit();
+
+            const inside = try long.mixAt(short, 1, .{});
+            defer inside.deinit();
+            try testing.expectEqualSlices(T, inside.samples, &.{ 1.0, 1.5, 1.5, 1.0 });
+
+            const overhanging = try long.mixAt(short, 3, .{});
+            defer overhanging.deinit();
+            try testing.expectEqualSlices(T, overhanging.samples, &.{ 1.0, 1.0, 1.0, 1.5, 0.5 });
+
+            // A gap of silence between the waves
+            const after = try short.mixAt(short, 3, .{});
+            defer after.deinit();
+            try testing.expectEqualSlices(T, after.samples, &.{ 0.5, 0.5, 0.0, 0.5, 0.5 });
+
+            // Only the overlap is averaged over both waves
+            const averaged = try long.mixAt(short, 0, .{ .mode = .average });
+            defer averaged.deinit();
+            try testing.expectEqualSlices(T, averaged.samples, &.{ 0.75, 0.75, 1.0, 1.0 });
+
+            const headroom = try short.mixAt(short, 1, .{ .mode = .headroom });
+            defer headroom.deinit();
+            try testing.expectApproxEqAbs(headroom.samples[0], 0.5, 0.000001);
+            try testing.expectApproxEqAbs(headroom.samples[1], std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(headroom.samples[2], 0.5, 0.000001);
+        }
+
+        test "mixAt counts the offset in frames" {
+            const allocator = testing.allocator;
+
+            const first = try Self.init(&[_]T{ 1.0, -1.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer first.deinit();
+
+            const result = try first.mixAt(first, 1, .{});
+            defer result.deinit();
+
+            try testing.expectEqual(result.channels, 2);
+            try testing.expectEqualSlices(T, result.samples, &.{ 1.0, -1.0, 1.0, -1.0 });
+        }
+
+        test "mixAt rejects mismatched waves" {
+            const allocator = testing.allocator;
+
+            const mono = try Self.init(&[_]T{1.0}, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer mono.deinit();
+            const stereo = try Self.init(&[_]T{ 1.0, 1.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer stereo.deinit();
+            const other_rate = try Self.init(&[_]T{1.0}, allocator, .{
+                .sample_rate = 48000,
+                .channels = 1,
+            });
+            defer other_rate.deinit();
+
+            try testing.expectError(error.ChannelMismatch, mono.mixAt(stereo, 0, .{}));
+            try testing.expectError(error.SampleRateMismatch, mono.mixAt(other_rate, 0, .{}));
+            try testing.expectError(error.LengthMismatch, mono.mixAt(mono, 0, .{ .mode = .{ .weighted = &.{1.0} } }));
+        }
+
+        test "mixAt rejects offsets which overflow" {
+            const allocator = testing.allocator;
+
+            const stereo = try Self.init(&[_]T{ 1.0, 1.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer stereo.deinit();
+
+            try testing.expectError(error.InvalidRange, stereo.mixAt(stereo, std.math.maxInt(usize), .{}));
+            try testing.expectError(error.InvalidRange, stereo.mixAt(stereo, std.math.maxInt(usize) / 2, .{}));
+        }
+
+        test "mix rejects mismatched waves" {
+            const allocator = testing.allocator;
+
+            const mono = try Self.init(&[_]T{ 1.0, 1.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer mono.deinit();
+            const stereo = try Self.init(&[_]T{ 1.0, 1.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer stereo.deinit();
+            const other_rate = try Self.init(&[_]T{ 1.0, 1.0 }, allocator, .{
+                .sample_rate = 48000,
+                .channels = 1,
+            });
+            defer other_rate.deinit();
+            const short = try Self.init(&[_]T{1.0}, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer short.deinit();
+
+            try testing.expectError(error.ChannelMismatch, mono.mix(stereo, .{}));
+            try testing.expectError(error.SampleRateMismatch, mono.mix(other_rate, .{}));
+            try testing.expectError(error.LengthMismatch, mono.mix(short, .{}));
+            try testing.expectError(error.LengthMismatch, Self.mixAll(&[_]Self{ mono, mono }, .{ .mode = .{ .weighted = &.{1.0} } }));
+            try testing.expectError(error.InvalidRange, Self.mixAll(&[_]Self{}, .{}));
+        }
+
+        test "mixAll folds with the mixer function" {
+            const allocator = testing.allocator;
+            const multiply = struct {
+                fn f(left: T, right: T) T {
+                    return left * right;
+                }
+            }.f;
+
+            const wave1 = try Self.init(&[_]T{ 2.0, 3.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave1.deinit();
+            const wave2 = try Self.init(&[_]T{ 4.0, 5.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave2.deinit();
+
+            const result = try Self.mixAll(&[_]Self{ wave1, wave2, wave2 }, .{ .mixer = multiply });
+            defer result.deinit();
+
+            try testing.expectEqualSlices(T, result.samples, &.{ 32.0, 75.0 });
+        }
+
         test "fill_zero_to_end" {
             const allocator = testing.allocator;
             const generator = struct {
@@ -838,6 +1719,26 @@ pub fn inner(comptime T: type) type {
             try testing.expectApproxEqAbs(filled_wave.samples[44099], 0.0, 0.00001);
         }
 
+        test "fill_zero_to_end with any range" {
+            const allocator = testing.allocator;
+            const wave = try Self.init(&[_]T{ 1.0, 2.0, 3.0, 4.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            const extended: Self = try wave.fill_zero_to_end(3, 6);
+            defer extended.deinit();
+            try testing.expectEqualSlices(T, extended.samples, &.{ 1.0, 2.0, 3.0, 0.0, 0.0, 0.0 });
+
+            const truncated: Self = try wave.fill_zero_to_end(1, 2);
+            defer truncated.deinit();
+            try testing.expectEqualSlices(T, truncated.samples, &.{ 1.0, 0.0 });
+
+            try testing.expectError(error.InvalidRange, wave.fill_zero_to_end(5, 6));
+            try testing.expectError(error.InvalidRange, wave.fill_zero_to_end(3, 2));
+        }
+
         test "filter_with" {
             const allocator = testing.allocator;
             const samples: []const T = &[_]T{};
@@ -999,6 +1900,299 @@ pub fn inner(comptime T: type) type {
             try testing.expect(wave.samples.len > 0);
         }
 
+        test "resample keeps the frequency of a sine" {
+            const allocator = testing.allocator;
+            const rates = [_][2]u32{ .{ 44100, 48000 }, .{ 48000, 44100 }, .{ 44100, 22050 } };
+
+            for (rates) |rate| {
+                const source_rate: T = @floatFromInt(rate[0]);
+                const target_rate: T = @floatFromInt(rate[1]);
+
+                // 0.1 seconds of a 440 Hz sine
+                const samples: []T = try allocator.alloc(T, rate[0] / 10);
+                defer allocator.free(samples);
+                for (0..samples.len) |i| {
+                    samples[i] = @sin(2.0 * std.math.pi * 440.0 * @as(T, @floatFromInt(i)) / source_rate);
+                }
+
+                const wave = try Self.init(samples, allocator, .{
+                    .sample_rate = rate[0],
+                    .channels = 1,
+                });
+                defer wave.deinit();
+
+                for ([_]ResampleQuality{ .linear, .cubic, .sinc }) |quality| {
+                    const resampled = try wave.resample(rate[1], quality);
+                    defer resampled.deinit();
+
+                    try testing.expectEqual(resampled.sample_rate, rate[1]);
+                    try testing.expectEqual(resampled.channels, 1);
+                    try testing.expectEqual(resampled.samples.len, rate[1] / 10);
+
+                    const tolerance: T = switch (quality) {
+                        .linear => 0.001,
+                        .cubic, .sinc => 0.0001,
+                    };
+
+                    // The edges are skipped, because the sinc kernel reaches outside of the wave
+                    for (64..resampled.samples.len - 64) |j| {
+                        const expected: T = @sin(2.0 * std.math.pi * 440.0 * @as(T, @floatFromInt(j)) / target_rate);
+                        try testing.expectApproxEqAbs(expected, resampled.samples[j], tolerance);
+                    }
+                }
+            }
+        }
+
+        test "resample with sinc removes frequencies above the new Nyquist frequency" {
+            const allocator = testing.allocator;
+
+            // A 15 kHz sine can't be represented at 22050 Hz
+            var samples: [4410]T = undefined;
+            for (0..samples.len) |i| {
+                samples[i] = @sin(2.0 * std.math.pi * 15000.0 * @as(T, @floatFromInt(i)) / 44100.0);
+            }
+
+            const wave = try Self.init(samples[0..], allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            const resampled = try wave.resample(22050, .sinc);
+            defer resampled.deinit();
+
+            for (resampled.samples[64 .. resampled.samples.len - 64]) |sample| {
+                try testing.expect(@abs(sample) < 0.001);
+            }
+        }
+
+        test "resample keeps channels apart" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0 };
+
+            const wave = try Self.init(samples, allocator, .{
+                .sample_rate = 8000,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            const resampled = try wave.resample(16000, .cubic);
+            defer resampled.deinit();
+
+            try testing.expectEqual(resampled.channels, 2);
+            try testing.expectEqual(resampled.samples.len, 16);
+            for (0..8) |j| {
+                try testing.expectApproxEqAbs(resampled.samples[j * 2], 1.0, 0.000001);
+                try testing.expectApproxEqAbs(resampled.samples[j * 2 + 1], -1.0, 0.000001);
+            }
+        }
+
+        test "resample to the same sample rate" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 2.0, 3.0 };
+
+            const wave = try Self.init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer wave.deinit();
+
+            const resampled = try wave.resample(44100, .sinc);
+            defer resampled.deinit();
+
+            try testing.expectEqualSlices(T, wave.samples, resampled.samples);
+        }
+
+        test "separate keeps frames together" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, -1.0, 2.0, -2.0, 3.0, -3.0 };
+            const original = try Self.init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer original.deinit();
+
+            const result = try original.separate(.{
+                .allocator = allocator,
+                .separate_point = 1,
+            });
+            defer result.initial.deinit();
+            defer result.terminal.deinit();
+
+            try testing.expectEqualSlices(T, result.initial.samples, &.{ 1.0, -1.0 });
+            try testing.expectEqualSlices(T, result.terminal.samples, &.{ 2.0, -2.0, 3.0, -3.0 });
+
+            try testing.expectError(error.TooBigSeparatePoint, original.separate(.{
+                .allocator = allocator,
+                .separate_point = 4,
+            }));
+        }
+
+        test "toMono" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 0.0, 0.5, 0.5, -1.0, 1.0 };
+            const wave = try Self.init(samples, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            const average = try wave.toMono(.average);
+            defer average.deinit();
+            try testing.expectEqual(average.channels, 1);
+            try testing.expectEqualSlices(T, average.samples, &.{ 0.5, 0.5, 0.0 });
+
+            const sum = try wave.toMono(.sum);
+            defer sum.deinit();
+            try testing.expectEqualSlices(T, sum.samples, &.{ 1.0, 1.0, 0.0 });
+
+            const equal_power = try wave.toMono(.equal_power);
+            defer equal_power.deinit();
+            try testing.expectApproxEqAbs(equal_power.samples[0], std.math.sqrt1_2, 0.000001);
+        }
+
+        test "toStereo" {
+            const allocator = testing.allocator;
+
+            const mono = try Self.init(&[_]T{ 1.0, 2.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer mono.deinit();
+
+            const from_mono = try mono.toStereo();
+            defer from_mono.deinit();
+            try testing.expectEqual(from_mono.channels, 2);
+            try testing.expectEqualSlices(T, from_mono.samples, &.{ 1.0, 1.0, 2.0, 2.0 });
+
+            const surround = try Self.init(&[_]T{ 1.0, 2.0, 3.0, 4.0, 5.0, 6.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 3,
+            });
+            defer surround.deinit();
+
+            const from_surround = try surround.toStereo();
+            defer from_surround.deinit();
+            try testing.expectEqual(from_surround.channels, 2);
+            try testing.expectApproxEqAbs(from_surround.samples[0], 1.0 + 3.0 * std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(from_surround.samples[1], 2.0 + 3.0 * std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(from_surround.samples[2], 4.0 + 6.0 * std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(from_surround.samples[3], 5.0 + 6.0 * std.math.sqrt1_2, 0.000001);
+
+            // 5.1: the low-frequency effects channel is dropped
+            const five_one = try Self.init(&[_]T{ 1.0, 1.0, 0.0, 1.0, 1.0, 0.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 6,
+            });
+            defer five_one.deinit();
+
+            const from_five_one = try five_one.toStereo();
+            defer from_five_one.deinit();
+            try testing.expectApproxEqAbs(from_five_one.samples[0], 1.0 + std.math.sqrt1_2, 0.000001);
+            try testing.expectApproxEqAbs(from_five_one.samples[1], 1.0, 0.000001);
+
+            const empty: Self = .{
+                .samples = &.{},
+                .allocator = allocator,
+                .sample_rate = 44100,
+                .channels = 0,
+            };
+            try testing.expectError(error.InvalidRange, empty.toStereo());
+        }
+
+        test "upmix" {
+            const allocator = testing.allocator;
+            const wave = try Self.init(&[_]T{ 1.0, 2.0, 3.0, 4.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            const upmixed = try wave.upmix(3);
+            defer upmixed.deinit();
+            try testing.expectEqual(upmixed.channels, 3);
+            try testing.expectEqualSlices(T, upmixed.samples, &.{ 1.0, 2.0, 0.0, 3.0, 4.0, 0.0 });
+
+            try testing.expectError(error.InvalidRange, wave.upmix(1));
+        }
+
+        test "selectChannel" {
+            const allocator = testing.allocator;
+            const wave = try Self.init(&[_]T{ 1.0, 2.0, 3.0, 4.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer wave.deinit();
+
+            const right = try wave.selectChannel(1);
+            defer right.deinit();
+            try testing.expectEqual(right.channels, 1);
+            try testing.expectEqualSlices(T, right.samples, &.{ 2.0, 4.0 });
+
+            try testing.expectError(error.InvalidRange, wave.selectChannel(2));
+        }
+
+        test "splitChannels & interleave" {
+            const allocator = testing.allocator;
+            const samples: []const T = &[_]T{ 1.0, 2.0, 3.0, 4.0, 5.0, 6.0 };
+            const wave = try Self.init(samples, allocator, .{
+                .sample_rate = 48000,
+                .channels = 3,
+            });
+            defer wave.deinit();
+
+            const channels = try wave.splitChannels();
+            defer {
+                for (channels) |channel| channel.deinit();
+                allocator.free(channels);
+            }
+
+            try testing.expectEqual(channels.len, 3);
+            try testing.expectEqualSlices(T, channels[0].samples, &.{ 1.0, 4.0 });
+            try testing.expectEqualSlices(T, channels[1].samples, &.{ 2.0, 5.0 });
+            try testing.expectEqualSlices(T, channels[2].samples, &.{ 3.0, 6.0 });
+
+            const rebuilt = try Self.interleave(channels, allocator);
+            defer rebuilt.deinit();
+
+            try testing.expectEqual(rebuilt.sample_rate, 48000);
+            try testing.expectEqual(rebuilt.channels, 3);
+            try testing.expectEqualSlices(T, rebuilt.samples, samples);
+        }
+
+        test "interleave rejects incompatible waves" {
+            const allocator = testing.allocator;
+
+            const short = try Self.init(&[_]T{1.0}, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer short.deinit();
+
+            const long = try Self.init(&[_]T{ 1.0, 2.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 1,
+            });
+            defer long.deinit();
+
+            const other_rate = try Self.init(&[_]T{1.0}, allocator, .{
+                .sample_rate = 48000,
+                .channels = 1,
+            });
+            defer other_rate.deinit();
+
+            const stereo = try Self.init(&[_]T{ 1.0, 2.0 }, allocator, .{
+                .sample_rate = 44100,
+                .channels = 2,
+            });
+            defer stereo.deinit();
+
+            try testing.expectError(error.LengthMismatch, Self.interleave(&[_]Self{ short, long }, allocator));
+            try testing.expectError(error.SampleRateMismatch, Self.interleave(&[_]Self{ short, other_rate }, allocator));
+            try testing.expectError(error.ChannelMismatch, Self.interleave(&[_]Self{ short, stereo }, allocator));
+            try testing.expectError(error.InvalidRange, Self.interleave(&[_]Self{}, allocator));
+        }
+
         test "separate func separates a Wave" {
             const allocator = testing.allocator;
             const samples: []const T = &[_]T{ 1.0, 2.0, 3.0, 4.0, 5.0 };
@@ -70,3 +70,67 @@ fn generate_soundless_data(length: usize, allocator: std.mem.Allocator) ![]const
 
     return result;
 }
+
+fn sine_instrument(note: u7, velocity: u7, duration_samples: usize, allocator: std.mem.Allocator) !Wave(f64) {
+    return lightmix.Oscillator(f64).sine(allocator, .{
+        .frequency = 440.0 * std.math.pow(f64, 2.0, (@as(f64, @floatFromInt(note)) - 69.0) / 12.0),
+        .amplitude = @as(f64, @floatFromInt(velocity)) / 127.0,
+        .duration = @as(f64, @floatFromInt(duration_samples)) / 44100.0,
+        .sample_rate = 44100,
+    });
+}
+
+test "Compose a MIDI file" {
+    const allocator = std.testing.allocator;
+
+    var reader = std.Io.Reader.fixed(@embedFile("./assets/melody.mid"));
+    const midi = try lightmix.Midi(f64).read(allocator, &reader);
+    defer midi.deinit();
+
+    // C4, E4 and G4 at 120 BPM with 96 ticks per quarter note
+    try std.testing.expectEqual(midi.notes.len, 3);
+    try std.testing.expectEqual(midi.notes[1].key, 64);
+    try std.testing.expectEqual(midi.notes[1].velocity, 80);
+    try std.testing.expectEqual(midi.notes[2].duration, 192);
+    try std.testing.expectEqual(midi.notes[2].track, 1);
+
+    var composer = Composer(f64).init(allocator, .{
+        .sample_rate = 44100,
+        .channels = 1,
+    });
+    defer composer.deinit();
+
+    try composer.appendMidi(midi, sine_instrument, .{});
+    try std.testing.expectEqual(composer.info.len, 3);
+    try std.testing.expectEqual(composer.info[2].start_point, 44100);
+
+    const result = try composer.finalize(.{});
+    defer result.deinit();
+
+    // The last note ends after two seconds
+    try std.testing.expectEqual(result.samples.len, 88200);
+}
+
+test "Compose a chord arpeggio" {
+    const allocator = std.testing.allocator;
+
+    // A4, C5, E5 and C5, an 8th note each at 120 BPM
+    const chord = try lightmix.Chord(f64).parse("Am", 4);
+    const arpeggio = try chord.arpeggiate(allocator, .{ .pattern = .up_down, .count = 4 });
+    defer arpeggio.deinit();
+
+    var composer = Composer(f64).init(allocator, .{
+        .sample_rate = 44100,
+        .channels = 1,
+    });
+    defer composer.deinit();
+
+    try composer.appendMidi(arpeggio, sine_instrument, .{});
+    try std.testing.expectEqual(composer.info.len, 4);
+    try std.testing.expectEqual(composer.info[3].start_point, 33075);
+
+    const result = try composer.finalize(.{});
+    defer result.deinit();
+
+    try std.testing.expectEqual(result.samples.len, 44100);
+}
@@ -29,3 +29,225 @@ test "read sine.wav" {
     };
     try std.testing.expectEqualSlices(f64, expected_samples, sine.samples[0..16]);
 }
+
+test "read sine.aiff" {
+    const allocator = std.testing.allocator;
+
+    var wav_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.wav"));
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    var aiff_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.aiff"));
+    const aiff = try Wave(f64).read(.aiff, allocator, &aiff_reader);
+    defer aiff.deinit();
+
+    // sine.aiff holds the first 440 samples of sine.wav
+    try std.testing.expectEqual(aiff.sample_rate, 44100);
+    try std.testing.expectEqual(aiff.channels, 1);
+    try std.testing.expectEqualSlices(f64, wav.samples[0..440], aiff.samples);
+}
+
+test "AIFF files round-trip" {
+    const allocator = std.testing.allocator;
+    const fixtures = [_]struct {
+        bytes: []const u8,
+        options: Wave(f64).LowLevelInterfaces.writeAiffOptions,
+        tolerance: f64,
+    }{
+        .{ .bytes = @embedFile("./assets/sine_8bit.aiff"), .options = .{ .bits = 8, .format_code = .pcm }, .tolerance = 0.01 },
+        .{ .bytes = @embedFile("./assets/sine.aiff"), .options = .{ .bits = 16, .format_code = .pcm }, .tolerance = 0.0 },
+        .{ .bytes = @embedFile("./assets/sine_24bit.aiff"), .options = .{ .bits = 24, .format_code = .pcm }, .tolerance = 0.0001 },
+        .{ .bytes = @embedFile("./assets/sine_32bit.aiff"), .options = .{ .bits = 32, .format_code = .pcm }, .tolerance = 0.0001 },
+        .{ .bytes = @embedFile("./assets/sine_float32.aifc"), .options = .{ .bits = 32, .format_code = .ieee_float }, .tolerance = 0.000001 },
+        .{ .bytes = @embedFile("./assets/sine_float64.aifc"), .options = .{ .bits = 64, .format_code = .ieee_float }, .tolerance = 0.0 },
+    };
+
+    var wav_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.wav"));
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    for (fixtures) |fixture| {
+        var reader = std.Io.Reader.fixed(fixture.bytes);
+        const wave = try Wave(f64).read(.aiff, allocator, &reader);
+        defer wave.deinit();
+
+        try std.testing.expectEqual(wave.sample_rate, 44100);
+        try std.testing.expectEqual(wave.channels, 1);
+        for (wav.samples[0..440], wave.samples) |expected, actual| {
+            try std.testing.expectApproxEqAbs(expected, actual, fixture.tolerance);
+        }
+
+        var writer = std.Io.Writer.Allocating.init(allocator);
+        defer writer.deinit();
+        try wave.write(.aiff, &writer.writer, fixture.options);
+
+        try std.testing.expectEqualSlices(u8, fixture.bytes, writer.written());
+    }
+}
+
+test "read sine.flac" {
+    const allocator = std.testing.allocator;
+
+    var wav_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.wav"));
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    // Both hold the first 8820 samples of sine.wav, with LPC and fixed predictors
+    for ([_][]const u8{ @embedFile("./assets/sine.flac"), @embedFile("./assets/sine_level0.flac") }) |bytes| {
+        var reader = std.Io.Reader.fixed(bytes);
+        const flac = try Wave(f64).read(.flac, allocator, &reader);
+        defer flac.deinit();
+
+        try std.testing.expectEqual(flac.sample_rate, 44100);
+        try std.testing.expectEqual(flac.channels, 1);
+        try std.testing.expectEqualSlices(f64, wav.samples[0..8820], flac.samples);
+    }
+}
+
+test "read sine_variable.flac" {
+    const allocator = std.testing.allocator;
+
+    var wav_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.wav"));
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    // The first 8820 samples of sine.wav, after VORBIS_COMMENT, APPLICATION and PADDING blocks,
+    // in blocks from 1 to 4096 samples long, whose shortest ones have wasted bits
+    var reader = std.Io.Reader.fixed(@embedFile("./assets/sine_variable.flac"));
+    const flac = try Wave(f64).read(.flac, allocator, &reader);
+    defer flac.deinit();
+
+    try std.testing.expectEqual(flac.sample_rate, 44100);
+    try std.testing.expectEqual(flac.channels, 1);
+    try std.testing.expectEqualSlices(f64, wav.samples[0..8820], flac.samples);
+}
+
+test "FLAC files round-trip" {
+    const allocator = std.testing.allocator;
+
+    var reader = std.Io.Reader.fixed(@embedFile("./assets/sine_level0.flac"));
+    const wave = try Wave(f64).read(.flac, allocator, &reader);
+    defer wave.deinit();
+
+    // Compression level 0 uses integer math only, so the encoder output is reproducible
+    var level0 = std.Io.Writer.Allocating.init(allocator);
+    defer level0.deinit();
+    try wave.write(.flac, &level0.writer, .{ .bits = 16, .compression_level = 0 });
+    try std.testing.expectEqualSlices(u8, @embedFile("./assets/sine_level0.flac"), level0.written());
+
+    var level8 = std.Io.Writer.Allocating.init(allocator);
+    defer level8.deinit();
+    try wave.write(.flac, &level8.writer, .{ .bits = 16, .compression_level = 8 });
+    try std.testing.expect(level8.written().len < level0.written().len);
+
+    var level8_reader = std.Io.Reader.fixed(level8.written());
+    const decoded = try Wave(f64).read(.flac, allocator, &level8_reader);
+    defer decoded.deinit();
+    try std.testing.expectEqualSlices(f64, wave.samples, decoded.samples);
+}
+
+test "read sine.ogg" {
+    const allocator = std.testing.allocator;
+
+    var wav_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.wav"));
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    // The first 8820 samples of sine.wav, with long and short blocks
+    var reader = std.Io.Reader.fixed(@embedFile("./assets/sine.ogg"));
+    const ogg = try Wave(f64).read(.ogg, allocator, &reader);
+    defer ogg.deinit();
+
+    try std.testing.expectEqual(ogg.sample_rate, 44100);
+    try std.testing.expectEqual(ogg.channels, 1);
+    try std.testing.expectEqual(ogg.samples.len, 8820);
+
+    // Decoded by the lewton crate
+    const expected = [_]f64{
+        0.0035184962, 0.053148016, 0.1025781,  0.15162648,
+        0.19999918,   0.24763791,  0.2944257,  0.33974266,
+        0.38417476,   0.4266441,   0.46779644, 0.50699675,
+        0.54409295,   0.57932216,  0.6119406,  0.64241356,
+    };
+    for (expected, ogg.samples[0..expected.len]) |e, actual| {
+        try std.testing.expectApproxEqAbs(e, actual, 0.00001);
+    }
+
+    // Vorbis is lossy, but keeps close to the source
+    for (wav.samples[0..8820], ogg.samples) |source, actual| {
+        try std.testing.expectApproxEqAbs(source, actual, 0.03);
+    }
+}
+
+test "read sine_stereo.ogg" {
+    const allocator = std.testing.allocator;
+
+    var wav_reader = std.Io.Reader.fixed(@embedFile("./assets/sine.wav"));
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    // sine.wav on the left, and inverted at half amplitude on the right, with channel coupling
+    var reader = std.Io.Reader.fixed(@embedFile("./assets/sine_stereo.ogg"));
+    const ogg = try Wave(f64).read(.ogg, allocator, &reader);
+    defer ogg.deinit();
+
+    try std.testing.expectEqual(ogg.sample_rate, 44100);
+    try std.testing.expectEqual(ogg.channels, 2);
+    try std.testing.expectEqual(ogg.samples.len, 8820 * 2);
+
+    // Decoded by the lewton crate
+    const expected_right = [_]f64{
+        0.0021420268, -0.022981383, -0.04801085, -0.07286463,
+        -0.097403504, -0.12157068,  -0.14532775, -0.16835918,
+        -0.19096732,  -0.21258031,  -0.23353392, -0.25352088,
+        -0.2724678,   -0.29048052,  -0.30720362, -0.32283872,
+    };
+    for (expected_right, 0..) |e, i| {
+        try std.testing.expectApproxEqAbs(e, ogg.samples[i * 2 + 1], 0.00001);
+    }
+
+    for (wav.samples[0..8820], 0..) |source, i| {
+        try std.testing.expectApproxEqAbs(source, ogg.samples[i * 2], 0.03);
+        try std.testing.expectApproxEqAbs(-0.5 * source, ogg.samples[i * 2 + 1], 0.03);
+    }
+}
+
+test "corrupted Ogg files" {
+    const allocator = std.testing.allocator;
+
+    var bytes = @embedFile("./assets/sine.ogg").*;
+    bytes[bytes.len - 100] ^= 0x01;
+
+    var reader = std.Io.Reader.fixed(&bytes);
+    try std.testing.expectError(error.ChecksumMismatch, Wave(f64).read(.ogg, allocator, &reader));
+}
+
+test "raw PCM round-trip" {
+    const allocator = std.testing.allocator;
+    const bytes = @embedFile("./assets/sine.wav");
+
+    var wav_reader = std.Io.Reader.fixed(bytes);
+    const wav = try Wave(f64).read(.wav, allocator, &wav_reader);
+    defer wav.deinit();
+
+    // sine.wav holds 16-bit little-endian samples after a 44-byte header
+    var reader = std.Io.Reader.fixed(bytes[44..]);
+    const raw = try Wave(f64).readRaw(allocator, &reader, .{
+        .encoding = .i16,
+        .sample_rate = 44100,
+        .channels = 1,
+    });
+    defer raw.deinit();
+
+    try std.testing.expectEqual(raw.sample_rate, 44100);
+    try std.testing.expectEqual(raw.channels, 1);
+    try std.testing.expectEqualSlices(f64, wav.samples, raw.samples);
+
+    // Without a header, the format of the samples is unknown
+    try std.testing.expectError(error.MissingReadOptions, Wave(f64).read(.raw, allocator, &reader));
+
+    var writer = std.Io.Writer.Allocating.init(allocator);
+    defer writer.deinit();
+    try raw.write(.raw, &writer.writer, .{ .encoding = .i16 });
+    try std.testing.expectEqualSlices(u8, bytes[44..], writer.written());
+}